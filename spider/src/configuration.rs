@@ -3,8 +3,9 @@ use crate::features::chrome_common::RequestInterceptConfiguration;
 pub use crate::features::chrome_common::{
     AuthChallengeResponse, AuthChallengeResponseResponse, AutomationScripts, AutomationScriptsMap,
     CaptureScreenshotFormat, CaptureScreenshotParams, ClipViewport, ExecutionScripts,
-    ExecutionScriptsMap, ScreenShotConfig, ScreenshotParams, Viewport, WaitFor, WaitForDelay,
-    WaitForIdleNetwork, WaitForSelector, WebAutomation,
+    ExecutionScriptsMap, MhtmlConfig, PaperFormat, PdfConfig, PdfParams, ScreenShotConfig,
+    ScreenshotParams, Viewport, WaitFor, WaitForDelay, WaitForIdleNetwork, WaitForSelector,
+    WebAutomation,
 };
 pub use crate::features::openai_common::GPTConfigs;
 use crate::utils::get_domain_from_url;
@@ -53,6 +54,22 @@ pub struct ChromeEventTracker {
     pub responses: bool,
     /// Track the requests.
     pub requests: bool,
+    /// Record the network activity of the page into a HAR.
+    pub har: bool,
+    /// Capture the response bodies up to the amount of bytes in the HAR.
+    pub har_body_limit: Option<usize>,
+    /// Capture the responses matching the filter with the bodies.
+    pub response_capture: Option<crate::features::response_capture::ResponseCaptureConfig>,
+    /// Collect the console output, uncaught exceptions, browser logs and dialogs of the page.
+    pub console_logs: bool,
+    /// Print the page to a PDF.
+    pub pdf: Option<PdfConfig>,
+    /// Capture a MHTML snapshot of the page.
+    pub mhtml: Option<MhtmlConfig>,
+    /// Get the accessibility tree of the page.
+    pub accessibility_tree: bool,
+    /// Get the visible text of the page with the bounding boxes.
+    pub visible_text: bool,
 }
 
 #[cfg(feature = "chrome")]
@@ -62,8 +79,115 @@ impl ChromeEventTracker {
         ChromeEventTracker {
             requests,
             responses,
+            ..Default::default()
+        }
+    }
+
+    /// Record the network activity of the page into a HAR with the response bodies up to the amount of bytes.
+    pub fn with_har(mut self, har: bool, har_body_limit: Option<usize>) -> Self {
+        self.har = har;
+        self.har_body_limit = har_body_limit;
+        self
+    }
+
+    /// Capture the responses matching the filter with the bodies like the JSON of the XHR and fetch requests.
+    pub fn with_response_capture(
+        mut self,
+        response_capture: Option<crate::features::response_capture::ResponseCaptureConfig>,
+    ) -> Self {
+        self.response_capture = response_capture;
+        self
+    }
+
+    /// Collect the console output, uncaught exceptions, browser logs and dialogs of the page.
+    pub fn with_console_logs(mut self, console_logs: bool) -> Self {
+        self.console_logs = console_logs;
+        self
+    }
+
+    /// Print the page to a PDF.
+    pub fn with_pdf(mut self, pdf: Option<PdfConfig>) -> Self {
+        self.pdf = pdf;
+        self
+    }
+
+    /// Capture a MHTML snapshot of the page.
+    pub fn with_mhtml(mut self, mhtml: Option<MhtmlConfig>) -> Self {
+        self.mhtml = mhtml;
+        self
+    }
+
+    /// Get the accessibility tree of the page.
+    pub fn with_accessibility_tree(mut self, accessibility_tree: bool) -> Self {
+        self.accessibility_tree = accessibility_tree;
+        self
+    }
+
+    /// Get the visible text of the page with the bounding boxes.
+    pub fn with_visible_text(mut self, visible_text: bool) -> Self {
+        self.visible_text = visible_text;
+        self
+    }
+}
+
+#[cfg(feature = "chrome")]
+/// The chrome browser pool. The tabs are reused and the browsers are relaunched after a crash, a max amount of pages or a memory threshold.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrowserPoolConfig {
+    /// The amount of browsers to launch.
+    pub browsers: usize,
+    /// The max tabs open at once on each browser.
+    pub tabs_per_browser: usize,
+    /// Relaunch the browser after it handled the amount of pages.
+    pub max_pages_per_browser: Option<usize>,
+    /// Relaunch the browser once the JS heap of a tab passes the amount of bytes.
+    pub max_memory_bytes: Option<u64>,
+    /// The times a page is re-queued after the browser crashed while loading it.
+    pub max_requeue: u8,
+}
+
+#[cfg(feature = "chrome")]
+impl Default for BrowserPoolConfig {
+    fn default() -> Self {
+        Self {
+            browsers: 1,
+            tabs_per_browser: 10,
+            max_pages_per_browser: None,
+            max_memory_bytes: None,
+            max_requeue: 1,
+        }
+    }
+}
+
+#[cfg(feature = "chrome")]
+impl BrowserPoolConfig {
+    /// A new pool of browsers with the max tabs each.
+    pub fn new(browsers: usize, tabs_per_browser: usize) -> Self {
+        Self {
+            browsers,
+            tabs_per_browser,
+            ..Default::default()
         }
     }
+
+    /// Relaunch the browser after it handled the amount of pages.
+    pub fn with_max_pages_per_browser(mut self, max_pages_per_browser: Option<usize>) -> Self {
+        self.max_pages_per_browser = max_pages_per_browser;
+        self
+    }
+
+    /// Relaunch the browser once the JS heap of a tab passes the amount of bytes.
+    pub fn with_max_memory_bytes(mut self, max_memory_bytes: Option<u64>) -> Self {
+        self.max_memory_bytes = max_memory_bytes;
+        self
+    }
+
+    /// The times a page is re-queued after the browser crashed while loading it.
+    pub fn with_max_requeue(mut self, max_requeue: u8) -> Self {
+        self.max_requeue = max_requeue;
+        self
+    }
 }
 
 /// Determine allow proxy
@@ -148,6 +272,9 @@ pub struct Configuration {
     #[cfg(feature = "cookies")]
     /// Cookie string to use for network requests ex: "foo=bar; Domain=blog.spider"
     pub cookie_str: Box<String>,
+    #[cfg(feature = "storage_state")]
+    /// The cookies and web storage shared by the HTTP client and the chrome pages.
+    pub storage_state: Option<crate::features::storage_state::StorageStateHandle>,
     #[cfg(feature = "rquest")]
     /// The type of request emulation. This does nothing without the flag `sync` enabled.
     pub emulation: Option<rquest_util::Emulation>,
@@ -205,6 +332,9 @@ pub struct Configuration {
     /// Track the events made via chrome.
     #[cfg(feature = "chrome")]
     pub track_events: Option<ChromeEventTracker>,
+    /// Crawl with a pool of browsers reusing the tabs and relaunching crashed browsers.
+    #[cfg(feature = "chrome")]
+    pub browser_pool: Option<BrowserPoolConfig>,
     /// The auth challenge response. The 'chrome_intercept' flag is also required in order to intercept the response.
     pub auth_challenge_response: Option<AuthChallengeResponse>,
     /// The OpenAI configs to use to help drive the chrome browser. This does nothing without the 'openai' flag.
@@ -221,6 +351,11 @@ pub struct Configuration {
     /// Web automation scripts to run up to a duration of 60 seconds.
     #[cfg(feature = "chrome")]
     pub automation_scripts: Option<AutomationScripts>,
+    /// The login to run in chrome before the crawl and when a page redirects to the login url.
+    #[cfg(feature = "chrome")]
+    pub login_flow: Option<Box<crate::features::chrome_common::LoginFlow>>,
+    /// Follow the next pages of the listings with a next link, a load more button or a url template.
+    pub pagination: Option<Box<crate::features::pagination::Pagination>>,
     /// Use a shared queue strategy when crawling. This can scale workloads evenly that do not need priority.
     pub shared_queue: bool,
     /// Return the page links in the subscription channels. This does nothing without the flag `sync` enabled.
@@ -245,6 +380,8 @@ pub struct Configuration {
     pub concurrency_limit: Option<usize>,
     /// Normalize the html de-deplucating the content.
     pub normalize: bool,
+    /// Collect the embedded metadata of the pages. This does nothing without the flag `metadata` enabled.
+    pub metadata: bool,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -630,6 +767,25 @@ impl Configuration {
         self
     }
 
+    #[cfg(feature = "storage_state")]
+    /// Start the crawl from the cookies and web storage. The state is shared by the HTTP client and the chrome pages.
+    pub fn with_storage_state(
+        &mut self,
+        storage_state: Option<crate::features::storage_state::StorageState>,
+    ) -> &mut Self {
+        self.storage_state =
+            storage_state.map(crate::features::storage_state::StorageStateHandle::new);
+        self
+    }
+
+    #[cfg(feature = "storage_state")]
+    /// Load the cookies and web storage from the Playwright storage state file when it exists and save the state to it at the end of the crawl.
+    pub fn with_storage_state_file<P: Into<std::path::PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.storage_state =
+            Some(crate::features::storage_state::StorageStateHandle::from_file(path));
+        self
+    }
+
     #[cfg(feature = "chrome")]
     /// Set custom fingerprint ID for request. This does nothing without the `chrome` flag enabled.
     pub fn with_fingerprint(&mut self, fingerprint: bool) -> &mut Self {
@@ -1045,6 +1201,34 @@ impl Configuration {
         self
     }
 
+    #[cfg(not(feature = "chrome"))]
+    /// Log in with the automation steps on the login url before the crawl. This method does nothing if the `chrome` is not enabled.
+    pub fn with_login_flow(
+        &mut self,
+        _login_flow: Option<crate::features::chrome_common::LoginFlow>,
+    ) -> &mut Self {
+        self
+    }
+
+    #[cfg(feature = "chrome")]
+    /// Log in with the automation steps on the login url before the crawl. The cookies are shared with the chrome pages and the HTTP client and the login re-runs when a page redirects to the login url. This method does nothing if the `chrome` is not enabled.
+    pub fn with_login_flow(
+        &mut self,
+        login_flow: Option<crate::features::chrome_common::LoginFlow>,
+    ) -> &mut Self {
+        self.login_flow = login_flow.map(Box::new);
+        self
+    }
+
+    /// Follow the next pages of the listings. The next links and the url template pages are enqueued with HTTP and the next link or load more button is clicked with chrome capturing each state as a page.
+    pub fn with_pagination(
+        &mut self,
+        pagination: Option<crate::features::pagination::Pagination>,
+    ) -> &mut Self {
+        self.pagination = pagination.map(Box::new);
+        self
+    }
+
     /// Set a crawl budget per path with levels support /a/b/c or for all paths with "*". This does nothing without the `budget` flag enabled.
     pub fn with_budget(&mut self, budget: Option<hashbrown::HashMap<&str, u32>>) -> &mut Self {
         self.budget = match budget {
@@ -1111,6 +1295,12 @@ impl Configuration {
         self
     }
 
+    /// Collect the embedded metadata of the pages while streaming: title, description, canonical, OpenGraph, Twitter cards, JSON-LD, microdata, RDFa and hreflang alternates. This does nothing without the flag `metadata` enabled.
+    pub fn with_metadata(&mut self, metadata: bool) -> &mut Self {
+        self.metadata = metadata;
+        self
+    }
+
     #[cfg(not(feature = "chrome"))]
     /// Overrides default host system timezone with the specified one. This does nothing without the `chrome` flag enabled.
     pub fn with_timezone_id(&mut self, _timezone_id: Option<String>) -> &mut Self {
@@ -1150,6 +1340,110 @@ impl Configuration {
         self
     }
 
+    #[cfg(feature = "chrome")]
+    /// Crawl with a pool of browsers reusing the tabs and relaunching crashed browsers.
+    /// The pool is used by `crawl` and `scrape`. The `crawl_smart`, `crawl_chrome_send`, and sitemap crawls keep using a single browser.
+    pub fn with_browser_pool(&mut self, browser_pool: Option<BrowserPoolConfig>) -> &mut Self {
+        self.browser_pool = browser_pool;
+        self
+    }
+
+    /// Collect the console output, uncaught exceptions, browser logs and dialogs of each page into `Page::console_logs`. This does nothing without the `chrome` flag enabled.
+    #[cfg(not(feature = "chrome"))]
+    pub fn with_console_logs(&mut self, _console_logs: bool) -> &mut Self {
+        self
+    }
+
+    /// Collect the console output, uncaught exceptions, browser logs and dialogs of each page into `Page::console_logs`. Set `dismiss_dialogs` to false to log the dialogs the polyfill would replace. This does nothing without the `chrome` flag enabled.
+    #[cfg(feature = "chrome")]
+    pub fn with_console_logs(&mut self, console_logs: bool) -> &mut Self {
+        match self.track_events.as_mut() {
+            Some(tracker) => tracker.console_logs = console_logs,
+            _ if console_logs => {
+                self.track_events = Some(ChromeEventTracker::default().with_console_logs(true))
+            }
+            _ => (),
+        }
+        self
+    }
+
+    /// Print each page to a PDF stored on `Page::pdf_bytes` or saved to the output directory. This does nothing without the `chrome` flag enabled.
+    #[cfg(not(feature = "chrome"))]
+    pub fn with_pdf(&mut self, _pdf: Option<PdfConfig>) -> &mut Self {
+        self
+    }
+
+    /// Print each page to a PDF stored on `Page::pdf_bytes` or saved to the output directory. This does nothing without the `chrome` flag enabled.
+    #[cfg(feature = "chrome")]
+    pub fn with_pdf(&mut self, pdf: Option<PdfConfig>) -> &mut Self {
+        match self.track_events.as_mut() {
+            Some(tracker) => tracker.pdf = pdf,
+            _ if pdf.is_some() => {
+                self.track_events = Some(ChromeEventTracker::default().with_pdf(pdf))
+            }
+            _ => (),
+        }
+        self
+    }
+
+    /// Capture a single file MHTML snapshot of each page stored on `Page::mhtml_bytes` or saved to the output directory. This does nothing without the `chrome` flag enabled.
+    #[cfg(not(feature = "chrome"))]
+    pub fn with_mhtml(&mut self, _mhtml: Option<MhtmlConfig>) -> &mut Self {
+        self
+    }
+
+    /// Capture a single file MHTML snapshot of each page stored on `Page::mhtml_bytes` or saved to the output directory. This does nothing without the `chrome` flag enabled.
+    #[cfg(feature = "chrome")]
+    pub fn with_mhtml(&mut self, mhtml: Option<MhtmlConfig>) -> &mut Self {
+        match self.track_events.as_mut() {
+            Some(tracker) => tracker.mhtml = mhtml,
+            _ if mhtml.is_some() => {
+                self.track_events = Some(ChromeEventTracker::default().with_mhtml(mhtml))
+            }
+            _ => (),
+        }
+        self
+    }
+
+    /// Get the accessibility tree of each page into `Page::accessibility_tree`. This does nothing without the `chrome` flag enabled.
+    #[cfg(not(feature = "chrome"))]
+    pub fn with_accessibility_tree(&mut self, _accessibility_tree: bool) -> &mut Self {
+        self
+    }
+
+    /// Get the accessibility tree of each page into `Page::accessibility_tree`. This does nothing without the `chrome` flag enabled.
+    #[cfg(feature = "chrome")]
+    pub fn with_accessibility_tree(&mut self, accessibility_tree: bool) -> &mut Self {
+        match self.track_events.as_mut() {
+            Some(tracker) => tracker.accessibility_tree = accessibility_tree,
+            _ if accessibility_tree => {
+                self.track_events =
+                    Some(ChromeEventTracker::default().with_accessibility_tree(true))
+            }
+            _ => (),
+        }
+        self
+    }
+
+    /// Get the visible text of each page with the bounding boxes into `Page::visible_text`. The hidden elements are left out. This does nothing without the `chrome` flag enabled.
+    #[cfg(not(feature = "chrome"))]
+    pub fn with_visible_text(&mut self, _visible_text: bool) -> &mut Self {
+        self
+    }
+
+    /// Get the visible text of each page with the bounding boxes into `Page::visible_text`. The hidden elements are left out. This does nothing without the `chrome` flag enabled.
+    #[cfg(feature = "chrome")]
+    pub fn with_visible_text(&mut self, visible_text: bool) -> &mut Self {
+        match self.track_events.as_mut() {
+            Some(tracker) => tracker.visible_text = visible_text,
+            _ if visible_text => {
+                self.track_events = Some(ChromeEventTracker::default().with_visible_text(true))
+            }
+            _ => (),
+        }
+        self
+    }
+
     /// Set the chrome screenshot configuration. This does nothing without the `chrome` flag enabled.
     #[cfg(not(feature = "chrome"))]
     pub fn with_screenshot(&mut self, _screenshot_config: Option<ScreenShotConfig>) -> &mut Self {
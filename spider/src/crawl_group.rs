@@ -0,0 +1,397 @@
+use crate::configuration::{Configuration, RedirectPolicy};
+use crate::page::Page;
+use crate::website::Website;
+use crate::Client;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::task::JoinSet;
+
+/// A page crawled by a website in the group tagged with the site id.
+#[derive(Debug, Clone)]
+pub struct GroupPage {
+    /// The id of the website that crawled the page.
+    pub site_id: Arc<str>,
+    /// The page crawled.
+    pub page: Page,
+}
+
+/// Crawl multiple websites together re-using the HTTP clients, one chrome instance, and the shared queue semaphore.
+/// The pages of every website are merged into a single subscription tagged with the site id.
+///
+/// The websites with the same client settings share one HTTP client built from the first of them. The websites with a different
+/// user agent, headers, proxies, timeout, or redirect limit, and the websites with a strict redirect policy, cookies, a login flow, or a storage state build their own client.
+/// The group does not schedule the requests across the websites or share a DNS cache, each website crawls with its own limits.
+/// ```rust,no_run
+/// use spider::crawl_group::CrawlGroup;
+/// use spider::tokio;
+/// use spider::website::Website;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut group = CrawlGroup::new();
+///     group
+///         .with_website("docs", Website::new("https://spider.cloud"))
+///         .with_website("licenses", Website::new("https://choosealicense.com"));
+///     let mut rx = group.subscribe(0).unwrap();
+///
+///     tokio::spawn(async move {
+///         while let Ok(res) = rx.recv().await {
+///             println!("{} - {}", res.site_id, res.page.get_url());
+///         }
+///     });
+///
+///     group.crawl().await;
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct CrawlGroup {
+    /// The websites to crawl with their site id.
+    websites: Vec<(Arc<str>, Website)>,
+    /// The request client shared between all the websites. The clients are built from the website configurations when not set.
+    client: Option<Client>,
+    /// Subscribe and broadcast the pages of all websites.
+    channel: Option<broadcast::Sender<GroupPage>>,
+}
+
+impl CrawlGroup {
+    /// A new empty crawl group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a website to the group. The site id is used to tag the pages sent to subscriptions.
+    pub fn with_website(&mut self, site_id: &str, website: Website) -> &mut Self {
+        self.websites.push((site_id.into(), website));
+        self
+    }
+
+    /// Set the HTTP client to share between all websites. The client settings of the website configurations are not used with the client set.
+    pub fn with_client(&mut self, client: Client) -> &mut Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Get a website in the group by the site id.
+    pub fn get_website(&self, site_id: &str) -> Option<&Website> {
+        self.websites
+            .iter()
+            .find(|(id, _)| id.as_ref() == site_id)
+            .map(|(_, website)| website)
+    }
+
+    /// Get a mutable website in the group by the site id.
+    pub fn get_website_mut(&mut self, site_id: &str) -> Option<&mut Website> {
+        self.websites
+            .iter_mut()
+            .find(|(id, _)| id.as_ref() == site_id)
+            .map(|(_, website)| website)
+    }
+
+    /// All the websites in the group with the site id.
+    pub fn get_websites(&self) -> &[(Arc<str>, Website)] {
+        &self.websites
+    }
+
+    /// The amount of websites in the group.
+    pub fn len(&self) -> usize {
+        self.websites.len()
+    }
+
+    /// The group does not have any websites.
+    pub fn is_empty(&self) -> bool {
+        self.websites.is_empty()
+    }
+
+    /// Sets up a subscription to receive the pages of every website in the group. Set the value to `0` to use the default capacity.
+    /// This does nothing unless the `sync` flag is enabled.
+    #[cfg(not(feature = "sync"))]
+    pub fn subscribe(&mut self, _capacity: usize) -> Option<broadcast::Receiver<GroupPage>> {
+        None
+    }
+
+    /// Sets up a subscription to receive the pages of every website in the group. Set the value to `0` to use the default capacity.
+    /// This does nothing unless the `sync` flag is enabled.
+    #[cfg(feature = "sync")]
+    pub fn subscribe(&mut self, capacity: usize) -> Option<broadcast::Receiver<GroupPage>> {
+        let channel = self.channel.get_or_insert_with(|| {
+            let (tx, _) = broadcast::channel(
+                (if capacity == 0 {
+                    crate::website::calc_limits(1)
+                } else {
+                    capacity
+                })
+                .max(1),
+            );
+            tx
+        });
+
+        Some(channel.subscribe())
+    }
+
+    /// Remove the subscriptions of the group.
+    pub fn unsubscribe(&mut self) {
+        self.channel.take();
+    }
+
+    /// Get the HTTP client set to share between all websites.
+    pub fn get_client(&self) -> &Option<Client> {
+        &self.client
+    }
+
+    /// The HTTP client of each website. The websites with the same client settings share the client.
+    fn setup_clients(&self) -> Vec<Client> {
+        if let Some(ref client) = self.client {
+            return vec![client.clone(); self.websites.len()];
+        }
+
+        let mut clients: Vec<(&Website, Client)> = Vec::with_capacity(self.websites.len());
+
+        self.websites
+            .iter()
+            .map(|(_, website)| {
+                match clients
+                    .iter()
+                    .find(|(shared, _)| shares_client(shared, website))
+                {
+                    Some((_, client)) => client.clone(),
+                    _ => {
+                        let client = website.configure_http_client();
+                        clients.push((website, client.clone()));
+                        client
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Launch the chrome instance to share between the websites. Websites with a `chrome_connection_url` set keep their own connection.
+    /// Returns the browser with the site ids of the websites connected to it.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    async fn setup_browser(
+        &mut self,
+    ) -> (
+        Option<crate::features::chrome::BrowserController>,
+        Vec<Arc<str>>,
+    ) {
+        let mut connected = Vec::new();
+        let needs_browser = self
+            .websites
+            .iter()
+            .any(|(_, w)| w.configuration.chrome_connection_url.is_none());
+
+        if !needs_browser {
+            return (None, connected);
+        }
+
+        let browser = match self.websites.first() {
+            Some((_, w)) => w.setup_browser().await,
+            _ => None,
+        };
+
+        if let Some(ref b) = browser {
+            let connection_url = b.browser.0.websocket_address();
+
+            for (site_id, website) in self.websites.iter_mut() {
+                if website.configuration.chrome_connection_url.is_none() {
+                    website.with_chrome_connection(Some(connection_url.clone()));
+                    connected.push(site_id.clone());
+                }
+            }
+        }
+
+        (browser, connected)
+    }
+
+    /// Forward the pages of the website to the group subscription tagged with the site id.
+    #[cfg(feature = "sync")]
+    fn forward_pages(&self, site_id: &Arc<str>, website: &mut Website, set: &mut JoinSet<()>) {
+        if let Some(ref tx) = self.channel {
+            if let Some(mut rx) = website.subscribe(0) {
+                let tx = tx.clone();
+                let site_id = site_id.clone();
+
+                crate::utils::spawn_set("crawl_group_forward", set, async move {
+                    loop {
+                        match rx.recv().await {
+                            Ok(page) => {
+                                let _ = tx.send(GroupPage {
+                                    site_id: site_id.clone(),
+                                    page,
+                                });
+                            }
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    /// Forward the pages of the website to the group subscription tagged with the site id.
+    #[cfg(not(feature = "sync"))]
+    fn forward_pages(&self, _site_id: &Arc<str>, _website: &mut Website, _set: &mut JoinSet<()>) {}
+
+    /// Crawl all the websites in the group concurrently.
+    /// The HTTP clients, the chrome instance, and the shared queue semaphore are re-used by the websites.
+    pub async fn crawl(&mut self) {
+        if self.websites.is_empty() {
+            return;
+        }
+
+        let clients = self.setup_clients();
+
+        #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+        let (browser, connected) = self.setup_browser().await;
+
+        let mut forwarders = JoinSet::new();
+        let mut set = JoinSet::new();
+        let site_ids: Vec<Arc<str>> = self.websites.iter().map(|(id, _)| id.clone()).collect();
+
+        for (index, ((site_id, mut website), client)) in std::mem::take(&mut self.websites)
+            .into_iter()
+            .zip(clients)
+            .enumerate()
+        {
+            website.set_http_client(client);
+            website.with_shared_queue(true);
+
+            self.forward_pages(&site_id, &mut website, &mut forwarders);
+
+            crate::utils::spawn_set("crawl_group", &mut set, async move {
+                website.crawl().await;
+                website.unsubscribe();
+                (index, site_id, website)
+            });
+        }
+
+        let mut websites = Vec::with_capacity(set.len());
+
+        while let Some(result) = set.join_next().await {
+            match result {
+                Ok(result) => websites.push(result),
+                Err(e) => log::error!("a website crawl of the group failed: {e}"),
+            }
+        }
+
+        while forwarders.join_next().await.is_some() {}
+
+        websites.sort_by_key(|(index, _, _)| *index);
+
+        for (index, site_id) in site_ids.iter().enumerate() {
+            if !websites.iter().any(|(i, _, _)| *i == index) {
+                log::error!(
+                    "the website {site_id} was removed from the group after the crawl failed"
+                );
+            }
+        }
+
+        self.websites = websites
+            .into_iter()
+            .map(|(_, site_id, website)| (site_id, website))
+            .collect();
+
+        #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+        {
+            // the connection of the group browser is closed after the crawl.
+            for (site_id, website) in self.websites.iter_mut() {
+                if connected.contains(site_id) {
+                    website.with_chrome_connection(None);
+                }
+            }
+
+            drop(browser);
+        }
+    }
+}
+
+/// The cookies of the website are set on the HTTP client.
+fn has_client_cookies(configuration: &Configuration) -> bool {
+    #[cfg(feature = "chrome")]
+    let login = configuration.login_flow.is_some();
+    #[cfg(not(feature = "chrome"))]
+    let login = false;
+    #[cfg(feature = "storage_state")]
+    let login = login || configuration.storage_state.is_some();
+    #[cfg(feature = "cookies")]
+    let login = login || !configuration.cookie_str.is_empty();
+
+    login
+}
+
+/// The websites build the same HTTP client.
+fn shares_client(website: &Website, other: &Website) -> bool {
+    let (a, b) = (&website.configuration, &other.configuration);
+
+    #[cfg(feature = "rquest")]
+    let emulation = format!("{:?}", a.emulation) == format!("{:?}", b.emulation);
+    #[cfg(not(feature = "rquest"))]
+    let emulation = true;
+    #[cfg(feature = "cache_request")]
+    let cache = a.cache == b.cache;
+    #[cfg(not(feature = "cache_request"))]
+    let cache = true;
+
+    emulation
+        && cache
+        && !has_client_cookies(a)
+        && !has_client_cookies(b)
+        && !a.preserve_host_header
+        && !b.preserve_host_header
+        && a.user_agent == b.user_agent
+        && a.only_chrome_agent() == b.only_chrome_agent()
+        && a.headers == b.headers
+        && a.proxies == b.proxies
+        && a.request_timeout == b.request_timeout
+        && a.redirect_policy == RedirectPolicy::Loose
+        && b.redirect_policy == RedirectPolicy::Loose
+        && a.redirect_limit == b.redirect_limit
+        && a.accept_invalid_certs == b.accept_invalid_certs
+        && a.http2_prior_knowledge == b.http2_prior_knowledge
+        && a.concurrency_limit == b.concurrency_limit
+}
+
+#[cfg(all(feature = "sync", not(feature = "decentralized")))]
+#[tokio::test]
+async fn crawl_group() {
+    let mut group = CrawlGroup::new();
+    group
+        .with_website("licenses", Website::new("https://choosealicense.com"))
+        .with_website("rust", Website::new("https://www.rust-lang.org"));
+    let mut rx = group.subscribe(0).unwrap();
+
+    let join_handle = tokio::spawn(async move {
+        let mut site_ids = hashbrown::HashSet::new();
+        while let Ok(res) = rx.recv().await {
+            site_ids.insert(res.site_id.to_string());
+        }
+        site_ids
+    });
+
+    group.crawl().await;
+    group.unsubscribe();
+
+    let site_ids = join_handle.await.unwrap();
+
+    assert!(site_ids.contains("licenses"), "{:?}", site_ids);
+    assert!(site_ids.contains("rust"), "{:?}", site_ids);
+    assert!(group.get_website("licenses").unwrap().size() > 1);
+}
+
+#[test]
+fn crawl_group_shares_client() {
+    let website = Website::new("https://choosealicense.com");
+    let mut other = Website::new("https://www.rust-lang.org");
+
+    assert!(shares_client(&website, &other));
+
+    other.with_user_agent(Some("spider-group"));
+    assert!(!shares_client(&website, &other));
+
+    #[cfg(feature = "cookies")]
+    {
+        let mut other = Website::new("https://www.rust-lang.org");
+        other.with_cookies("session=1");
+        assert!(!shares_client(&website, &other));
+    }
+}
@@ -476,6 +476,13 @@ pub async fn launch_browser(
                 if let Ok(c) = browser.create_browser_context(create_content).await {
                     let _ = browser.send_new_context(c.clone()).await;
                     let _ = context_id.insert(c);
+                    #[cfg(feature = "storage_state")]
+                    if let Some(ref storage_state) = config.storage_state {
+                        let cookies = storage_state.cookie_params();
+                        if !cookies.is_empty() {
+                            let _ = browser.set_cookies(cookies).await;
+                        }
+                    }
                     if !config.cookie_str.is_empty() {
                         if let Some(parsed) = url_parsed {
                             let cookies = parse_cookies_with_jar(&config.cookie_str, &*parsed);
@@ -732,8 +739,25 @@ pub async fn setup_chrome_events(chrome_page: &chromiumoxide::Page, config: &Con
         }
     };
 
+    #[cfg(feature = "storage_state")]
+    let storage_state = async {
+        if let Some(ref storage_state) = config.storage_state {
+            if let Some(script) = storage_state.init_script() {
+                let _ = chrome_page.evaluate_on_new_document(script).await;
+            }
+            storage_state.track_page(chrome_page).await;
+        }
+    };
+    #[cfg(not(feature = "storage_state"))]
+    let storage_state = async {};
+
     if let Err(_) = tokio::time::timeout(tokio::time::Duration::from_secs(10), async {
-        tokio::join!(stealth, eval_docs, configure_browser(&chrome_page, &config))
+        tokio::join!(
+            stealth,
+            eval_docs,
+            storage_state,
+            configure_browser(&chrome_page, &config)
+        )
     })
     .await
     {
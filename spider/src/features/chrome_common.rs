@@ -407,6 +407,196 @@ impl From<ScreenshotParams> for chromiumoxide::page::ScreenshotParams {
     }
 }
 
+/// The paper sizes to print the PDF with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PaperFormat {
+    #[default]
+    /// 8.5in x 11in
+    Letter,
+    /// 8.5in x 14in
+    Legal,
+    /// 11in x 17in
+    Tabloid,
+    /// 5.83in x 8.27in
+    A5,
+    /// 8.27in x 11.7in
+    A4,
+    /// 11.7in x 16.54in
+    A3,
+}
+
+impl PaperFormat {
+    /// The width and height of the paper in inches.
+    pub fn size(&self) -> (f64, f64) {
+        match self {
+            PaperFormat::Letter => (8.5, 11.0),
+            PaperFormat::Legal => (8.5, 14.0),
+            PaperFormat::Tabloid => (11.0, 17.0),
+            PaperFormat::A5 => (5.83, 8.27),
+            PaperFormat::A4 => (8.27, 11.7),
+            PaperFormat::A3 => (11.7, 16.54),
+        }
+    }
+}
+
+/// The PDF print params for the page. The sizes are in inches and the chrome defaults are used for the values not set.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PdfParams {
+    /// Print in landscape orientation.
+    pub landscape: bool,
+    /// Print the background graphics.
+    pub print_background: bool,
+    /// The scale of the page rendering. Defaults to 1.
+    pub scale: Option<f64>,
+    /// The paper size. Defaults to letter.
+    pub format: Option<PaperFormat>,
+    /// The paper width overriding the format.
+    pub paper_width: Option<f64>,
+    /// The paper height overriding the format.
+    pub paper_height: Option<f64>,
+    /// The top margin. Defaults to 0.4in.
+    pub margin_top: Option<f64>,
+    /// The bottom margin. Defaults to 0.4in.
+    pub margin_bottom: Option<f64>,
+    /// The left margin. Defaults to 0.4in.
+    pub margin_left: Option<f64>,
+    /// The right margin. Defaults to 0.4in.
+    pub margin_right: Option<f64>,
+    /// The pages to print ex: `1-5, 8`. Defaults to all pages.
+    pub page_ranges: Option<String>,
+    /// Use the page size of the css `@page` rule over the paper size.
+    pub prefer_css_page_size: bool,
+}
+
+impl PartialEq for PdfParams {
+    fn eq(&self, other: &Self) -> bool {
+        // the sizes are compared by the bits to keep the equality total.
+        let bits = |size: Option<f64>| size.map(f64::to_bits);
+
+        self.landscape == other.landscape
+            && self.print_background == other.print_background
+            && bits(self.scale) == bits(other.scale)
+            && self.format == other.format
+            && bits(self.paper_width) == bits(other.paper_width)
+            && bits(self.paper_height) == bits(other.paper_height)
+            && bits(self.margin_top) == bits(other.margin_top)
+            && bits(self.margin_bottom) == bits(other.margin_bottom)
+            && bits(self.margin_left) == bits(other.margin_left)
+            && bits(self.margin_right) == bits(other.margin_right)
+            && self.page_ranges == other.page_ranges
+            && self.prefer_css_page_size == other.prefer_css_page_size
+    }
+}
+
+impl Eq for PdfParams {}
+
+impl PdfParams {
+    /// Set the paper size.
+    pub fn with_format(mut self, format: PaperFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Set the same margin on all sides in inches.
+    pub fn with_margin(mut self, margin: f64) -> Self {
+        self.margin_top = Some(margin);
+        self.margin_bottom = Some(margin);
+        self.margin_left = Some(margin);
+        self.margin_right = Some(margin);
+        self
+    }
+
+    /// Print in landscape orientation.
+    pub fn with_landscape(mut self, landscape: bool) -> Self {
+        self.landscape = landscape;
+        self
+    }
+
+    /// Print the background graphics.
+    pub fn with_print_background(mut self, print_background: bool) -> Self {
+        self.print_background = print_background;
+        self
+    }
+}
+
+#[cfg(feature = "chrome")]
+impl From<PdfParams> for chromiumoxide::cdp::browser_protocol::page::PrintToPdfParams {
+    fn from(params: PdfParams) -> Self {
+        let size = params.format.map(|format| format.size());
+
+        Self {
+            landscape: Some(params.landscape),
+            print_background: Some(params.print_background),
+            scale: params.scale,
+            paper_width: params.paper_width.or(size.map(|s| s.0)),
+            paper_height: params.paper_height.or(size.map(|s| s.1)),
+            margin_top: params.margin_top,
+            margin_bottom: params.margin_bottom,
+            margin_left: params.margin_left,
+            margin_right: params.margin_right,
+            page_ranges: params.page_ranges,
+            prefer_css_page_size: Some(params.prefer_css_page_size),
+            ..Default::default()
+        }
+    }
+}
+
+/// PDF configuration.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PdfConfig {
+    /// The PDF print params.
+    pub params: PdfParams,
+    /// Return the bytes of the PDF on the Page.
+    pub bytes: bool,
+    /// Store the PDF to disk. This can be used with output_dir. If disabled will not store the file to the output directory.
+    pub save: bool,
+    /// The output directory to store the file. Parant folders may be created inside the directory.
+    pub output_dir: Option<std::path::PathBuf>,
+}
+
+impl PdfConfig {
+    /// Create a new PDF configuration.
+    pub fn new(
+        params: PdfParams,
+        bytes: bool,
+        save: bool,
+        output_dir: Option<std::path::PathBuf>,
+    ) -> Self {
+        Self {
+            params,
+            bytes,
+            save,
+            output_dir,
+        }
+    }
+}
+
+/// MHTML snapshot configuration. The snapshot is a single file archive of the page with the resources inlined.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MhtmlConfig {
+    /// Return the bytes of the snapshot on the Page.
+    pub bytes: bool,
+    /// Store the snapshot to disk. This can be used with output_dir. If disabled will not store the file to the output directory.
+    pub save: bool,
+    /// The output directory to store the file. Parant folders may be created inside the directory.
+    pub output_dir: Option<std::path::PathBuf>,
+}
+
+impl MhtmlConfig {
+    /// Create a new MHTML snapshot configuration.
+    pub fn new(bytes: bool, save: bool, output_dir: Option<std::path::PathBuf>) -> Self {
+        Self {
+            bytes,
+            save,
+            output_dir,
+        }
+    }
+}
+
 #[doc = "The decision on what to do in response to the authorization challenge.  Default means\ndeferring to the default behavior of the net stack, which will likely either the Cancel\nauthentication or display a popup dialog box."]
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -502,6 +692,67 @@ pub enum WebAutomation {
         /// The output file to store the screenshot.
         output: String,
     },
+    /// Selects the option of a dropdown by the value or the visible text and dispatches the `input` and `change` events.
+    Select {
+        /// The selector of the select element.
+        selector: String,
+        /// The value or the text of the option to select.
+        value: String,
+    },
+    /// Presses a key on the focused element like `Enter`, `Tab`, `Escape` or `ArrowDown`.
+    Press(String),
+    /// Types the text as key events into the focused element or the element of the selector.
+    Type {
+        /// The selector of the element to focus before typing.
+        selector: Option<String>,
+        /// The text to type.
+        value: String,
+    },
+    /// Moves the mouse over an element.
+    Hover(String),
+    /// Sets the files of a file input element.
+    UploadFile {
+        /// The selector of the file input element.
+        selector: String,
+        /// The paths of the files to upload.
+        files: Vec<String>,
+    },
+    /// Clicks on all of the elements matching the selector in order.
+    ClickAll(String),
+    /// Waits until the url of the page contains the value.
+    WaitForUrl {
+        /// The value the url should contain.
+        url: String,
+        /// The timeout to wait for in ms.
+        timeout: u64,
+    },
+    /// Runs the `then` steps when an element matches the selector and the `otherwise` steps when it does not.
+    If {
+        /// The selector of the element to check.
+        selector: String,
+        /// The steps to run when the element exists.
+        then: Vec<WebAutomation>,
+        /// The steps to run when the element does not exist.
+        otherwise: Vec<WebAutomation>,
+    },
+    /// Repeats the steps up to `times` or until an element matches the `until` selector. The repeat stops early when an iteration is not valid, like clicking a next button that no longer exists.
+    Repeat {
+        /// The max amount of iterations.
+        times: u32,
+        /// Stop once an element matches the selector.
+        until: Option<String>,
+        /// The steps to repeat.
+        steps: Vec<WebAutomation>,
+    },
+    /// Stores the text or the attribute of the elements matching the selector into the page automation data under the name.
+    Extract {
+        /// The key to store the values under.
+        name: String,
+        /// The selector of the elements.
+        selector: String,
+        /// The attribute to read instead of the inner text.
+        attribute: Option<String>,
+    },
     /// Only continue to the next automation if the prior step was valid. Use this intermediate after a step to break out of the chain.
     ValidateChain,
 }
@@ -541,9 +792,26 @@ impl WebAutomation {
     #[cfg(feature = "chrome")]
     /// Run the web automation step.
     pub async fn run(&self, page: &chromiumoxide::Page) -> bool {
+        self.run_with_data(page, &mut Default::default()).await
+    }
+
+    #[cfg(feature = "chrome")]
+    /// Run the web automation step storing the values of the `Extract` steps into the data.
+    pub fn run_with_data<'a>(
+        &'a self,
+        page: &'a chromiumoxide::Page,
+        data: &'a mut AutomationData,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = bool> + Send + 'a>> {
+        Box::pin(self.run_step(page, data))
+    }
+
+    #[cfg(feature = "chrome")]
+    /// Run the web automation step.
+    async fn run_step(&self, page: &chromiumoxide::Page, data: &mut AutomationData) -> bool {
         use crate::utils::wait_for_selector;
         use chromiumoxide::cdp::browser_protocol::dom::Rect;
         use chromiumoxide::cdp::browser_protocol::dom::ScrollIntoViewIfNeededParams;
+        use chromiumoxide::cdp::browser_protocol::dom::SetFileInputFilesParams;
         use std::time::Duration;
 
         let mut valid = false;
@@ -624,13 +892,136 @@ impl WebAutomation {
                     .await
                     .is_ok();
             }
-            _ => (),
+            WebAutomation::Select { selector, value } => {
+                if let Ok(ele) = page.find_element(selector).await {
+                    if let Ok(res) = ele.call_js_fn(select_option_js(value), false).await {
+                        valid = res.result.value.and_then(|v| v.as_bool()) == Some(true);
+                    }
+                }
+            }
+            WebAutomation::Press(key) => {
+                valid = page.press_key(key).await.is_ok();
+            }
+            WebAutomation::Type { selector, value } => match selector {
+                Some(selector) => {
+                    if let Ok(ele) = page.find_element(selector).await {
+                        if let Ok(el) = ele.click().await {
+                            valid = el.type_str(value).await.is_ok();
+                        }
+                    }
+                }
+                _ => {
+                    valid = page.type_str(value).await.is_ok();
+                }
+            },
+            WebAutomation::Hover(selector) => {
+                if let Ok(ele) = page.find_element(selector).await {
+                    valid = ele.hover().await.is_ok();
+                }
+            }
+            WebAutomation::UploadFile { selector, files } => {
+                if let Ok(ele) = page.find_element(selector).await {
+                    if let Ok(cmd) = SetFileInputFilesParams::builder()
+                        .files(files.clone())
+                        .backend_node_id(ele.backend_node_id)
+                        .build()
+                    {
+                        valid = page.execute(cmd).await.is_ok();
+                    }
+                }
+            }
+            WebAutomation::ClickAll(selector) => {
+                if let Ok(eles) = page.find_elements(selector).await {
+                    for ele in eles {
+                        valid = ele.click().await.is_ok() || valid;
+                    }
+                }
+            }
+            WebAutomation::WaitForUrl { url, timeout } => {
+                valid = tokio::time::timeout(Duration::from_millis(*timeout), async {
+                    loop {
+                        if let Ok(Some(current)) = page.url().await {
+                            if current.contains(url.as_str()) {
+                                break;
+                            }
+                        }
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                })
+                .await
+                .is_ok();
+            }
+            WebAutomation::If {
+                selector,
+                then,
+                otherwise,
+            } => {
+                let steps = if page.find_element(selector).await.is_ok() {
+                    then
+                } else {
+                    otherwise
+                };
+
+                valid = steps.is_empty() || run_automation_steps(page, steps, data).await;
+            }
+            WebAutomation::Repeat {
+                times,
+                until,
+                steps,
+            } => {
+                valid = true;
+
+                for _ in 0..*times {
+                    if let Some(until) = until {
+                        if page.find_element(until).await.is_ok() {
+                            break;
+                        }
+                    }
+                    valid = run_automation_steps(page, steps, data).await;
+                    if !valid {
+                        break;
+                    }
+                }
+            }
+            WebAutomation::Extract {
+                name,
+                selector,
+                attribute,
+            } => {
+                if let Ok(eles) = page.find_elements(selector).await {
+                    let mut values = Vec::with_capacity(eles.len());
+
+                    for ele in eles {
+                        let value = match attribute {
+                            Some(attribute) => ele.attribute(attribute).await,
+                            _ => ele.inner_text().await,
+                        };
+                        if let Ok(Some(value)) = value {
+                            values.push(value);
+                        }
+                    }
+
+                    valid = !values.is_empty();
+                    data.entry(name.clone()).or_default().extend(values);
+                }
+            }
+            WebAutomation::ValidateChain => (),
         };
 
         valid
     }
 }
 
+#[cfg(feature = "chrome")]
+/// Generate the function selecting the option of the select element by the value or the visible text.
+fn select_option_js(value: &str) -> String {
+    let value = serde_json::to_string(value).unwrap_or_default();
+
+    format!(
+        "function(){{const v={value},o=[...this.options].find(o=>o.value===v||o.text.trim()===v);if(!o)return!1;this.value=o.value;this.dispatchEvent(new Event('input',{{bubbles:!0}}));this.dispatchEvent(new Event('change',{{bubbles:!0}}));return!0}}"
+    )
+}
+
 /// Set a dynamic time to scroll.
 pub fn set_dynamic_scroll(timeout: u32) -> String {
     let timeout = timeout.min(crate::utils::FIVE_MINUTES);
@@ -643,10 +1034,75 @@ pub fn set_dynamic_scroll(timeout: u32) -> String {
     s
 }
 
+/// The check that the login flow succeeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoginCheck {
+    /// The element is on the page after the login.
+    Selector(String),
+    /// The page left the login url.
+    UrlChanged,
+}
+
+/// A login run in chrome before the crawl starts. The cookies are shared with the chrome pages and the HTTP client and the login re-runs when a page redirects to the login url.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoginFlow {
+    /// The url of the login page.
+    pub url: String,
+    /// The automation steps to fill and submit the login form.
+    pub steps: Vec<WebAutomation>,
+    /// The check that the login succeeded.
+    pub success: LoginCheck,
+    /// The max time to wait for the success check in ms.
+    pub timeout: u64,
+    /// The max times the login re-runs during the crawl.
+    pub max_relogins: u32,
+}
+
+impl LoginFlow {
+    /// A new login flow on the url.
+    pub fn new(url: &str, steps: Vec<WebAutomation>, success: LoginCheck) -> Self {
+        Self {
+            url: url.into(),
+            steps,
+            success,
+            timeout: 30_000,
+            max_relogins: 3,
+        }
+    }
+
+    /// The max time to wait for the success check in ms.
+    pub fn with_timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The max times the login re-runs during the crawl.
+    pub fn with_max_relogins(mut self, max_relogins: u32) -> Self {
+        self.max_relogins = max_relogins;
+        self
+    }
+
+    /// The url is the login page ignoring the query, fragment and trailing slash.
+    pub fn is_login_url(&self, url: &str) -> bool {
+        fn base(url: &str) -> &str {
+            url.split(['?', '#'])
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches('/')
+        }
+
+        base(url).eq_ignore_ascii_case(base(&self.url))
+    }
+}
+
 /// Execution scripts to run on the page when using chrome by url.
 pub type ExecutionScriptsMap = hashbrown::HashMap<String, String>;
 /// Automation scripts to run on the page when using chrome by url.
 pub type AutomationScriptsMap = hashbrown::HashMap<String, Vec<WebAutomation>>;
+/// The values stored by the `Extract` automation steps by name.
+pub type AutomationData = hashbrown::HashMap<String, Vec<String>>;
 
 /// Execution scripts to run on the page when using chrome by url.
 pub type ExecutionScripts = Trie<String>;
@@ -767,51 +1223,138 @@ pub async fn eval_execution_scripts(
     }
 }
 
-/// Run automation scripts.
+/// Run the automation steps in order. A `ValidateChain` step stops the steps when the prior step was not valid. Returns if the last step was valid.
+#[cfg(feature = "chrome")]
+pub async fn run_automation_steps(
+    page: &chromiumoxide::Page,
+    steps: &[WebAutomation],
+    data: &mut AutomationData,
+) -> bool {
+    let mut valid = false;
+
+    for step in steps {
+        if step == &WebAutomation::ValidateChain && !valid {
+            break;
+        }
+        match tokio::time::timeout(
+            tokio::time::Duration::from_secs(60),
+            step.run_with_data(page, data),
+        )
+        .await
+        {
+            Ok(next) => valid = next,
+            Err(elasped) => {
+                log::warn!("Script execution timed out for: {step:?} - {elasped}")
+            }
+        }
+    }
+
+    valid
+}
+
+/// Run automation scripts. Returns the values stored by the `Extract` steps.
 #[cfg(feature = "chrome")]
 pub async fn eval_automation_scripts(
     page: &chromiumoxide::Page,
     target_url: &str,
     automation_scripts: &Option<AutomationScripts>,
-) {
+) -> AutomationData {
+    let mut data = AutomationData::default();
+
     if let Some(script_map) = automation_scripts {
         if let Some(scripts) = script_map.search(target_url) {
-            let mut valid = false;
-
-            for script in scripts {
-                if script == &WebAutomation::ValidateChain && !valid {
-                    break;
-                }
-                match tokio::time::timeout(tokio::time::Duration::from_secs(60), script.run(page))
-                    .await
-                {
-                    Ok(next) => valid = next,
-                    Err(elasped) => {
-                        log::warn!("Script execution timed out for: {target_url} - {elasped}")
-                    }
-                }
-            }
+            run_automation_steps(page, scripts, &mut data).await;
         } else if script_map.match_all {
             if let Some(scripts) = script_map.root.value.as_ref() {
-                let mut valid = false;
-
-                for script in scripts {
-                    if script == &WebAutomation::ValidateChain && !valid {
-                        break;
-                    }
-                    match tokio::time::timeout(
-                        tokio::time::Duration::from_secs(60),
-                        script.run(page),
-                    )
-                    .await
-                    {
-                        Ok(next) => valid = next,
-                        Err(elasped) => {
-                            log::warn!("Script execution timed out for: {target_url} - {elasped}")
-                        }
-                    }
-                }
+                run_automation_steps(page, scripts, &mut data).await;
             }
         }
     }
+
+    data
+}
+
+#[cfg(all(test, any(feature = "chrome", feature = "serde")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_web_automation_serde_nested_steps() {
+        let steps = vec![
+            WebAutomation::If {
+                selector: "#cookie-banner".into(),
+                then: vec![WebAutomation::Click("#accept".into())],
+                otherwise: vec![WebAutomation::Press("Escape".into())],
+            },
+            WebAutomation::Repeat {
+                times: 3,
+                until: Some(".last-page".into()),
+                steps: vec![
+                    WebAutomation::Extract {
+                        name: "titles".into(),
+                        selector: "h2".into(),
+                        attribute: None,
+                    },
+                    WebAutomation::Click("a.next".into()),
+                    WebAutomation::ValidateChain,
+                ],
+            },
+            WebAutomation::Select {
+                selector: "select#sort".into(),
+                value: "Newest".into(),
+            },
+            WebAutomation::WaitForUrl {
+                url: "/results".into(),
+                timeout: 5000,
+            },
+        ];
+
+        let json = serde_json::to_string(&steps).unwrap();
+        let parsed: Vec<WebAutomation> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, steps);
+    }
+
+    #[cfg(feature = "chrome")]
+    #[test]
+    fn test_pdf_params_print_to_pdf() {
+        use chromiumoxide::cdp::browser_protocol::page::PrintToPdfParams;
+
+        let params: PrintToPdfParams = PdfParams::default()
+            .with_format(PaperFormat::A4)
+            .with_landscape(true)
+            .into();
+
+        assert_eq!(params.paper_width, Some(8.27));
+        assert_eq!(params.paper_height, Some(11.7));
+        assert_eq!(params.landscape, Some(true));
+        assert_eq!(params.margin_top, None);
+
+        let params: PrintToPdfParams = PdfParams {
+            paper_width: Some(4.0),
+            ..PdfParams::default()
+                .with_format(PaperFormat::Legal)
+                .with_margin(0.0)
+        }
+        .into();
+
+        assert_eq!(params.paper_width, Some(4.0));
+        assert_eq!(params.paper_height, Some(14.0));
+        assert_eq!(params.margin_left, Some(0.0));
+
+        let params: PrintToPdfParams = PdfParams::default().into();
+
+        assert_eq!(params.paper_width, None);
+        assert_eq!(params.paper_height, None);
+    }
+
+    #[cfg(feature = "chrome")]
+    #[test]
+    fn test_select_option_js_escape() {
+        let js = select_option_js("Tom's \"pick\" \\ <b>\n\u{1b}");
+
+        assert!(js.starts_with(r#"function(){const v="Tom's \"pick\" \\ <b>\n\u001b","#));
+        assert_eq!(js.matches("const v=").count(), 1);
+    }
 }
@@ -0,0 +1,445 @@
+use crate::configuration::{BrowserPoolConfig, Configuration};
+use crate::features::chrome::{
+    attempt_navigation, launch_browser, setup_chrome_events, setup_chrome_interception_base,
+};
+use case_insensitive_string::CaseInsensitiveString;
+use chromiumoxide::cdp::browser_protocol::browser::BrowserContextId;
+use chromiumoxide::cdp::js_protocol::runtime::GetHeapUsageParams;
+use chromiumoxide::{Browser, Page};
+use hashbrown::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
+use url::Url;
+
+/// The max time to reset a tab before it is closed.
+const RESET_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// What to do with a tab returned to the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReleaseAction {
+    /// The browser of the tab was relaunched while the tab was in use.
+    Stale,
+    /// The browser crashed while the tab was in use.
+    Crashed,
+    /// Close the tab and relaunch the browser once the tabs in use are returned.
+    Recycle,
+    /// Reset the tab and keep it for reuse.
+    Reset,
+}
+
+/// The action for a tab of the launch `generation` returned to the browser of the launch `current`.
+fn release_action(
+    config: &BrowserPoolConfig,
+    generation: usize,
+    current: Option<usize>,
+    crashed: bool,
+    pages: usize,
+    over_memory: bool,
+) -> ReleaseAction {
+    if current != Some(generation) {
+        ReleaseAction::Stale
+    } else if crashed {
+        ReleaseAction::Crashed
+    } else if over_memory || config.max_pages_per_browser.is_some_and(|max| pages >= max) {
+        ReleaseAction::Recycle
+    } else {
+        ReleaseAction::Reset
+    }
+}
+
+/// The count of the pages re-queued after a browser crash.
+#[derive(Debug, Default)]
+pub struct Requeued {
+    /// The max times a page is re-queued.
+    max_requeue: u8,
+    /// The times each page was re-queued.
+    counts: HashMap<CaseInsensitiveString, u8>,
+}
+
+impl Requeued {
+    /// A new count with the max times a page is re-queued.
+    pub fn new(max_requeue: u8) -> Self {
+        Self {
+            max_requeue,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Count the re-queue of the link. Returns `false` once the link crashed the browser more than the max times.
+    pub fn requeue(&mut self, link: &CaseInsensitiveString) -> bool {
+        let count = self.counts.entry(link.clone()).or_default();
+
+        if *count < self.max_requeue {
+            *count += 1;
+            true
+        } else {
+            log::warn!(
+                "{} crashed the browser {} times, skipping.",
+                link.inner(),
+                *count + 1
+            );
+            false
+        }
+    }
+}
+
+/// A launched browser of the pool.
+struct PooledBrowser {
+    /// The browser.
+    browser: Browser,
+    /// The handler of the browser connection.
+    handle: Option<JoinHandle<()>>,
+    /// The browser context of the tabs.
+    context_id: Option<BrowserContextId>,
+    /// The tabs ready to be reused.
+    idle: Vec<Page>,
+    /// The pages handled since the launch.
+    pages: usize,
+    /// The launch of the slot.
+    generation: usize,
+    /// The browser is relaunched once the tabs in use finish.
+    recycle: bool,
+}
+
+impl PooledBrowser {
+    /// The browser process exited or the connection closed.
+    fn crashed(&mut self) -> bool {
+        matches!(self.browser.try_wait(), Ok(Some(_)) | Err(_))
+            || self.handle.as_ref().is_none_or(|h| h.is_finished())
+    }
+}
+
+impl Drop for PooledBrowser {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// A browser slot of the pool. The slot relaunches the browser after a crash or recycle.
+struct Slot {
+    /// The browser of the slot.
+    state: Mutex<Option<PooledBrowser>>,
+    /// The tabs that can be used.
+    tabs: Arc<Semaphore>,
+    /// The launches of the slot.
+    launches: AtomicUsize,
+}
+
+/// A tab leased from the pool. Return it with [`BrowserPool::release`].
+pub struct PooledTab {
+    /// The chrome page.
+    pub page: Page,
+    /// The browser slot of the tab.
+    slot: usize,
+    /// The launch of the browser the tab belongs to.
+    generation: usize,
+    /// The tab permit of the browser.
+    _permit: OwnedSemaphorePermit,
+}
+
+/// A pool of chrome browsers each with a max amount of tabs. The tabs are reset and reused, the browsers are relaunched after a crash, a max amount of pages or once a tab passes the memory threshold.
+pub struct BrowserPool {
+    /// The pool configuration.
+    config: BrowserPoolConfig,
+    /// The crawl configuration to launch the browsers and setup the tabs.
+    configuration: Box<Configuration>,
+    /// The crawl url used for the cookies.
+    url: Option<Box<Url>>,
+    /// The browser slots.
+    slots: Vec<Arc<Slot>>,
+}
+
+impl BrowserPool {
+    /// A new pool. The browsers are launched on the first use.
+    pub fn new(
+        config: BrowserPoolConfig,
+        configuration: &Configuration,
+        url: &Option<Box<Url>>,
+    ) -> Self {
+        let slots = (0..config.browsers.max(1))
+            .map(|_| {
+                Arc::new(Slot {
+                    state: Mutex::new(None),
+                    tabs: Arc::new(Semaphore::new(config.tabs_per_browser.max(1))),
+                    launches: AtomicUsize::new(0),
+                })
+            })
+            .collect();
+
+        Self {
+            config,
+            configuration: Box::new(configuration.clone()),
+            url: url.clone(),
+            slots,
+        }
+    }
+
+    /// The pool configuration.
+    pub fn config(&self) -> &BrowserPoolConfig {
+        &self.config
+    }
+
+    /// Lease a tab from the browser with the most free tabs. The browser is launched or relaunched when needed.
+    pub async fn acquire(&self) -> Option<PooledTab> {
+        let (index, slot) = self
+            .slots
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, slot)| (slot.tabs.available_permits(), usize::MAX - i))?;
+
+        let permit = slot.tabs.clone().acquire_owned().await.ok()?;
+        let mut state = slot.state.lock().await;
+
+        if state.as_mut().is_some_and(|browser| browser.crashed()) {
+            log::warn!("browser {index} crashed, relaunching.");
+            state.take();
+        }
+
+        if state.is_none() {
+            *state = self.launch(slot).await;
+        }
+
+        let browser = state.as_mut()?;
+
+        let page = match browser.idle.pop() {
+            Some(page) => page,
+            _ => {
+                let page = attempt_navigation(
+                    "about:blank",
+                    &browser.browser,
+                    &self.configuration.request_timeout,
+                    &browser.context_id,
+                    &self.configuration.viewport,
+                )
+                .await
+                .ok()?;
+
+                setup_chrome_events(&page, &self.configuration).await;
+
+                // the interception is setup once for the lifetime of the tab.
+                let _ = setup_chrome_interception_base(
+                    &page,
+                    self.configuration.chrome_intercept.enabled,
+                    &self.configuration.auth_challenge_response,
+                    self.configuration.chrome_intercept.block_visuals,
+                    self.url.as_ref().map(|u| u.as_str()).unwrap_or_default(),
+                )
+                .await;
+
+                page
+            }
+        };
+
+        browser.pages += 1;
+
+        Some(PooledTab {
+            page,
+            slot: index,
+            generation: browser.generation,
+            _permit: permit,
+        })
+    }
+
+    /// Return the tab to the pool. Returns `true` when the browser crashed while the tab was in use and the page should be fetched again.
+    pub async fn release(&self, tab: PooledTab) -> bool {
+        let slot = match self.slots.get(tab.slot) {
+            Some(slot) => slot,
+            _ => return false,
+        };
+
+        let over_memory = match self.config.max_memory_bytes {
+            Some(max) => tab
+                .page
+                .execute(GetHeapUsageParams::default())
+                .await
+                .is_ok_and(|usage| usage.result.used_size as u64 > max),
+            _ => false,
+        };
+
+        let mut state = slot.state.lock().await;
+
+        let browser = match state.as_mut() {
+            Some(browser) => browser,
+            _ => return true,
+        };
+
+        let crashed = browser.generation == tab.generation && browser.crashed();
+
+        match release_action(
+            &self.config,
+            tab.generation,
+            Some(browser.generation),
+            crashed,
+            browser.pages,
+            over_memory,
+        ) {
+            ReleaseAction::Stale => true,
+            ReleaseAction::Crashed => {
+                log::warn!("browser {} crashed, re-queueing the page.", tab.slot);
+                state.take();
+                true
+            }
+            ReleaseAction::Recycle => {
+                if !browser.recycle {
+                    browser.recycle = true;
+                    self.drain(slot.clone(), tab.generation);
+                }
+                let _ = tab.page.close().await;
+                false
+            }
+            ReleaseAction::Reset => {
+                if tokio::time::timeout(RESET_TIMEOUT, reset_tab(&tab.page))
+                    .await
+                    .is_ok_and(|reset| reset)
+                {
+                    browser.idle.push(tab.page);
+                } else {
+                    let _ = tab.page.close().await;
+                }
+                false
+            }
+        }
+    }
+
+    /// Close every browser of the pool.
+    pub async fn close(&self) {
+        for slot in self.slots.iter() {
+            if let Some(mut browser) = slot.state.lock().await.take() {
+                let _ = browser.browser.close().await;
+                let _ = browser.browser.try_wait();
+            }
+        }
+    }
+
+    /// Launch the browser of the slot.
+    async fn launch(&self, slot: &Slot) -> Option<PooledBrowser> {
+        match launch_browser(&self.configuration, &self.url).await {
+            Some((browser, handle, context_id)) => Some(PooledBrowser {
+                browser,
+                handle: Some(handle),
+                context_id,
+                idle: Vec::new(),
+                pages: 0,
+                generation: slot.launches.fetch_add(1, Ordering::Relaxed) + 1,
+                recycle: false,
+            }),
+            _ => {
+                log::error!("Chrome initialization failed.");
+                None
+            }
+        }
+    }
+
+    /// Close the browser once the tabs in use are returned. The next tab launches a new browser.
+    fn drain(&self, slot: Arc<Slot>, generation: usize) {
+        let permits = self.config.tabs_per_browser.max(1) as u32;
+
+        crate::utils::spawn_task("browser_recycle", async move {
+            // the permit of the releasing tab is dropped after the lock is released.
+            if let Ok(all) = slot.tabs.clone().acquire_many_owned(permits).await {
+                let mut state = slot.state.lock().await;
+
+                if state
+                    .as_ref()
+                    .is_some_and(|browser| browser.generation == generation)
+                {
+                    if let Some(mut browser) = state.take() {
+                        let _ = browser.browser.close().await;
+                        let _ = browser.browser.try_wait();
+                    }
+                }
+
+                drop(all);
+            }
+        });
+    }
+}
+
+/// Reset the session state of the tab and leave the page.
+async fn reset_tab(page: &Page) -> bool {
+    let _ = page
+        .evaluate("try { sessionStorage.clear() } catch (e) {}")
+        .await;
+
+    page.goto("about:blank").await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_action() {
+        let config = BrowserPoolConfig {
+            max_pages_per_browser: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            release_action(&config, 1, Some(2), false, 0, false),
+            ReleaseAction::Stale
+        );
+        assert_eq!(
+            release_action(&config, 1, None, false, 0, false),
+            ReleaseAction::Stale
+        );
+        assert_eq!(
+            release_action(&config, 1, Some(1), true, 0, false),
+            ReleaseAction::Crashed
+        );
+        assert_eq!(
+            release_action(&config, 1, Some(1), false, 2, false),
+            ReleaseAction::Recycle
+        );
+        assert_eq!(
+            release_action(&config, 1, Some(1), false, 1, true),
+            ReleaseAction::Recycle
+        );
+        assert_eq!(
+            release_action(&config, 1, Some(1), false, 1, false),
+            ReleaseAction::Reset
+        );
+    }
+
+    #[test]
+    fn test_requeued() {
+        let mut requeued = Requeued::new(1);
+        let link = CaseInsensitiveString::from("https://choosealicense.com/");
+
+        assert!(requeued.requeue(&link));
+        assert!(!requeued.requeue(&link));
+        assert!(requeued.requeue(&"https://choosealicense.com/about/".into()));
+        assert!(!Requeued::new(0).requeue(&link));
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_tabs() {
+        let pool = BrowserPool::new(
+            BrowserPoolConfig::new(1, 2),
+            &Configuration::default(),
+            &None,
+        );
+        let slot = pool.slots[0].clone();
+        let in_use = slot.tabs.clone().acquire_owned().await.unwrap();
+
+        pool.drain(slot.clone(), 1);
+        tokio::task::yield_now().await;
+
+        // the new tabs wait for the browser to close.
+        assert!(slot.tabs.clone().try_acquire_owned().is_err());
+
+        drop(in_use);
+
+        let tab = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            slot.tabs.clone().acquire_owned(),
+        )
+        .await;
+
+        assert!(tab.is_ok_and(|tab| tab.is_ok()));
+    }
+}
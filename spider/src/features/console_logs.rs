@@ -0,0 +1,263 @@
+use chromiumoxide::cdp::browser_protocol::log::{EventEntryAdded, LogEntryLevel};
+use chromiumoxide::cdp::browser_protocol::page::{
+    EventJavascriptDialogOpening, HandleJavaScriptDialogParams,
+};
+use chromiumoxide::cdp::js_protocol::runtime::{
+    ConsoleApiCalledType, EventConsoleApiCalled, EventExceptionThrown, RemoteObject, StackTrace,
+};
+use chromiumoxide::Page;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+
+/// The severity of the log.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ConsoleLogLevel {
+    /// Verbose and debug output.
+    Debug,
+    #[default]
+    /// Informational output like `console.log`.
+    Info,
+    /// Warnings.
+    Warning,
+    /// Errors and uncaught exceptions.
+    Error,
+}
+
+/// Where the log came from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ConsoleLogSource {
+    #[default]
+    /// A `console.*` call of the page.
+    Console,
+    /// An uncaught exception or unhandled rejection.
+    Exception,
+    /// A browser log entry like a blocked script, a failed request or a deprecation.
+    Browser,
+    /// A javascript dialog like `alert`, `confirm`, `prompt` or `beforeunload`.
+    Dialog,
+}
+
+/// A log collected while rendering the page.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConsoleLog {
+    /// Where the log came from.
+    pub source: ConsoleLogSource,
+    /// The severity of the log.
+    pub level: ConsoleLogLevel,
+    /// The chrome type of the log like `log`, `table`, `network` or `alert`.
+    pub kind: String,
+    /// The log message.
+    pub text: String,
+    /// The url of the script or resource.
+    pub url: Option<String>,
+    /// The 0-based line in the script.
+    pub line: Option<i64>,
+    /// The 0-based column in the script.
+    pub column: Option<i64>,
+    /// The time of the log in milliseconds since the epoch.
+    pub timestamp: f64,
+}
+
+/// Record the console output, exceptions, browser logs and dialogs of the page.
+pub struct ConsoleLogRecorder {
+    /// Stop the recording.
+    stop: oneshot::Sender<()>,
+    /// The recording task.
+    handle: JoinHandle<Vec<ConsoleLog>>,
+}
+
+impl ConsoleLogRecorder {
+    /// Start recording the logs of the page. The dialogs are accepted once logged so the page keeps rendering. The dialogs replaced by the `dismiss_dialogs` polyfill never reach the browser and are not logged.
+    pub async fn new(page: &Page) -> Option<Self> {
+        let (console, exceptions, entries, dialogs) = tokio::join!(
+            page.event_listener::<EventConsoleApiCalled>(),
+            page.event_listener::<EventExceptionThrown>(),
+            page.event_listener::<EventEntryAdded>(),
+            page.event_listener::<EventJavascriptDialogOpening>(),
+        );
+
+        let (mut console, mut exceptions, mut entries, mut dialogs) = (
+            console.ok()?,
+            exceptions.ok()?,
+            entries.ok()?,
+            dialogs.ok()?,
+        );
+        let (stop, mut stop_rx) = oneshot::channel::<()>();
+        let page = page.clone();
+
+        let handle = crate::utils::spawn_task("console_logs", async move {
+            let mut logs = Vec::new();
+
+            loop {
+                tokio::select! {
+                    biased;
+                    Some(event) = console.next() => {
+                        let frame = top_frame(&event.stack_trace);
+
+                        logs.push(ConsoleLog {
+                            source: ConsoleLogSource::Console,
+                            level: console_level(&event.r#type),
+                            kind: event.r#type.as_ref().into(),
+                            text: event
+                                .args
+                                .iter()
+                                .map(remote_object_text)
+                                .collect::<Vec<_>>()
+                                .join(" "),
+                            url: frame.map(|f| f.0.to_string()),
+                            line: frame.map(|f| f.1),
+                            column: frame.map(|f| f.2),
+                            timestamp: *event.timestamp.inner(),
+                        });
+                    }
+                    Some(event) = exceptions.next() => {
+                        let details = &event.exception_details;
+
+                        logs.push(ConsoleLog {
+                            source: ConsoleLogSource::Exception,
+                            level: ConsoleLogLevel::Error,
+                            kind: "exception".into(),
+                            text: match details.exception {
+                                Some(ref exception) => remote_object_text(exception),
+                                _ => details.text.clone(),
+                            },
+                            url: details
+                                .url
+                                .clone()
+                                .or_else(|| top_frame(&details.stack_trace).map(|f| f.0.to_string())),
+                            line: Some(details.line_number),
+                            column: Some(details.column_number),
+                            timestamp: *event.timestamp.inner(),
+                        });
+                    }
+                    Some(event) = entries.next() => {
+                        let entry = &event.entry;
+
+                        logs.push(ConsoleLog {
+                            source: ConsoleLogSource::Browser,
+                            level: match entry.level {
+                                LogEntryLevel::Verbose => ConsoleLogLevel::Debug,
+                                LogEntryLevel::Info => ConsoleLogLevel::Info,
+                                LogEntryLevel::Warning => ConsoleLogLevel::Warning,
+                                LogEntryLevel::Error => ConsoleLogLevel::Error,
+                            },
+                            kind: entry.source.as_ref().into(),
+                            text: entry.text.clone(),
+                            url: entry.url.clone(),
+                            line: entry.line_number,
+                            column: None,
+                            timestamp: *entry.timestamp.inner(),
+                        });
+                    }
+                    Some(event) = dialogs.next() => {
+                        logs.push(ConsoleLog {
+                            source: ConsoleLogSource::Dialog,
+                            level: ConsoleLogLevel::Info,
+                            kind: event.r#type.as_ref().into(),
+                            text: event.message.clone(),
+                            url: Some(event.url.clone()),
+                            line: None,
+                            column: None,
+                            timestamp: std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .map(|d| d.as_secs_f64() * 1000.0)
+                                .unwrap_or_default(),
+                        });
+
+                        if !event.has_browser_handler {
+                            let _ = page.execute(HandleJavaScriptDialogParams::new(true)).await;
+                        }
+                    }
+                    _ = &mut stop_rx => break,
+                    else => break,
+                }
+            }
+
+            logs
+        });
+
+        Some(Self { stop, handle })
+    }
+
+    /// Stop the recording and return the logs in the order they happened.
+    pub async fn finish(self) -> Vec<ConsoleLog> {
+        let _ = self.stop.send(());
+        self.handle.await.unwrap_or_default()
+    }
+}
+
+/// The level of the `console.*` call.
+fn console_level(kind: &ConsoleApiCalledType) -> ConsoleLogLevel {
+    match kind {
+        ConsoleApiCalledType::Error | ConsoleApiCalledType::Assert => ConsoleLogLevel::Error,
+        ConsoleApiCalledType::Warning => ConsoleLogLevel::Warning,
+        ConsoleApiCalledType::Debug | ConsoleApiCalledType::Trace => ConsoleLogLevel::Debug,
+        _ => ConsoleLogLevel::Info,
+    }
+}
+
+/// The url, line and column of the frame the log came from.
+fn top_frame(stack_trace: &Option<StackTrace>) -> Option<(&str, i64, i64)> {
+    stack_trace
+        .as_ref()
+        .and_then(|trace| trace.call_frames.first())
+        .map(|frame| (frame.url.as_str(), frame.line_number, frame.column_number))
+}
+
+/// The text of a logged value. Strings are kept as is and other values use the preview chrome gives.
+fn remote_object_text(object: &RemoteObject) -> String {
+    match object.value {
+        Some(ref value) if value.is_string() => value.as_str().unwrap_or_default().into(),
+        Some(ref value) if object.description.is_none() => value.to_string(),
+        _ => match (&object.description, &object.unserializable_value) {
+            (Some(description), _) => description.clone(),
+            (_, Some(value)) => value.inner().clone(),
+            _ => object.r#type.as_ref().into(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chromiumoxide::cdp::js_protocol::runtime::{RemoteObjectType, UnserializableValue};
+
+    #[test]
+    fn test_console_log_text() {
+        let mut object = RemoteObject::new(RemoteObjectType::String);
+        object.value = Some("failed to load".into());
+        assert_eq!(remote_object_text(&object), "failed to load");
+
+        let mut object = RemoteObject::new(RemoteObjectType::Number);
+        object.value = Some(42.into());
+        assert_eq!(remote_object_text(&object), "42");
+
+        let mut object = RemoteObject::new(RemoteObjectType::Number);
+        object.unserializable_value = Some(UnserializableValue::new("NaN"));
+        assert_eq!(remote_object_text(&object), "NaN");
+
+        let mut object = RemoteObject::new(RemoteObjectType::Object);
+        object.description = Some("TypeError: x is undefined".into());
+        assert_eq!(remote_object_text(&object), "TypeError: x is undefined");
+
+        assert_eq!(
+            remote_object_text(&RemoteObject::new(RemoteObjectType::Undefined)),
+            "undefined"
+        );
+
+        assert_eq!(
+            console_level(&ConsoleApiCalledType::Assert),
+            ConsoleLogLevel::Error
+        );
+        assert_eq!(
+            console_level(&ConsoleApiCalledType::Table),
+            ConsoleLogLevel::Info
+        );
+    }
+}
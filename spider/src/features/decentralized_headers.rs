@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::iter::FromIterator;
 use std::str::FromStr;
+use std::time::Duration;
 
 /// The prefix of all header values retrieved & proxied by a worker
 pub const WORKER_PROXY_HEADER_PREFIX: &'static str = "zz-spider-r--";
@@ -180,11 +181,188 @@ pub fn extract_proxy_headers<T: Clone>(src: &HeaderMap<T>) -> HeaderMap<T> {
     )
 }
 
+/// The header enabling chrome rendering on a worker with the `chrome` feature.
+pub const RENDER_HEADER: HeaderName = HeaderName::from_static(const_format::concatcp!(
+    WORKER_PROXY_HEADER_PREFIX,
+    "render"
+));
+
+/// The header with the css selector to wait for before returning the rendered html.
+pub const RENDER_WAIT_FOR_SELECTOR_HEADER: HeaderName = HeaderName::from_static(
+    const_format::concatcp!(WORKER_PROXY_HEADER_PREFIX, "render-wait-for-selector"),
+);
+
+/// The header with the max milliseconds to wait for the network to be idle.
+pub const RENDER_WAIT_FOR_IDLE_NETWORK_HEADER: HeaderName = HeaderName::from_static(
+    const_format::concatcp!(WORKER_PROXY_HEADER_PREFIX, "render-wait-for-idle-network"),
+);
+
+/// The header with the milliseconds to wait before returning the rendered html.
+pub const RENDER_WAIT_FOR_DELAY_HEADER: HeaderName = HeaderName::from_static(
+    const_format::concatcp!(WORKER_PROXY_HEADER_PREFIX, "render-wait-for-delay"),
+);
+
+/// The header to take a screenshot of the `viewport` or the `full` page.
+pub const RENDER_SCREENSHOT_HEADER: HeaderName = HeaderName::from_static(const_format::concatcp!(
+    WORKER_PROXY_HEADER_PREFIX,
+    "render-screenshot"
+));
+
+/// The header with the percent encoded javascript to run on the page before returning the html.
+pub const RENDER_SCRIPT_HEADER: HeaderName = HeaderName::from_static(const_format::concatcp!(
+    WORKER_PROXY_HEADER_PREFIX,
+    "render-script"
+));
+
+/// The chrome render options sent to a worker with the `chrome` feature using the request headers.
+/// ```rust
+/// use spider::features::decentralized_headers::WorkerRenderOptions;
+///
+/// let mut options = WorkerRenderOptions::new();
+/// options.wait_for_selector = Some("main".into());
+/// options.screenshot = true;
+///
+/// let headers = options.headers();
+/// assert_eq!(WorkerRenderOptions::from_headers(&headers), Some(options));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WorkerRenderOptions {
+    /// Wait for the css selector before returning the html.
+    pub wait_for_selector: Option<String>,
+    /// Wait for the network to be idle up to the duration.
+    pub wait_for_idle_network: Option<Duration>,
+    /// Wait for the duration before returning the html.
+    pub wait_for_delay: Option<Duration>,
+    /// Take a screenshot of the page.
+    pub screenshot: bool,
+    /// Take the screenshot of the full page instead of the viewport.
+    pub full_page: bool,
+    /// The javascript to run on the page before returning the html.
+    pub script: Option<String>,
+}
+
+impl WorkerRenderOptions {
+    /// New render options rendering the page without waiting.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The request headers to send to the worker. Set the headers with `website.with_headers`.
+    pub fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        headers.insert(RENDER_HEADER, HeaderValue::from_static("true"));
+
+        if let Some(value) = self
+            .wait_for_selector
+            .as_ref()
+            .and_then(|selector| HeaderValue::from_str(selector).ok())
+        {
+            headers.insert(RENDER_WAIT_FOR_SELECTOR_HEADER, value);
+        }
+        if let Some(timeout) = self.wait_for_idle_network {
+            headers.insert(
+                RENDER_WAIT_FOR_IDLE_NETWORK_HEADER,
+                HeaderValue::from(timeout.as_millis() as u64),
+            );
+        }
+        if let Some(delay) = self.wait_for_delay {
+            headers.insert(
+                RENDER_WAIT_FOR_DELAY_HEADER,
+                HeaderValue::from(delay.as_millis() as u64),
+            );
+        }
+        if self.screenshot {
+            headers.insert(
+                RENDER_SCREENSHOT_HEADER,
+                HeaderValue::from_static(if self.full_page { "full" } else { "viewport" }),
+            );
+        }
+        if let Some(value) = self.script.as_ref().and_then(|script| {
+            HeaderValue::from_str(
+                &percent_encoding::utf8_percent_encode(script, percent_encoding::NON_ALPHANUMERIC)
+                    .to_string(),
+            )
+            .ok()
+        }) {
+            headers.insert(RENDER_SCRIPT_HEADER, value);
+        }
+
+        headers
+    }
+
+    /// Get the render options from the request headers. Returns `None` if rendering was not requested.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        Self::from_pairs(
+            headers
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_bytes())),
+        )
+    }
+
+    /// Get the render options from the header name and value pairs. Returns `None` if rendering was not requested.
+    pub fn from_pairs<'a, I: IntoIterator<Item = (&'a str, &'a [u8])>>(pairs: I) -> Option<Self> {
+        let mut options = Self::default();
+        let mut render = false;
+
+        let millis = |value: &str| value.trim().parse::<u64>().ok().map(Duration::from_millis);
+
+        for (key, value) in pairs {
+            let key = match key.strip_prefix(WORKER_PROXY_HEADER_PREFIX) {
+                Some(key) => key,
+                _ => continue,
+            };
+            let value = match std::str::from_utf8(value) {
+                Ok(value) => value,
+                _ => continue,
+            };
+
+            match key {
+                "render" => render = value != "false",
+                "render-wait-for-selector" => options.wait_for_selector = Some(value.into()),
+                "render-wait-for-idle-network" => options.wait_for_idle_network = millis(value),
+                "render-wait-for-delay" => options.wait_for_delay = millis(value),
+                "render-screenshot" => {
+                    options.screenshot = true;
+                    options.full_page = value == "full";
+                }
+                "render-script" => {
+                    options.script = percent_encoding::percent_decode_str(value)
+                        .decode_utf8()
+                        .ok()
+                        .map(|script| script.into_owned())
+                }
+                _ => (),
+            }
+        }
+
+        if render {
+            Some(options)
+        } else {
+            None
+        }
+    }
+}
+
+/// The rendered page returned by a worker with the `chrome` feature in the flexbuffers payload.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkerRenderedPage {
+    /// The links found on the rendered page.
+    pub links: hashbrown::HashSet<crate::CaseInsensitiveString>,
+    /// The rendered html.
+    pub html: Option<Vec<u8>>,
+    /// The screenshot bytes of the page.
+    pub screenshot: Option<Vec<u8>>,
+    /// The status code of the page.
+    pub status_code: u16,
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::{
-        extract_proxy_headers, set_prefix, WorkerProxyHeaderBuilder, PROXIED_ORIGINAL_STATUS,
-        STATUS_CODE_HEADER_FIELD,
+        extract_proxy_headers, set_prefix, WorkerProxyHeaderBuilder, WorkerRenderOptions,
+        PROXIED_ORIGINAL_STATUS, STATUS_CODE_HEADER_FIELD,
     };
     use reqwest::header::HeaderValue;
 
@@ -232,4 +410,21 @@ pub mod tests {
             HeaderValue::from(404)
         );
     }
+
+    #[test]
+    pub fn can_pass_render_options() {
+        let options = WorkerRenderOptions {
+            wait_for_selector: Some("#content".into()),
+            wait_for_idle_network: Some(std::time::Duration::from_millis(500)),
+            screenshot: true,
+            full_page: true,
+            script: Some("document.title = 'spider';\nwindow.scrollTo(0, 100);".into()),
+            ..Default::default()
+        };
+
+        let headers = options.headers();
+
+        assert_eq!(WorkerRenderOptions::from_headers(&headers), Some(options));
+        assert_eq!(WorkerRenderOptions::from_headers(&Default::default()), None);
+    }
 }
@@ -0,0 +1,782 @@
+use chromiumoxide::cdp::browser_protocol::network::{
+    EventLoadingFailed, EventLoadingFinished, EventRequestWillBeSent, EventResponseReceived,
+    GetResponseBodyParams, Headers, Request, ResourceTiming, Response,
+};
+use chromiumoxide::cdp::browser_protocol::page::{EventDomContentEventFired, EventLoadEventFired};
+use chromiumoxide::Page;
+use hashbrown::HashMap;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+
+/// The HAR version written.
+const HAR_VERSION: &str = "1.2";
+/// The id of the page in the HAR.
+const HAR_PAGE_ID: &str = "page_1";
+
+/// The HTTP Archive of the page network activity. [HAR 1.2](http://www.softwareishard.com/blog/har-12-spec/)
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Har {
+    /// The log of the archive.
+    pub log: HarLog,
+}
+
+/// The root of the HAR.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarLog {
+    /// The HAR format version.
+    pub version: String,
+    /// The application that created the log.
+    pub creator: HarCreator,
+    /// The pages of the log.
+    pub pages: Vec<HarPage>,
+    /// The requests in the order sent.
+    pub entries: Vec<HarEntry>,
+}
+
+/// The application that created the log.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarCreator {
+    /// The name of the application.
+    pub name: String,
+    /// The version of the application.
+    pub version: String,
+}
+
+/// The page of the log.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarPage {
+    /// The time the page load started in ISO 8601.
+    pub started_date_time: String,
+    /// The id of the page referenced by the entries.
+    pub id: String,
+    /// The title of the page.
+    pub title: String,
+    /// The page load timings.
+    pub page_timings: HarPageTimings,
+}
+
+/// The page load timings in milliseconds since the start. -1 when the event did not fire.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarPageTimings {
+    /// The `DOMContentLoaded` event.
+    pub on_content_load: f64,
+    /// The `load` event.
+    pub on_load: f64,
+}
+
+/// A request of the page.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarEntry {
+    /// The id of the page of the request.
+    pub pageref: String,
+    /// The time the request started in ISO 8601.
+    pub started_date_time: String,
+    /// The total time of the request in milliseconds.
+    pub time: f64,
+    /// The request.
+    pub request: HarRequest,
+    /// The response. The status is 0 when the request failed.
+    pub response: HarResponse,
+    /// The cache usage.
+    pub cache: HarCache,
+    /// The timings of the request phases.
+    pub timings: HarTimings,
+    /// The IP address of the server.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")
+    )]
+    pub server_ip_address: Option<String>,
+    /// The id of the TCP connection.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub connection: Option<String>,
+    /// What started the request.
+    #[cfg_attr(feature = "serde", serde(rename = "_initiator"))]
+    pub initiator: HarInitiator,
+    /// The chrome resource type like `Document` or `Script`.
+    #[cfg_attr(feature = "serde", serde(rename = "_resourceType"))]
+    pub resource_type: String,
+    /// The network error when the request failed or was blocked.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "_error", skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<String>,
+}
+
+/// The request sent.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarRequest {
+    /// The request method.
+    pub method: String,
+    /// The absolute url of the request.
+    pub url: String,
+    /// The HTTP version.
+    pub http_version: String,
+    /// The cookies sent.
+    pub cookies: Vec<HarNameValue>,
+    /// The headers sent.
+    pub headers: Vec<HarNameValue>,
+    /// The query string parameters of the url.
+    pub query_string: Vec<HarNameValue>,
+    /// The posted data.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub post_data: Option<HarPostData>,
+    /// The size of the headers in bytes. -1 when unknown.
+    pub headers_size: i64,
+    /// The size of the posted data in bytes.
+    pub body_size: i64,
+}
+
+/// The response received.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarResponse {
+    /// The response status.
+    pub status: i64,
+    /// The response status text.
+    pub status_text: String,
+    /// The HTTP version.
+    pub http_version: String,
+    /// The cookies set.
+    pub cookies: Vec<HarNameValue>,
+    /// The headers received.
+    pub headers: Vec<HarNameValue>,
+    /// The response body.
+    pub content: HarContent,
+    /// The `Location` of the redirect.
+    #[cfg_attr(feature = "serde", serde(rename = "redirectURL"))]
+    pub redirect_url: String,
+    /// The size of the headers in bytes. -1 when unknown.
+    pub headers_size: i64,
+    /// The bytes transferred for the body. -1 when unknown.
+    pub body_size: i64,
+}
+
+/// A name and value pair for the headers, cookies and query string.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarNameValue {
+    /// The name.
+    pub name: String,
+    /// The value.
+    pub value: String,
+}
+
+/// The posted data of the request.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarPostData {
+    /// The mime type of the data.
+    pub mime_type: String,
+    /// The posted data.
+    pub text: String,
+}
+
+/// The response body.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarContent {
+    /// The decoded size of the body in bytes.
+    pub size: i64,
+    /// The mime type of the body.
+    pub mime_type: String,
+    /// The body. This is only set when the response bodies are captured.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub text: Option<String>,
+    /// The `base64` encoding of the binary bodies.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub encoding: Option<String>,
+}
+
+/// The cache usage. Chrome does not report the cache entries.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarCache {}
+
+/// The timings of the request phases in milliseconds. -1 when the phase does not apply.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HarTimings {
+    /// Waiting in the queue for a connection.
+    pub blocked: f64,
+    /// The DNS resolution.
+    pub dns: f64,
+    /// The TCP connection including the TLS handshake.
+    pub connect: f64,
+    /// Sending the request.
+    pub send: f64,
+    /// Waiting for the first byte of the response.
+    pub wait: f64,
+    /// Receiving the response.
+    pub receive: f64,
+    /// The TLS handshake.
+    pub ssl: f64,
+}
+
+impl Default for HarTimings {
+    fn default() -> Self {
+        Self {
+            blocked: -1.0,
+            dns: -1.0,
+            connect: -1.0,
+            send: 0.0,
+            wait: 0.0,
+            receive: 0.0,
+            ssl: -1.0,
+        }
+    }
+}
+
+/// What started the request.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HarInitiator {
+    /// The initiator type like `parser` or `script`.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub initiator_type: String,
+    /// The url of the initiator.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub url: Option<String>,
+    /// The line of the initiator.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub line_number: Option<f64>,
+}
+
+impl Har {
+    /// The entries of the requests that failed or were blocked.
+    pub fn failed_entries(&self) -> impl Iterator<Item = &HarEntry> {
+        self.log
+            .entries
+            .iter()
+            .filter(|entry| entry.error.is_some())
+    }
+
+    /// The entries sorted by the slowest total time first.
+    pub fn slowest_entries(&self) -> Vec<&HarEntry> {
+        let mut entries: Vec<&HarEntry> = self.log.entries.iter().collect();
+        entries.sort_by(|a, b| b.time.total_cmp(&a.time));
+        entries
+    }
+}
+
+/// A request collected before the HAR is built.
+#[derive(Debug, Default)]
+struct PendingEntry {
+    /// The entry with the request set.
+    entry: HarEntry,
+    /// The monotonic time the request was sent in seconds.
+    started: f64,
+    /// The monotonic time the request finished in seconds.
+    finished: Option<f64>,
+    /// The network timing of the response.
+    timing: Option<ResourceTiming>,
+}
+
+/// Record the network activity of a page into a HAR. The recorder listens to the same network events as the chrome network manager and stops when finished.
+pub struct HarRecorder {
+    /// Stop the recording.
+    stop: oneshot::Sender<()>,
+    /// The recording task.
+    handle: JoinHandle<Recording>,
+}
+
+/// The events recorded for the page.
+#[derive(Debug, Default)]
+struct Recording {
+    /// The requests in the order sent.
+    entries: Vec<PendingEntry>,
+    /// The wall time and monotonic time of the first request in seconds.
+    start: Option<(f64, f64)>,
+    /// The monotonic time of the `DOMContentLoaded` event.
+    on_content_load: Option<f64>,
+    /// The monotonic time of the `load` event.
+    on_load: Option<f64>,
+}
+
+impl HarRecorder {
+    /// Start recording the page network activity. The response bodies up to `body_limit` bytes are captured when set.
+    pub async fn new(page: &Page, body_limit: Option<usize>) -> Option<Self> {
+        let (sent, received, finished, failed, content_loaded, loaded) = tokio::join!(
+            page.event_listener::<EventRequestWillBeSent>(),
+            page.event_listener::<EventResponseReceived>(),
+            page.event_listener::<EventLoadingFinished>(),
+            page.event_listener::<EventLoadingFailed>(),
+            page.event_listener::<EventDomContentEventFired>(),
+            page.event_listener::<EventLoadEventFired>(),
+        );
+
+        let (mut sent, mut received, mut finished, mut failed) =
+            (sent.ok()?, received.ok()?, finished.ok()?, failed.ok()?);
+        let (mut content_loaded, mut loaded) = (content_loaded.ok()?, loaded.ok()?);
+
+        let (stop, mut stop_rx) = oneshot::channel::<()>();
+        let page = page.clone();
+
+        let handle = crate::utils::spawn_task("har_recorder", async move {
+            let mut entries: Vec<PendingEntry> = Vec::new();
+            let mut ids: HashMap<String, usize> = HashMap::new();
+            // the wall time and monotonic time of the first request.
+            let mut start: Option<(f64, f64)> = None;
+            let mut on_content_load = None;
+            let mut on_load = None;
+
+            loop {
+                tokio::select! {
+                    biased;
+                    Some(event) = sent.next() => {
+                        let id = event.request_id.inner().clone();
+
+                        // a redirect re-uses the request id and completes the previous entry.
+                        if let Some(redirect) = &event.redirect_response {
+                            if let Some(pending) = ids.get(&id).and_then(|i| entries.get_mut(*i)) {
+                                set_response(pending, redirect, &event.request.url);
+                                pending.finished = Some(*event.timestamp.inner());
+                            }
+                        }
+
+                        let wall_time = *event.wall_time.inner();
+                        let timestamp = *event.timestamp.inner();
+
+                        if start.is_none() {
+                            start = Some((wall_time, timestamp));
+                        }
+
+                        ids.insert(id, entries.len());
+                        entries.push(PendingEntry {
+                            entry: HarEntry {
+                                pageref: HAR_PAGE_ID.into(),
+                                started_date_time: iso_date_time(wall_time),
+                                request: har_request(&event.request),
+                                initiator: HarInitiator {
+                                    initiator_type: event.initiator.r#type.as_ref().into(),
+                                    url: event.initiator.url.clone(),
+                                    line_number: event.initiator.line_number,
+                                },
+                                resource_type: event
+                                    .r#type
+                                    .as_ref()
+                                    .map(|t| t.as_ref().to_string())
+                                    .unwrap_or_default(),
+                                ..Default::default()
+                            },
+                            started: timestamp,
+                            ..Default::default()
+                        });
+                    }
+                    Some(event) = received.next() => {
+                        if let Some(pending) = ids
+                            .get(event.request_id.inner())
+                            .and_then(|i| entries.get_mut(*i))
+                        {
+                            set_response(pending, &event.response, "");
+                            pending.entry.resource_type = event.r#type.as_ref().into();
+                        }
+                    }
+                    Some(event) = finished.next() => {
+                        if let Some(pending) = ids
+                            .get(event.request_id.inner())
+                            .and_then(|i| entries.get_mut(*i))
+                        {
+                            pending.finished = Some(*event.timestamp.inner());
+                            pending.entry.response.body_size = event.encoded_data_length as i64;
+
+                            if let Some(limit) = body_limit {
+                                if event.encoded_data_length as usize <= limit {
+                                    if let Ok(body) = page
+                                        .execute(GetResponseBodyParams::new(event.request_id.clone()))
+                                        .await
+                                    {
+                                        set_content(&mut pending.entry.response.content, &body.body, body.base64_encoded, limit);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Some(event) = failed.next() => {
+                        if let Some(pending) = ids
+                            .get(event.request_id.inner())
+                            .and_then(|i| entries.get_mut(*i))
+                        {
+                            pending.finished = Some(*event.timestamp.inner());
+                            pending.entry.error = Some(match &event.blocked_reason {
+                                Some(reason) => format!("{} ({})", event.error_text, reason.as_ref()),
+                                _ => event.error_text.clone(),
+                            });
+                        }
+                    }
+                    Some(event) = content_loaded.next() => {
+                        on_content_load.get_or_insert(*event.timestamp.inner());
+                    }
+                    Some(event) = loaded.next() => {
+                        on_load.get_or_insert(*event.timestamp.inner());
+                    }
+                    _ = &mut stop_rx => break,
+                    else => break,
+                }
+            }
+
+            Recording {
+                entries,
+                start,
+                on_content_load,
+                on_load,
+            }
+        });
+
+        Some(Self { stop, handle })
+    }
+
+    /// Stop the recording and build the HAR. The title of the page is set to the url.
+    pub async fn finish(self, url: &str) -> Har {
+        let _ = self.stop.send(());
+
+        let Recording {
+            entries,
+            start,
+            on_content_load,
+            on_load,
+        } = self.handle.await.unwrap_or_default();
+
+        let (started_wall, started) = start.unwrap_or_default();
+        let since_start = |time: Option<f64>| match time {
+            Some(time) if start.is_some() => (time - started) * 1000.0,
+            _ => -1.0,
+        };
+
+        Har {
+            log: HarLog {
+                version: HAR_VERSION.into(),
+                creator: HarCreator {
+                    name: "spider".into(),
+                    version: env!("CARGO_PKG_VERSION").into(),
+                },
+                pages: vec![HarPage {
+                    started_date_time: iso_date_time(started_wall),
+                    id: HAR_PAGE_ID.into(),
+                    title: url.into(),
+                    page_timings: HarPageTimings {
+                        on_content_load: since_start(on_content_load),
+                        on_load: since_start(on_load),
+                    },
+                }],
+                entries: entries.into_iter().map(build_entry).collect(),
+            },
+        }
+    }
+}
+
+/// Complete the entry with the timings.
+fn build_entry(pending: PendingEntry) -> HarEntry {
+    let PendingEntry {
+        mut entry,
+        started,
+        finished,
+        timing,
+    } = pending;
+
+    match timing {
+        Some(timing) if timing.request_time > 0.0 => {
+            let phase = |start: f64, end: f64| {
+                if start >= 0.0 && end >= start {
+                    end - start
+                } else {
+                    -1.0
+                }
+            };
+
+            let blocked = [timing.dns_start, timing.connect_start, timing.send_start]
+                .into_iter()
+                .find(|t| *t >= 0.0)
+                .unwrap_or_default();
+
+            let receive = match finished {
+                Some(finished) => ((finished - timing.request_time) * 1000.0
+                    - timing.receive_headers_end)
+                    .max(0.0),
+                _ => 0.0,
+            };
+
+            entry.timings = HarTimings {
+                blocked,
+                dns: phase(timing.dns_start, timing.dns_end),
+                connect: phase(timing.connect_start, timing.connect_end),
+                ssl: phase(timing.ssl_start, timing.ssl_end),
+                send: phase(timing.send_start, timing.send_end).max(0.0),
+                wait: (timing.receive_headers_end - timing.send_end).max(0.0),
+                receive,
+            };
+
+            // the ssl time is part of the connect time.
+            entry.time = [
+                entry.timings.blocked,
+                entry.timings.dns,
+                entry.timings.connect,
+                entry.timings.send,
+                entry.timings.wait,
+                entry.timings.receive,
+            ]
+            .into_iter()
+            .filter(|t| *t > 0.0)
+            .sum();
+        }
+        _ => {
+            if let Some(finished) = finished {
+                entry.time = ((finished - started) * 1000.0).max(0.0);
+                entry.timings.receive = entry.time;
+            }
+        }
+    }
+
+    if entry.response.status == 0 && entry.response.body_size == 0 {
+        entry.response.body_size = -1;
+    }
+
+    entry
+}
+
+/// Set the response of the entry.
+fn set_response(pending: &mut PendingEntry, response: &Response, redirect_url: &str) {
+    let headers = name_values(&response.headers);
+    let http_version = response.protocol.clone().unwrap_or_default();
+
+    if pending.entry.request.http_version.is_empty() {
+        pending.entry.request.http_version.clone_from(&http_version);
+    }
+
+    if let Some(request_headers) = &response.request_headers {
+        pending.entry.request.headers = name_values(request_headers);
+    }
+
+    pending.entry.response = HarResponse {
+        status: response.status,
+        status_text: response.status_text.clone(),
+        http_version,
+        cookies: set_cookies(&headers),
+        redirect_url: if redirect_url.is_empty() {
+            header_value(&headers, "location").unwrap_or_default()
+        } else {
+            redirect_url.into()
+        },
+        headers,
+        content: HarContent {
+            size: 0,
+            mime_type: response.mime_type.clone(),
+            text: None,
+            encoding: None,
+        },
+        headers_size: -1,
+        body_size: response.encoded_data_length as i64,
+    };
+    pending.entry.server_ip_address = response.remote_ip_address.clone();
+    pending.entry.connection = if response.connection_id > 0.0 {
+        Some(response.connection_id.to_string())
+    } else {
+        None
+    };
+    pending.timing = response.timing.clone();
+}
+
+/// Set the body of the response when it is within the limit.
+fn set_content(content: &mut HarContent, body: &str, base64_encoded: bool, limit: usize) {
+    let size = if base64_encoded {
+        body.len() / 4 * 3
+    } else {
+        body.len()
+    };
+
+    content.size = size as i64;
+
+    if size <= limit {
+        content.text = Some(body.into());
+        content.encoding = if base64_encoded {
+            Some("base64".into())
+        } else {
+            None
+        };
+    }
+}
+
+/// The request of the entry.
+fn har_request(request: &Request) -> HarRequest {
+    let headers = name_values(&request.headers);
+
+    let post_data = request.post_data_entries.as_ref().map(|entries| {
+        let mut data = Vec::new();
+
+        for bytes in entries.iter().filter_map(|entry| entry.bytes.as_ref()) {
+            data.extend(chromiumoxide::utils::base64::decode(&bytes.0).unwrap_or_default());
+        }
+
+        HarPostData {
+            mime_type: header_value(&headers, "content-type").unwrap_or_default(),
+            text: String::from_utf8_lossy(&data).into_owned(),
+        }
+    });
+
+    HarRequest {
+        method: request.method.clone(),
+        url: request.url.clone(),
+        http_version: String::new(),
+        cookies: header_value(&headers, "cookie")
+            .map(|cookie| cookie.split(';').filter_map(name_value).collect())
+            .unwrap_or_default(),
+        query_string: url::Url::parse(&request.url)
+            .map(|url| {
+                url.query_pairs()
+                    .map(|(name, value)| HarNameValue {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        body_size: post_data.as_ref().map_or(0, |data| data.text.len() as i64),
+        post_data,
+        headers,
+        headers_size: -1,
+    }
+}
+
+/// The cookies of the `Set-Cookie` headers. Chrome joins multiple headers with a new line.
+fn set_cookies(headers: &[HarNameValue]) -> Vec<HarNameValue> {
+    headers
+        .iter()
+        .filter(|header| header.name.eq_ignore_ascii_case("set-cookie"))
+        .flat_map(|header| header.value.lines())
+        .filter_map(|cookie| cookie.split(';').next().and_then(name_value))
+        .collect()
+}
+
+/// Parse a `name=value` pair.
+fn name_value(pair: &str) -> Option<HarNameValue> {
+    let (name, value) = pair.trim().split_once('=')?;
+
+    Some(HarNameValue {
+        name: name.trim().into(),
+        value: value.trim().into(),
+    })
+}
+
+/// The value of the header by the case insensitive name.
+fn header_value(headers: &[HarNameValue], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.clone())
+}
+
+/// The headers as name and value pairs.
+fn name_values(headers: &Headers) -> Vec<HarNameValue> {
+    match headers.inner().as_object() {
+        Some(headers) => headers
+            .iter()
+            .map(|(name, value)| HarNameValue {
+                name: name.clone(),
+                value: match value.as_str() {
+                    Some(value) => value.into(),
+                    _ => value.to_string(),
+                },
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The seconds since the unix epoch in ISO 8601 with milliseconds.
+pub(crate) fn iso_date_time(seconds: f64) -> String {
+    let millis = (seconds * 1000.0).max(0.0) as i64;
+    let days = millis.div_euclid(86_400_000);
+    let day_millis = millis.rem_euclid(86_400_000);
+
+    // the civil date of the days since the epoch.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        day_millis / 3_600_000,
+        day_millis / 60_000 % 60,
+        day_millis / 1000 % 60,
+        day_millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso_date_time() {
+        assert_eq!(iso_date_time(0.0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(iso_date_time(951_782_400.5), "2000-02-29T00:00:00.500Z");
+        assert_eq!(iso_date_time(1_700_000_000.123), "2023-11-14T22:13:20.123Z");
+    }
+
+    #[test]
+    fn test_build_entry_timings() {
+        let timing = ResourceTiming {
+            request_time: 100.0,
+            dns_start: 1.0,
+            dns_end: 5.0,
+            connect_start: 5.0,
+            connect_end: 25.0,
+            ssl_start: 10.0,
+            ssl_end: 25.0,
+            send_start: 26.0,
+            send_end: 27.0,
+            receive_headers_start: 70.0,
+            receive_headers_end: 77.0,
+            proxy_start: -1.0,
+            proxy_end: -1.0,
+            worker_start: -1.0,
+            worker_ready: -1.0,
+            worker_fetch_start: -1.0,
+            worker_respond_with_settled: -1.0,
+            worker_router_evaluation_start: None,
+            worker_cache_lookup_start: None,
+            push_start: 0.0,
+            push_end: 0.0,
+        };
+
+        let entry = build_entry(PendingEntry {
+            started: 100.0,
+            finished: Some(100.1),
+            timing: Some(timing),
+            ..Default::default()
+        });
+
+        assert_eq!(entry.timings.dns, 4.0);
+        assert_eq!(entry.timings.connect, 20.0);
+        assert_eq!(entry.timings.ssl, 15.0);
+        assert_eq!(entry.timings.wait, 50.0);
+        assert!((entry.timings.receive - 23.0).abs() < 0.001);
+        assert!((entry.time - 99.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::features::chrome_common::{run_automation_steps, LoginCheck, LoginFlow};
+use crate::page::Page;
+use chromiumoxide::cdp::browser_protocol::network::{
+    Cookie, CookieParam, SetCookiesParams, TimeSinceEpoch,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// The max time to load the login page.
+const LOGIN_PAGE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The login flow state shared by the pages of a crawl. The logins run one at a time and the pages redirected to the login url during a login wait for it instead of starting another.
+#[derive(Debug)]
+pub struct LoginSession {
+    /// The login flow.
+    flow: LoginFlow,
+    /// The cookies of the last successful login.
+    cookies: Mutex<Vec<CookieParam>>,
+    /// The successful logins.
+    logins: AtomicUsize,
+    /// The login attempts.
+    attempts: AtomicUsize,
+    #[cfg(feature = "cookies")]
+    /// The HTTP client cookie jar.
+    jar: Option<std::sync::Arc<crate::client::cookie::Jar>>,
+}
+
+impl LoginSession {
+    /// A new login session.
+    pub fn new(
+        flow: LoginFlow,
+        #[cfg(feature = "cookies")] jar: Option<std::sync::Arc<crate::client::cookie::Jar>>,
+    ) -> Self {
+        Self {
+            flow,
+            cookies: Default::default(),
+            logins: AtomicUsize::new(0),
+            attempts: AtomicUsize::new(0),
+            #[cfg(feature = "cookies")]
+            jar,
+        }
+    }
+
+    /// The login flow.
+    pub fn flow(&self) -> &LoginFlow {
+        &self.flow
+    }
+
+    /// The amount of successful logins. Capture it before fetching a page to pass to [`LoginSession::relogin`].
+    pub fn generation(&self) -> usize {
+        self.logins.load(Ordering::Relaxed)
+    }
+
+    /// The page was redirected to the login url.
+    pub fn is_login_redirect(&self, page: &Page) -> bool {
+        !self.flow.is_login_url(page.get_url()) && self.flow.is_login_url(page.get_url_final())
+    }
+
+    /// Run the login flow on the chrome page. The cookies are shared with the browser context of the page and the HTTP client.
+    pub async fn login(&self, page: &chromiumoxide::Page) -> bool {
+        let mut cookies = self.cookies.lock().await;
+
+        self.run(page, &mut cookies).await
+    }
+
+    /// Run the login flow again after a page was redirected to the login url. Returns `true` when the page can be fetched again, either after this login or a login that finished since the `generation` was captured.
+    pub async fn relogin(&self, page: &chromiumoxide::Page, generation: usize) -> bool {
+        let mut cookies = self.cookies.lock().await;
+
+        if self.generation() != generation {
+            return true;
+        }
+
+        if self.attempts.load(Ordering::Relaxed) > self.flow.max_relogins as usize {
+            return false;
+        }
+
+        log::info!("session expired, running the login flow {}", self.flow.url);
+
+        self.run(page, &mut cookies).await
+    }
+
+    /// Set the cookies of the last login on the browser context of the page. This shares the login with the pages of other browsers.
+    pub async fn apply(&self, page: &chromiumoxide::Page) {
+        let cookies = self.cookies.lock().await.clone();
+
+        if !cookies.is_empty() {
+            let _ = page.execute(SetCookiesParams::new(cookies)).await;
+        }
+    }
+
+    /// Run the login steps and the success check.
+    async fn run(&self, page: &chromiumoxide::Page, cookies: &mut Vec<CookieParam>) -> bool {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+
+        if !matches!(
+            tokio::time::timeout(LOGIN_PAGE_TIMEOUT, page.goto(self.flow.url.as_str())).await,
+            Ok(Ok(_))
+        ) {
+            log::error!("failed to load the login page {}", self.flow.url);
+            return false;
+        }
+
+        run_automation_steps(page, &self.flow.steps, &mut Default::default()).await;
+
+        if !self.check(page).await {
+            log::error!(
+                "the login flow did not pass the success check {}",
+                self.flow.url
+            );
+            return false;
+        }
+
+        match page.get_cookies().await {
+            Ok(session) => {
+                self.share_cookies(&session);
+                *cookies = session.iter().map(cookie_param).collect();
+            }
+            Err(e) => log::warn!("failed to read the login cookies: {e}"),
+        }
+
+        self.logins.fetch_add(1, Ordering::Relaxed);
+
+        true
+    }
+
+    /// Wait for the success check of the login.
+    async fn check(&self, page: &chromiumoxide::Page) -> bool {
+        let timeout = Duration::from_millis(self.flow.timeout);
+
+        match self.flow.success {
+            LoginCheck::Selector(ref selector) => {
+                crate::utils::wait_for_selector(page, Some(timeout), selector).await
+            }
+            LoginCheck::UrlChanged => tokio::time::timeout(timeout, async {
+                loop {
+                    if let Ok(Some(url)) = page.url().await {
+                        if !self.flow.is_login_url(&url) {
+                            break;
+                        }
+                    }
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            })
+            .await
+            .is_ok(),
+        }
+    }
+
+    /// Add the cookies to the HTTP client cookie jar.
+    #[cfg(feature = "cookies")]
+    fn share_cookies(&self, cookies: &[Cookie]) {
+        if let Some(ref jar) = self.jar {
+            for cookie in cookies {
+                let host = cookie.domain.trim_start_matches('.');
+                let scheme = if cookie.secure { "https" } else { "http" };
+
+                if let Ok(url) = url::Url::parse(&format!("{scheme}://{host}{}", cookie.path)) {
+                    let mut set_cookie =
+                        format!("{}={}; Path={}", cookie.name, cookie.value, cookie.path);
+
+                    if cookie.domain.starts_with('.') {
+                        set_cookie.push_str("; Domain=");
+                        set_cookie.push_str(host);
+                    }
+                    if cookie.secure {
+                        set_cookie.push_str("; Secure");
+                    }
+                    if cookie.http_only {
+                        set_cookie.push_str("; HttpOnly");
+                    }
+
+                    jar.add_cookie_str(&set_cookie, &url);
+                }
+            }
+        }
+    }
+
+    /// Add the cookies to the HTTP client cookie jar. This does nothing without the `cookies` flag enabled.
+    #[cfg(not(feature = "cookies"))]
+    fn share_cookies(&self, _cookies: &[Cookie]) {}
+}
+
+/// The cookie to set on another browser context.
+fn cookie_param(cookie: &Cookie) -> CookieParam {
+    let mut param = CookieParam::new(cookie.name.clone(), cookie.value.clone());
+
+    param.domain = Some(cookie.domain.clone());
+    param.path = Some(cookie.path.clone());
+    param.secure = Some(cookie.secure);
+    param.http_only = Some(cookie.http_only);
+    param.same_site = cookie.same_site.clone();
+
+    if !cookie.session {
+        param.expires = Some(TimeSinceEpoch::new(cookie.expires));
+    }
+
+    param
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::chrome_common::WebAutomation;
+
+    #[test]
+    fn test_login_url() {
+        let flow = LoginFlow::new(
+            "https://spider.cloud/login",
+            vec![WebAutomation::Click("button[type=submit]".into())],
+            LoginCheck::UrlChanged,
+        );
+
+        assert!(flow.is_login_url("https://spider.cloud/login/?next=%2Fpricing"));
+        assert!(flow.is_login_url("https://spider.cloud/LOGIN#form"));
+        assert!(!flow.is_login_url("https://spider.cloud/login-help"));
+        assert!(!flow.is_login_url("https://spider.cloud/pricing"));
+    }
+}
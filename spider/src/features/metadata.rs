@@ -0,0 +1,603 @@
+use lol_html::send::{DocumentContentHandlers, ElementContentHandlers, EndTagHandler};
+use lol_html::Selector;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+/// The selector of the JSON-LD blocks.
+const JSON_LD_SELECTOR: &str = r#"script[type="application/ld+json"]"#;
+
+/// The OpenGraph namespaces stored with the `og:` properties.
+const OPEN_GRAPH_PREFIXES: [&str; 7] = [
+    "og:", "fb:", "article:", "book:", "profile:", "music:", "video:",
+];
+
+/// A meta property and the content of it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetaProperty {
+    /// The property name ex: `og:title`.
+    pub property: String,
+    /// The content of the property.
+    pub content: String,
+}
+
+/// An alternate version of the page for a language or region.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alternate {
+    /// The language of the page ex: `en-US` or `x-default`.
+    pub hreflang: String,
+    /// The url of the page.
+    pub href: String,
+}
+
+/// The value of a microdata property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum MicrodataValue {
+    /// A text or url value.
+    Text(String),
+    /// A nested item.
+    Item(MicrodataItem),
+}
+
+/// A microdata property of an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MicrodataProperty {
+    /// The `itemprop` name.
+    pub name: String,
+    /// The value of the property.
+    pub value: MicrodataValue,
+}
+
+/// A microdata item declared with `itemscope`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MicrodataItem {
+    /// The `itemtype` of the item ex: `https://schema.org/Product`.
+    pub item_type: Option<String>,
+    /// The `itemid` of the item.
+    pub item_id: Option<String>,
+    /// The properties of the item in document order.
+    pub properties: Vec<MicrodataProperty>,
+}
+
+impl MicrodataItem {
+    /// Get the first value of the property.
+    pub fn get(&self, name: &str) -> Option<&MicrodataValue> {
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| &p.value)
+    }
+}
+
+/// The structured metadata embedded in a page.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageMetadata {
+    /// The `<title>` of the page.
+    pub title: Option<String>,
+    /// The `<meta name="description">` of the page.
+    pub description: Option<String>,
+    /// The `<link rel="canonical">` of the page.
+    pub canonical: Option<String>,
+    /// The OpenGraph `og:*` meta properties.
+    pub open_graph: Vec<MetaProperty>,
+    /// The Twitter card `twitter:*` meta properties.
+    pub twitter: Vec<MetaProperty>,
+    /// The JSON-LD blocks. Blocks that fail to parse are skipped.
+    pub json_ld: Vec<serde_json::Value>,
+    /// The top level microdata items.
+    pub microdata: Vec<MicrodataItem>,
+    /// The RDFa `property` values outside of the OpenGraph and Twitter namespaces.
+    pub rdfa: Vec<MetaProperty>,
+    /// The `hreflang` alternates of the page.
+    pub alternates: Vec<Alternate>,
+}
+
+impl PageMetadata {
+    /// Get the first OpenGraph property ex: `og:image`.
+    pub fn open_graph(&self, property: &str) -> Option<&str> {
+        find_property(&self.open_graph, property)
+    }
+
+    /// Get the first Twitter card property ex: `twitter:card`.
+    pub fn twitter(&self, property: &str) -> Option<&str> {
+        find_property(&self.twitter, property)
+    }
+
+    /// The page has no metadata.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Find the first content of the property.
+fn find_property<'a>(properties: &'a [MetaProperty], property: &str) -> Option<&'a str> {
+    properties
+        .iter()
+        .find(|p| p.property == property)
+        .map(|p| p.content.as_str())
+}
+
+/// Where the captured text of an element is stored.
+enum CaptureTarget {
+    /// A microdata property of the open item.
+    Microdata(Vec<String>),
+    /// An RDFa property.
+    Rdfa(String),
+}
+
+/// The text captured until the end tag of the element.
+struct Capture {
+    /// The target of the text.
+    target: CaptureTarget,
+    /// The text of the element.
+    text: String,
+}
+
+/// An open microdata item.
+struct OpenItem {
+    /// The `itemprop` names of the item when nested.
+    names: Vec<String>,
+    /// The item.
+    item: MicrodataItem,
+}
+
+/// The state shared between the handlers.
+#[derive(Default)]
+struct MetadataState {
+    /// The metadata collected.
+    metadata: PageMetadata,
+    /// The title text.
+    title: String,
+    /// The title element finished.
+    title_done: bool,
+    /// The JSON-LD text of the current block.
+    json_ld: String,
+    /// The open text captures.
+    captures: Vec<Capture>,
+    /// The open microdata items.
+    items: Vec<OpenItem>,
+}
+
+impl MetadataState {
+    /// Add the property to the open item.
+    fn push_microdata(&mut self, names: Vec<String>, value: MicrodataValue) {
+        if let Some(open) = self.items.last_mut() {
+            for name in names {
+                open.item.properties.push(MicrodataProperty {
+                    name,
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+
+    /// Close the item and add it to the parent item or the top level.
+    fn close_item(&mut self) {
+        if let Some(open) = self.items.pop() {
+            if open.names.is_empty() || self.items.is_empty() {
+                self.metadata.microdata.push(open.item);
+            } else {
+                self.push_microdata(open.names, MicrodataValue::Item(open.item));
+            }
+        }
+    }
+
+    /// Close the last text capture.
+    fn close_capture(&mut self) {
+        if let Some(capture) = self.captures.pop() {
+            let text = collapse_whitespace(&crate::utils::decode_html_entities(&capture.text));
+
+            match capture.target {
+                CaptureTarget::Microdata(names) => {
+                    self.push_microdata(names, MicrodataValue::Text(text))
+                }
+                CaptureTarget::Rdfa(property) => self.metadata.rdfa.push(MetaProperty {
+                    property,
+                    content: text,
+                }),
+            }
+        }
+    }
+}
+
+/// Collapse the whitespace of the text.
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for word in text.split_whitespace() {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+
+    out
+}
+
+/// The value of an `itemprop` taken from the attributes of the element.
+fn microdata_attr_value(el: &lol_html::send::Element) -> Option<String> {
+    let attribute = match el.tag_name().as_str() {
+        "meta" => "content",
+        "a" | "area" | "link" => "href",
+        "audio" | "embed" | "iframe" | "img" | "source" | "track" | "video" => "src",
+        "object" => "data",
+        "data" | "meter" => "value",
+        "time" if el.has_attribute("datetime") => "datetime",
+        _ if el.has_attribute("content") => "content",
+        _ => return None,
+    };
+
+    Some(get_attribute(el, attribute).unwrap_or_default())
+}
+
+/// Run the state change on the end tag of the element.
+fn on_end_tag(
+    state: &Arc<Mutex<MetadataState>>,
+    close: fn(&mut MetadataState),
+) -> EndTagHandler<'static> {
+    let state = state.clone();
+
+    Box::new(move |_end| {
+        if let Ok(mut state) = state.lock() {
+            close(&mut state);
+        }
+        Ok(())
+    })
+}
+
+/// Get the attribute value with the html entities decoded.
+fn get_attribute(el: &lol_html::send::Element, name: &str) -> Option<String> {
+    el.get_attribute(name)
+        .map(|value| crate::utils::decode_html_entities(&value).into_owned())
+}
+
+/// Split the space separated attribute value.
+fn split_names(value: &str) -> Vec<String> {
+    value.split_whitespace().map(String::from).collect()
+}
+
+/// Streaming collector of the embedded page metadata. Add the handlers to a `lol_html` rewriter and call [`MetadataCollector::finish`] after the rewriter ends.
+#[derive(Default, Clone)]
+pub struct MetadataCollector {
+    /// The state shared between the handlers.
+    state: Arc<Mutex<MetadataState>>,
+}
+
+impl MetadataCollector {
+    /// A new metadata collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The element handlers collecting the metadata.
+    pub fn element_content_handlers<'h, 's>(
+        &self,
+    ) -> Vec<(Cow<'s, Selector>, ElementContentHandlers<'h>)> {
+        let title_state = self.state.clone();
+        let json_ld_state = self.state.clone();
+        let meta_state = self.state.clone();
+        let link_state = self.state.clone();
+        let microdata_state = self.state.clone();
+        let rdfa_state = self.state.clone();
+
+        vec![
+            lol_html::text!("title", move |chunk| {
+                if let Ok(mut state) = title_state.lock() {
+                    if !state.title_done {
+                        state.title.push_str(chunk.as_str());
+                        if chunk.last_in_text_node() {
+                            state.title_done = true;
+                        }
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::text!(JSON_LD_SELECTOR, move |chunk| {
+                if let Ok(mut state) = json_ld_state.lock() {
+                    state.json_ld.push_str(chunk.as_str());
+
+                    if chunk.last_in_text_node() {
+                        let block = std::mem::take(&mut state.json_ld);
+
+                        if let Ok(value) = serde_json::from_str(block.trim()) {
+                            state.metadata.json_ld.push(value);
+                        }
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!("meta[content]", move |el| {
+                let content = get_attribute(el, "content").unwrap_or_default();
+
+                if let Ok(mut state) = meta_state.lock() {
+                    if let Some(name) = get_attribute(el, "name") {
+                        let name = name.to_ascii_lowercase();
+
+                        if name == "description" {
+                            if state.metadata.description.is_none() {
+                                state.metadata.description = Some(content.clone());
+                            }
+                        } else if name.starts_with("twitter:") {
+                            state.metadata.twitter.push(MetaProperty {
+                                property: name,
+                                content: content.clone(),
+                            });
+                        }
+                    }
+
+                    if let Some(property) = get_attribute(el, "property") {
+                        for property in split_names(&property) {
+                            let meta = MetaProperty {
+                                property,
+                                content: content.clone(),
+                            };
+
+                            if OPEN_GRAPH_PREFIXES
+                                .iter()
+                                .any(|prefix| meta.property.starts_with(prefix))
+                            {
+                                state.metadata.open_graph.push(meta);
+                            } else if meta.property.starts_with("twitter:") {
+                                state.metadata.twitter.push(meta);
+                            } else {
+                                state.metadata.rdfa.push(meta);
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!("link[rel][href]", move |el| {
+                let rel = get_attribute(el, "rel").unwrap_or_default();
+                let href = get_attribute(el, "href").unwrap_or_default();
+
+                if let Ok(mut state) = link_state.lock() {
+                    for rel in rel.split_whitespace() {
+                        if rel.eq_ignore_ascii_case("canonical") {
+                            if state.metadata.canonical.is_none() {
+                                state.metadata.canonical = Some(href.clone());
+                            }
+                        } else if rel.eq_ignore_ascii_case("alternate") {
+                            if let Some(hreflang) = get_attribute(el, "hreflang") {
+                                state.metadata.alternates.push(Alternate {
+                                    hreflang,
+                                    href: href.clone(),
+                                });
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!("[itemscope], [itemprop]", move |el| {
+                let names = el
+                    .get_attribute("itemprop")
+                    .map(|names| split_names(&names))
+                    .unwrap_or_default();
+
+                if el.has_attribute("itemscope") {
+                    if let Ok(mut state) = microdata_state.lock() {
+                        state.items.push(OpenItem {
+                            names,
+                            item: MicrodataItem {
+                                item_type: get_attribute(el, "itemtype"),
+                                item_id: get_attribute(el, "itemid"),
+                                properties: Vec::new(),
+                            },
+                        });
+
+                        match el.end_tag_handlers() {
+                            Some(handlers) => {
+                                handlers
+                                    .push(on_end_tag(&microdata_state, MetadataState::close_item));
+                            }
+                            _ => state.close_item(),
+                        }
+                    }
+                } else if !names.is_empty() {
+                    match microdata_attr_value(el) {
+                        Some(value) => {
+                            if let Ok(mut state) = microdata_state.lock() {
+                                state.push_microdata(names, MicrodataValue::Text(value));
+                            }
+                        }
+                        _ => {
+                            if let Some(handlers) = el.end_tag_handlers() {
+                                if let Ok(mut state) = microdata_state.lock() {
+                                    state.captures.push(Capture {
+                                        target: CaptureTarget::Microdata(names),
+                                        text: String::new(),
+                                    });
+                                }
+                                handlers.push(on_end_tag(
+                                    &microdata_state,
+                                    MetadataState::close_capture,
+                                ));
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }),
+            lol_html::element!("[property]:not(meta)", move |el| {
+                let properties = el
+                    .get_attribute("property")
+                    .map(|p| split_names(&p))
+                    .unwrap_or_default();
+                let value = ["content", "href", "src", "resource"]
+                    .iter()
+                    .find_map(|attr| get_attribute(el, attr));
+
+                match value {
+                    Some(content) => {
+                        if let Ok(mut state) = rdfa_state.lock() {
+                            for property in properties {
+                                state.metadata.rdfa.push(MetaProperty {
+                                    property,
+                                    content: content.clone(),
+                                });
+                            }
+                        }
+                    }
+                    _ => {
+                        if let Some(handlers) = el.end_tag_handlers() {
+                            for property in properties {
+                                if let Ok(mut state) = rdfa_state.lock() {
+                                    state.captures.push(Capture {
+                                        target: CaptureTarget::Rdfa(property),
+                                        text: String::new(),
+                                    });
+                                }
+                                handlers
+                                    .push(on_end_tag(&rdfa_state, MetadataState::close_capture));
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }),
+        ]
+    }
+
+    /// The document handlers gathering the text of the microdata and RDFa properties.
+    pub fn document_content_handlers<'h>(&self) -> Vec<DocumentContentHandlers<'h>> {
+        let state = self.state.clone();
+
+        vec![lol_html::doc_text!(move |chunk| {
+            if let Ok(mut state) = state.lock() {
+                for capture in state.captures.iter_mut() {
+                    capture.text.push_str(chunk.as_str());
+                }
+            }
+            Ok(())
+        })]
+    }
+
+    /// Take the metadata collected.
+    pub fn finish(&self) -> PageMetadata {
+        match self.state.lock() {
+            Ok(mut state) => {
+                while !state.captures.is_empty() {
+                    state.close_capture();
+                }
+                while !state.items.is_empty() {
+                    state.close_item();
+                }
+
+                let title = collapse_whitespace(&crate::utils::decode_html_entities(&state.title));
+
+                if !title.is_empty() {
+                    state.metadata.title = Some(title);
+                }
+
+                std::mem::take(&mut state.metadata)
+            }
+            _ => Default::default(),
+        }
+    }
+}
+
+/// Extract the embedded metadata of the html.
+pub fn extract_metadata(html: &[u8]) -> PageMetadata {
+    let collector = MetadataCollector::new();
+
+    let mut rewriter = lol_html::send::HtmlRewriter::new(
+        lol_html::send::Settings {
+            element_content_handlers: collector.element_content_handlers(),
+            document_content_handlers: collector.document_content_handlers(),
+            adjust_charset_on_meta_tag: true,
+            ..lol_html::send::Settings::new_for_handler_types()
+        },
+        |_c: &[u8]| {},
+    );
+
+    let _ = rewriter.write(html);
+    let _ = rewriter.end();
+
+    collector.finish()
+}
+
+#[test]
+fn test_extract_metadata() {
+    let html = br#"<html lang="en"><head>
+        <title>Spider &amp; Web</title>
+        <meta name="description" content="A fast crawler &amp; scraper.">
+        <meta property="og:title" content="Spider">
+        <meta property="og:image" content="https://example.com/a.png">
+        <meta property="og:image" content="https://example.com/b.png">
+        <meta name="twitter:card" content="summary">
+        <link rel="canonical" href="https://example.com/">
+        <link rel="alternate" hreflang="de" href="https://example.com/de/">
+        <link rel="alternate" hreflang="x-default" href="https://example.com/">
+        <script type="application/ld+json">{"@type": "Organization", "name": "Spider"}</script>
+        <script type="application/ld+json">{ invalid </script>
+        </head><body>
+        <div itemscope itemtype="https://schema.org/Product">
+            <span itemprop="name">Spider   Pro</span>
+            <link itemprop="url" href="https://example.com/pro">
+            <div itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+                <meta itemprop="priceCurrency" content="USD">
+                <span itemprop="price">9.99</span>
+            </div>
+        </div>
+        <p vocab="https://schema.org/" typeof="Person"><span property="name">Jane</span></p>
+        </body></html>"#;
+
+    let metadata = extract_metadata(html);
+
+    assert_eq!(metadata.title.as_deref(), Some("Spider & Web"));
+    assert_eq!(
+        metadata.description.as_deref(),
+        Some("A fast crawler & scraper.")
+    );
+    assert_eq!(metadata.canonical.as_deref(), Some("https://example.com/"));
+    assert_eq!(metadata.open_graph("og:title"), Some("Spider"));
+    assert_eq!(metadata.open_graph.len(), 3);
+    assert_eq!(metadata.twitter("twitter:card"), Some("summary"));
+    assert_eq!(metadata.alternates.len(), 2);
+    assert_eq!(metadata.alternates[0].hreflang, "de");
+    assert_eq!(metadata.json_ld.len(), 1);
+    assert_eq!(metadata.json_ld[0]["name"], "Spider");
+
+    assert_eq!(metadata.microdata.len(), 1);
+    let product = &metadata.microdata[0];
+    assert_eq!(
+        product.item_type.as_deref(),
+        Some("https://schema.org/Product")
+    );
+    assert_eq!(
+        product.get("name"),
+        Some(&MicrodataValue::Text("Spider Pro".into()))
+    );
+    assert_eq!(
+        product.get("url"),
+        Some(&MicrodataValue::Text("https://example.com/pro".into()))
+    );
+    match product.get("offers") {
+        Some(MicrodataValue::Item(offer)) => {
+            assert_eq!(
+                offer.get("price"),
+                Some(&MicrodataValue::Text("9.99".into()))
+            );
+            assert_eq!(
+                offer.get("priceCurrency"),
+                Some(&MicrodataValue::Text("USD".into()))
+            );
+        }
+        _ => panic!("expected a nested offer item"),
+    }
+
+    assert_eq!(
+        metadata.rdfa,
+        vec![MetaProperty {
+            property: "name".into(),
+            content: "Jane".into()
+        }]
+    );
+}
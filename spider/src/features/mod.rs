@@ -6,9 +6,15 @@ pub mod chrome_common;
 #[cfg(feature = "real_browser")]
 /// Mouse movements
 pub mod chrome_mouse_movements;
+/// Chrome browser pool
+#[cfg(feature = "chrome")]
+pub mod chrome_pool;
 #[cfg(feature = "real_browser")]
 /// Viewport
 pub mod chrome_viewport;
+/// Console, exception and dialog logs of the chrome pages
+#[cfg(feature = "chrome")]
+pub mod console_logs;
 /// Decentralized header handling
 #[cfg(feature = "decentralized_headers")]
 pub mod decentralized_headers;
@@ -17,13 +23,36 @@ pub mod disk;
 /// URL globbing
 #[cfg(feature = "glob")]
 pub mod glob;
+/// HAR capture of the chrome network activity
+#[cfg(feature = "chrome")]
+pub mod har;
+/// Scripted chrome login before the crawl
+#[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+pub mod login_flow;
+/// Embedded page metadata
+#[cfg(feature = "metadata")]
+pub mod metadata;
 /// OpenAI
 #[cfg(feature = "openai")]
 pub mod openai;
 /// Common modules for OpenAI
 pub mod openai_common;
+/// The accessibility tree and the visible text of the chrome page
+#[cfg(feature = "chrome")]
+pub mod page_snapshot;
+/// Follow the next pages of a listing
+pub mod pagination;
+/// Capture the chrome responses matching a filter
+#[cfg(feature = "chrome")]
+pub mod response_capture;
 /// Spoof the refereer
 pub mod spoof_referrer;
+/// Playwright style storage state of the cookies and web storage
+#[cfg(feature = "storage_state")]
+pub mod storage_state;
+/// Worker pool for decentralized crawling
+#[cfg(feature = "decentralized")]
+pub mod worker_pool;
 
 #[cfg(all(not(feature = "simd"), feature = "openai"))]
 pub(crate) use serde_json;
@@ -0,0 +1,363 @@
+use chromiumoxide::cdp::browser_protocol::accessibility::{AxNode, AxValue, GetFullAxTreeParams};
+use chromiumoxide::cdp::browser_protocol::dom_snapshot::{
+    CaptureSnapshotParams, CaptureSnapshotReturns,
+};
+use chromiumoxide::Page;
+use hashbrown::HashMap;
+
+/// The computed styles captured to detect the hidden text in order.
+const SNAPSHOT_STYLES: [&str; 2] = ["visibility", "opacity"];
+
+/// A node of the accessibility tree. The nodes chrome ignores are left out and their children moved to the closest node kept.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessibilityNode {
+    /// The id of the node in the tree.
+    pub id: String,
+    /// The role of the node ex: `heading`, `link` or `button`.
+    pub role: Option<String>,
+    /// The accessible name of the node.
+    pub name: Option<String>,
+    /// The accessible description of the node.
+    pub description: Option<String>,
+    /// The value of the node like the text of an input.
+    pub value: Option<String>,
+    /// The states and the attributes of the node ex: `level`, `checked` or `focusable`.
+    pub properties: HashMap<String, String>,
+    /// The id of the parent node.
+    pub parent_id: Option<String>,
+    /// The ids of the child nodes in order.
+    pub child_ids: Vec<String>,
+    /// The backend id of the DOM node.
+    pub backend_node_id: Option<i64>,
+}
+
+/// A line of text rendered on the page with the bounding box in CSS pixels of the document.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextBox {
+    /// The text of the line.
+    pub text: String,
+    /// The left position.
+    pub x: f64,
+    /// The top position.
+    pub y: f64,
+    /// The width of the box.
+    pub width: f64,
+    /// The height of the box.
+    pub height: f64,
+    /// The backend id of the DOM text node.
+    pub backend_node_id: Option<i64>,
+}
+
+/// Get the accessibility tree of the page.
+pub async fn accessibility_tree(page: &Page) -> Option<Vec<AccessibilityNode>> {
+    match page.execute(GetFullAxTreeParams::default()).await {
+        Ok(tree) => Some(accessibility_nodes(&tree.result.nodes)),
+        Err(e) => {
+            log::warn!("failed to get the accessibility tree: {e}");
+            None
+        }
+    }
+}
+
+/// Get the visible text of the page with the bounding boxes in the layout order. The text of the elements not rendered, hidden, transparent or without a size is left out.
+pub async fn visible_text(page: &Page) -> Option<Vec<TextBox>> {
+    match page
+        .execute(CaptureSnapshotParams::new(
+            SNAPSHOT_STYLES.iter().map(|s| s.to_string()).collect(),
+        ))
+        .await
+    {
+        Ok(snapshot) => Some(text_boxes(&snapshot.result)),
+        Err(e) => {
+            log::warn!("failed to capture the dom snapshot: {e}");
+            None
+        }
+    }
+}
+
+/// Join the text boxes into plain text starting a new line when the next box is below the prior one.
+pub fn visible_text_dump(text_boxes: &[TextBox]) -> String {
+    let mut text = String::new();
+    let mut prior: Option<&TextBox> = None;
+
+    for text_box in text_boxes {
+        if let Some(prior) = prior {
+            if text_box.y >= prior.y + prior.height / 2.0 {
+                text.push('\n');
+            } else if !text.ends_with(char::is_whitespace)
+                && !text_box.text.starts_with(char::is_whitespace)
+            {
+                text.push(' ');
+            }
+        }
+        text.push_str(&text_box.text);
+        prior = Some(text_box);
+    }
+
+    text
+}
+
+/// The text of the accessibility value.
+fn ax_value_text(value: &Option<AxValue>) -> Option<String> {
+    let value = value.as_ref()?.value.as_ref()?;
+
+    let text = match value.as_str() {
+        Some(text) => text.to_string(),
+        _ => value.to_string(),
+    };
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Convert the chrome accessibility nodes leaving out the ignored nodes.
+fn accessibility_nodes(nodes: &[AxNode]) -> Vec<AccessibilityNode> {
+    let by_id: HashMap<&str, &AxNode> = nodes
+        .iter()
+        .map(|node| (node.node_id.inner().as_str(), node))
+        .collect();
+
+    // the children kept for the node walking through the ignored nodes.
+    fn kept_children(node: &AxNode, by_id: &HashMap<&str, &AxNode>, out: &mut Vec<String>) {
+        for child_id in node.child_ids.iter().flatten() {
+            if let Some(child) = by_id.get(child_id.inner().as_str()) {
+                if child.ignored {
+                    kept_children(child, by_id, out);
+                } else {
+                    out.push(child_id.inner().clone());
+                }
+            }
+        }
+    }
+
+    let mut tree: Vec<AccessibilityNode> = nodes
+        .iter()
+        .filter(|node| !node.ignored)
+        .map(|node| {
+            let mut child_ids = Vec::new();
+            kept_children(node, &by_id, &mut child_ids);
+
+            AccessibilityNode {
+                id: node.node_id.inner().clone(),
+                role: ax_value_text(&node.role),
+                name: ax_value_text(&node.name),
+                description: ax_value_text(&node.description),
+                value: ax_value_text(&node.value),
+                properties: node
+                    .properties
+                    .iter()
+                    .flatten()
+                    .filter_map(|property| {
+                        ax_value_text(&Some(property.value.clone()))
+                            .map(|value| (property.name.as_ref().to_string(), value))
+                    })
+                    .collect(),
+                parent_id: None,
+                child_ids,
+                backend_node_id: node.backend_dom_node_id.as_ref().map(|id| *id.inner()),
+            }
+        })
+        .collect();
+
+    let parents: HashMap<String, String> = tree
+        .iter()
+        .flat_map(|node| {
+            node.child_ids
+                .iter()
+                .map(|child_id| (child_id.clone(), node.id.clone()))
+        })
+        .collect();
+
+    for node in tree.iter_mut() {
+        node.parent_id = parents.get(&node.id).cloned();
+    }
+
+    tree
+}
+
+/// Get the visible text boxes of the main document of the snapshot.
+fn text_boxes(snapshot: &CaptureSnapshotReturns) -> Vec<TextBox> {
+    let mut boxes = Vec::new();
+
+    let document = match snapshot.documents.first() {
+        Some(document) => document,
+        _ => return boxes,
+    };
+
+    let string = |index: i64| -> &str {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| snapshot.strings.get(index))
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+    };
+
+    let layout = &document.layout;
+    let parents = document.nodes.parent_index.as_deref().unwrap_or_default();
+    let backend_ids = document
+        .nodes
+        .backend_node_id
+        .as_deref()
+        .unwrap_or_default();
+
+    // the layout index of each rendered node.
+    let layout_of: HashMap<i64, usize> = layout
+        .node_index
+        .iter()
+        .enumerate()
+        .map(|(layout_index, node_index)| (*node_index, layout_index))
+        .collect();
+
+    let style = |layout_index: usize, name: &str| -> &str {
+        SNAPSHOT_STYLES
+            .iter()
+            .position(|style| *style == name)
+            .and_then(|position| {
+                layout
+                    .styles
+                    .get(layout_index)
+                    .and_then(|styles| styles.inner().get(position))
+            })
+            .map(|index| string(*index.inner()))
+            .unwrap_or_default()
+    };
+
+    // the node or an ancestor is hidden or fully transparent.
+    let hidden = |node_index: i64| -> bool {
+        let mut current = Some(node_index);
+
+        while let Some(node_index) = current.filter(|index| *index >= 0) {
+            if let Some(layout_index) = layout_of.get(&node_index) {
+                let visibility = style(*layout_index, "visibility");
+
+                if visibility == "hidden" || visibility == "collapse" {
+                    return true;
+                }
+                if style(*layout_index, "opacity") == "0" {
+                    return true;
+                }
+            }
+            current = usize::try_from(node_index)
+                .ok()
+                .and_then(|index| parents.get(index))
+                .copied();
+        }
+
+        false
+    };
+
+    let text_boxes = &document.text_boxes;
+
+    for (index, layout_index) in text_boxes.layout_index.iter().enumerate() {
+        let layout_index = match usize::try_from(*layout_index) {
+            Ok(layout_index) => layout_index,
+            _ => continue,
+        };
+
+        let bounds = match text_boxes.bounds.get(index).map(|b| b.inner()) {
+            Some(bounds) if bounds.len() >= 4 && bounds[2] > 0.0 && bounds[3] > 0.0 => bounds,
+            _ => continue,
+        };
+
+        let node_index = match layout.node_index.get(layout_index) {
+            Some(node_index) => *node_index,
+            _ => continue,
+        };
+
+        if hidden(node_index) {
+            continue;
+        }
+
+        let full_text = match layout.text.get(layout_index) {
+            Some(text) => string(*text.inner()),
+            _ => continue,
+        };
+
+        let start = text_boxes.start.get(index).copied().unwrap_or_default();
+        let length = text_boxes.length.get(index).copied().unwrap_or_default();
+
+        // the offsets are in UTF-16 code units.
+        let text = String::from_utf16_lossy(
+            &full_text
+                .encode_utf16()
+                .skip(usize::try_from(start).unwrap_or_default())
+                .take(usize::try_from(length).unwrap_or_default())
+                .collect::<Vec<u16>>(),
+        );
+
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        boxes.push(TextBox {
+            text,
+            x: bounds[0],
+            y: bounds[1],
+            width: bounds[2],
+            height: bounds[3],
+            backend_node_id: usize::try_from(node_index)
+                .ok()
+                .and_then(|index| backend_ids.get(index))
+                .map(|id| *id.inner()),
+        });
+    }
+
+    boxes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chromiumoxide::cdp::browser_protocol::accessibility::{AxNodeId, AxValueType};
+
+    fn ax_node(id: &str, ignored: bool, role: &str, children: &[&str]) -> AxNode {
+        let mut node = AxNode::new(AxNodeId::new(id), ignored);
+        let mut value = AxValue::new(AxValueType::Role);
+        value.value = Some(role.into());
+        node.role = Some(value);
+        node.child_ids = Some(children.iter().map(|c| AxNodeId::new(*c)).collect());
+        node
+    }
+
+    #[test]
+    fn test_accessibility_nodes() {
+        let nodes = accessibility_nodes(&[
+            ax_node("1", false, "RootWebArea", &["2"]),
+            ax_node("2", true, "generic", &["3", "4"]),
+            ax_node("3", false, "heading", &[]),
+            ax_node("4", false, "link", &[]),
+        ]);
+
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[0].child_ids, vec!["3", "4"]);
+        assert_eq!(nodes[1].role.as_deref(), Some("heading"));
+        assert_eq!(nodes[1].parent_id.as_deref(), Some("1"));
+        assert_eq!(nodes[0].parent_id, None);
+    }
+
+    #[test]
+    fn test_visible_text_dump() {
+        let text_box = |text: &str, x: f64, y: f64| TextBox {
+            text: text.into(),
+            x,
+            y,
+            width: 40.0,
+            height: 16.0,
+            backend_node_id: None,
+        };
+
+        assert_eq!(
+            visible_text_dump(&[
+                text_box("Spider", 0.0, 0.0),
+                text_box("Crawler", 50.0, 2.0),
+                text_box("Fast", 0.0, 20.0),
+            ]),
+            "Spider Crawler\nFast"
+        );
+    }
+}
@@ -0,0 +1,537 @@
+use crate::CaseInsensitiveString;
+use hashbrown::{HashMap, HashSet};
+use lol_html::html_content::{Element, TextChunk};
+use lol_html::{ElementContentHandlers, HtmlRewriter, Selector, Settings};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::sync::Mutex;
+
+/// The placeholder of the page number in the url template.
+const PAGE_PLACEHOLDER: &str = "{page}";
+
+/// How to follow the pages of a listing like a blog index or the search results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Pagination {
+    /// The selector of the link to the next page. The href is enqueued with HTTP and the link is clicked with chrome.
+    pub next_selector: Option<String>,
+    /// The selector of the button loading more items into the page. This only applies with chrome.
+    pub load_more_selector: Option<String>,
+    /// The url of the pages with `{page}` replaced by the page number ex: `https://example.com/blog?page={page}`.
+    pub url_template: Option<String>,
+    /// The page number of the url template to enqueue from the first page of the crawl. Defaults to 2 since the first page is usually the url crawled.
+    pub start_page: u32,
+    /// The selector of the items of the listing. The pagination stops when a page has no items or only items already seen.
+    pub item_selector: Option<String>,
+    /// The max pages to follow from a listing including the first page. Defaults to 10.
+    pub max_pages: u32,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            next_selector: None,
+            load_more_selector: None,
+            url_template: None,
+            start_page: 2,
+            item_selector: None,
+            max_pages: 10,
+        }
+    }
+}
+
+impl Pagination {
+    /// Follow the link to the next page.
+    pub fn next(selector: &str) -> Self {
+        Self {
+            next_selector: Some(selector.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Click the button loading more items. This only applies with chrome.
+    pub fn load_more(selector: &str) -> Self {
+        Self {
+            load_more_selector: Some(selector.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Enqueue the pages of the url template with `{page}` replaced by the page number.
+    pub fn url_template(template: &str) -> Self {
+        Self {
+            url_template: Some(template.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the page number of the url template to enqueue from the first page of the crawl.
+    pub fn with_start_page(mut self, start_page: u32) -> Self {
+        self.start_page = start_page;
+        self
+    }
+
+    /// Stop the pagination when a page has no new items matching the selector.
+    pub fn with_item_selector(mut self, item_selector: &str) -> Self {
+        self.item_selector = Some(item_selector.into());
+        self
+    }
+
+    /// Set the max pages to follow from a listing including the first page.
+    pub fn with_max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+}
+
+/// The next link and the items found on a page of the listing.
+#[derive(Debug, Default)]
+struct Listing {
+    /// The href of the next link.
+    next: Option<String>,
+    /// The hash of the text of each item.
+    items: Vec<u64>,
+}
+
+/// Scan the html for the href of the next link and the items of the listing.
+fn scan(html: &[u8], next_selector: Option<&str>, item_selector: Option<&str>) -> Listing {
+    let listing = Rc::new(RefCell::new((Listing::default(), Vec::<String>::new())));
+    let mut handlers: Vec<(Cow<Selector>, ElementContentHandlers)> = Vec::new();
+
+    if let Some(selector) = next_selector.and_then(|s| s.parse::<Selector>().ok()) {
+        let listing = listing.clone();
+
+        handlers.push((
+            Cow::Owned(selector),
+            ElementContentHandlers::default().element(move |el: &mut Element| {
+                let mut listing = listing.borrow_mut();
+
+                if listing.0.next.is_none() {
+                    listing.0.next = el.get_attribute("href").filter(|href| {
+                        let href = href.trim();
+                        !href.is_empty()
+                            && !href.starts_with('#')
+                            && !href.starts_with("javascript:")
+                    });
+                }
+                Ok(())
+            }),
+        ));
+    }
+
+    if let Some(selector) = item_selector.and_then(|s| s.parse::<Selector>().ok()) {
+        let items = listing.clone();
+        let text = listing.clone();
+
+        handlers.push((
+            Cow::Owned(selector),
+            ElementContentHandlers::default()
+                .element(move |_el: &mut Element| {
+                    items.borrow_mut().1.push(String::new());
+                    Ok(())
+                })
+                .text(move |chunk: &mut TextChunk| {
+                    if let Some(item) = text.borrow_mut().1.last_mut() {
+                        item.push_str(chunk.as_str());
+                    }
+                    Ok(())
+                }),
+        ));
+    }
+
+    if !handlers.is_empty() {
+        let mut rewriter = HtmlRewriter::new(
+            Settings {
+                element_content_handlers: handlers,
+                ..Settings::new()
+            },
+            |_c: &[u8]| {},
+        );
+
+        let _ = rewriter.write(html);
+        let _ = rewriter.end();
+    }
+
+    let (mut page, items) = listing.take();
+
+    page.items = items
+        .iter()
+        .map(|item| {
+            let mut s = ahash::AHasher::default();
+            item.split_whitespace().for_each(|word| word.hash(&mut s));
+            s.finish()
+        })
+        .collect();
+
+    page
+}
+
+/// Build the regex matching the urls of the template capturing the page number.
+fn template_regex(template: &str) -> Option<regex::Regex> {
+    if !template.contains(PAGE_PLACEHOLDER) {
+        log::warn!("the pagination url template is missing {PAGE_PLACEHOLDER}: {template}");
+        return None;
+    }
+
+    regex::Regex::new(&format!(
+        "^{}$",
+        regex::escape(template).replace(&regex::escape(PAGE_PLACEHOLDER), r"(\d+)")
+    ))
+    .ok()
+}
+
+/// The pagination state of a crawl shared by the pages.
+#[derive(Debug)]
+pub struct Paginator {
+    /// The pagination configuration.
+    config: Pagination,
+    /// The regex matching the urls of the template.
+    template: Option<regex::Regex>,
+    /// The position of the enqueued pages in their listing.
+    pages: Mutex<HashMap<String, u32>>,
+    /// The items seen by the listings.
+    items: Mutex<HashSet<u64>>,
+    #[cfg(feature = "chrome")]
+    /// The urls of the pages followed and the states captured with chrome.
+    visited: Mutex<HashSet<String>>,
+    /// The urls of the states captured with chrome not marked visited by the crawl yet.
+    states: Mutex<Vec<CaseInsensitiveString>>,
+}
+
+impl Paginator {
+    /// A new paginator for the crawl.
+    pub fn new(config: Pagination) -> Self {
+        Self {
+            template: config.url_template.as_deref().and_then(template_regex),
+            config,
+            pages: Default::default(),
+            items: Default::default(),
+            #[cfg(feature = "chrome")]
+            visited: Default::default(),
+            states: Default::default(),
+        }
+    }
+
+    /// The pagination configuration.
+    pub fn config(&self) -> &Pagination {
+        &self.config
+    }
+
+    /// Take the urls of the states captured with chrome to mark them visited by the crawl.
+    pub fn drain_states(&self) -> Vec<CaseInsensitiveString> {
+        match self.states.lock() {
+            Ok(mut states) => std::mem::take(&mut *states),
+            _ => Default::default(),
+        }
+    }
+
+    /// The urls of the next pages of a listing fetched with HTTP. The url template starts from the `root` page of the crawl when it does not match the template.
+    pub fn next_links(&self, url: &str, html: &[u8], root: bool) -> HashSet<CaseInsensitiveString> {
+        self.links(url, html, root, true)
+    }
+
+    /// The urls of the url template pages of a listing fetched with chrome. The next link is clicked with [`Paginator::follow`] instead.
+    pub fn template_links(
+        &self,
+        url: &str,
+        html: &[u8],
+        root: bool,
+    ) -> HashSet<CaseInsensitiveString> {
+        if self.template.is_some() {
+            self.links(url, html, root, false)
+        } else {
+            Default::default()
+        }
+    }
+
+    /// The urls of the next pages of a listing.
+    fn links(
+        &self,
+        url: &str,
+        html: &[u8],
+        root: bool,
+        next: bool,
+    ) -> HashSet<CaseInsensitiveString> {
+        let mut links = HashSet::new();
+
+        let position = match self.pages.lock() {
+            Ok(pages) => pages.get(url).copied().unwrap_or(1),
+            _ => return links,
+        };
+
+        if position >= self.config.max_pages {
+            return links;
+        }
+
+        let listing = scan(
+            html,
+            self.config.next_selector.as_deref().filter(|_| next),
+            self.config.item_selector.as_deref(),
+        );
+
+        if self.config.item_selector.is_some() {
+            let mut new_items = false;
+
+            if let Ok(mut seen) = self.items.lock() {
+                for item in listing.items {
+                    new_items = seen.insert(item) || new_items;
+                }
+            }
+
+            if !new_items {
+                return links;
+            }
+        }
+
+        if let Some(href) = listing.next {
+            if let Ok(next) = url::Url::parse(url).and_then(|base| base.join(href.trim())) {
+                links.insert(next.as_str().into());
+            }
+        }
+
+        if let (Some(template), Some(regex)) = (&self.config.url_template, &self.template) {
+            let number = match regex.captures(url) {
+                Some(captures) => captures
+                    .get(1)
+                    .and_then(|n| n.as_str().parse::<u32>().ok())
+                    .map(|n| n.saturating_add(1)),
+                _ if root => Some(self.config.start_page),
+                _ => None,
+            };
+
+            if let Some(number) = number {
+                links.insert(
+                    template
+                        .replace(PAGE_PLACEHOLDER, &number.to_string())
+                        .into(),
+                );
+            }
+        }
+
+        if let Ok(mut pages) = self.pages.lock() {
+            for link in links.iter() {
+                pages
+                    .entry(link.inner().to_string())
+                    .or_insert(position + 1);
+            }
+        }
+
+        links
+    }
+
+    /// Click the load more button or the next link of the chrome page and capture each state as a page. The first page is not included. The pagination stops at the max pages of the listing, when the button is missing, the page has no new items, did not change or moved to a url already visited.
+    #[cfg(feature = "chrome")]
+    pub async fn follow(
+        &self,
+        page: &chromiumoxide::Page,
+        url: &str,
+        html: &[u8],
+    ) -> Vec<crate::page::Page> {
+        use std::time::Duration;
+
+        let mut pages = Vec::new();
+
+        let selector = match self
+            .config
+            .load_more_selector
+            .as_ref()
+            .or(self.config.next_selector.as_ref())
+        {
+            Some(selector) => selector,
+            _ => return pages,
+        };
+
+        let mut position = match self.pages.lock() {
+            Ok(pages) => pages.get(url).copied().unwrap_or(1),
+            _ => return pages,
+        };
+
+        match self.visited.lock() {
+            Ok(mut visited) => {
+                visited.insert(url.to_string());
+            }
+            _ => return pages,
+        }
+
+        let item_selector = self.config.item_selector.as_deref();
+
+        if let Ok(mut seen) = self.items.lock() {
+            seen.extend(scan(html, None, item_selector).items);
+        }
+
+        let mut signature = html_signature(html);
+
+        while position < self.config.max_pages {
+            let clicked = match page.find_element(selector.as_str()).await {
+                Ok(ele) => {
+                    let _ = ele.scroll_into_view().await;
+                    ele.click().await.is_ok()
+                }
+                _ => false,
+            };
+
+            if !clicked {
+                break;
+            }
+
+            let _ = tokio::time::timeout(Duration::from_secs(10), page.wait_for_navigation()).await;
+            let _ = tokio::time::timeout(
+                Duration::from_secs(10),
+                page.evaluate(
+                    crate::features::chrome_common::generate_wait_for_dom_js_code_with_selector(
+                        5000, None,
+                    ),
+                ),
+            )
+            .await;
+
+            let next_content = match page.outer_html_bytes().await {
+                Ok(content) => content,
+                _ => break,
+            };
+
+            if item_selector.is_some() {
+                let mut new_items = false;
+
+                if let Ok(mut seen) = self.items.lock() {
+                    for item in scan(&next_content, None, item_selector).items {
+                        new_items = seen.insert(item) || new_items;
+                    }
+                }
+
+                if !new_items {
+                    break;
+                }
+            } else {
+                let next_signature = html_signature(&next_content);
+
+                if next_signature == signature {
+                    break;
+                }
+
+                signature = next_signature;
+            }
+
+            let current_url = page.url().await.ok().flatten();
+            let state_url = current_url.as_deref().unwrap_or(url);
+
+            position += 1;
+
+            // a load more button keeps the url of the listing.
+            if state_url != url {
+                let inserted = match self.visited.lock() {
+                    Ok(mut visited) => visited.insert(state_url.to_string()),
+                    _ => false,
+                };
+
+                if !inserted {
+                    break;
+                }
+
+                if let Ok(mut states) = self.states.lock() {
+                    states.push(state_url.into());
+                }
+
+                if let Ok(mut pages) = self.pages.lock() {
+                    pages.insert(state_url.to_string(), position);
+                }
+            }
+
+            pages.push(crate::page::build(
+                state_url,
+                crate::utils::PageResponse {
+                    content: Some(Box::new(next_content)),
+                    final_url: current_url.clone(),
+                    ..Default::default()
+                },
+            ));
+        }
+
+        // the next link of the last state continues the listing after it.
+        if let Some(state) = pages.last() {
+            if let Some(href) = scan(
+                state.get_html_bytes_u8(),
+                self.config.next_selector.as_deref(),
+                None,
+            )
+            .next
+            {
+                if let Ok(next) =
+                    url::Url::parse(state.get_url()).and_then(|base| base.join(href.trim()))
+                {
+                    if let Ok(mut pages) = self.pages.lock() {
+                        pages
+                            .entry(next.as_str().to_string())
+                            .or_insert(position + 1);
+                    }
+                }
+            }
+        }
+
+        pages
+    }
+}
+
+/// The hash of the html to detect a page that did not change.
+#[cfg(feature = "chrome")]
+fn html_signature(html: &[u8]) -> u64 {
+    let mut s = ahash::AHasher::default();
+    html.hash(&mut s);
+    s.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagination_next_links() {
+        let html = br#"<ul><li class="item">A</li><li class="item">B</li></ul>
+            <a class="next" href="/blog?page=3">Next</a>"#;
+
+        let paginator = Paginator::new(
+            Pagination::next("a.next")
+                .with_item_selector("li.item")
+                .with_max_pages(3),
+        );
+        let links = paginator.next_links("https://example.com/blog?page=2", html, false);
+        assert!(links.contains(&CaseInsensitiveString::from(
+            "https://example.com/blog?page=3"
+        )));
+
+        // the same items stop the listing.
+        let links = paginator.next_links("https://example.com/blog?page=3", html, false);
+        assert!(links.is_empty());
+
+        let paginator = Paginator::new(Pagination::url_template(
+            "https://example.com/blog?page={page}",
+        ));
+        let links = paginator.next_links("https://example.com/blog", b"", true);
+        assert!(links.contains(&CaseInsensitiveString::from(
+            "https://example.com/blog?page=2"
+        )));
+        let links = paginator.next_links("https://example.com/blog?page=2", b"", false);
+        assert!(links.contains(&CaseInsensitiveString::from(
+            "https://example.com/blog?page=3"
+        )));
+        assert!(paginator
+            .next_links("https://example.com/about", b"", false)
+            .is_empty());
+
+        let paginator = Paginator::new(
+            Pagination::url_template("https://example.com/blog?page={page}").with_max_pages(2),
+        );
+        assert_eq!(
+            paginator
+                .next_links("https://example.com/blog", b"", true)
+                .len(),
+            1
+        );
+        assert!(paginator
+            .next_links("https://example.com/blog?page=2", b"", false)
+            .is_empty());
+    }
+}
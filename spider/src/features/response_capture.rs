@@ -0,0 +1,330 @@
+use chromiumoxide::cdp::browser_protocol::network::{
+    EventLoadingFinished, EventRequestWillBeSent, EventResponseReceived, GetResponseBodyParams,
+};
+use chromiumoxide::Page;
+use hashbrown::HashMap;
+use regex::RegexSet;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+
+/// The responses to capture while rendering with chrome. The patterns of a list match when any pattern matches and an empty list matches everything.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResponseCaptureConfig {
+    /// The url glob patterns. `*` matches any characters and `?` one character, example `*/api/*`.
+    pub url_globs: Vec<String>,
+    /// The url regex patterns.
+    pub url_regex: Vec<String>,
+    /// The chrome resource types like `XHR` and `Fetch`. The names are case insensitive.
+    pub resource_types: Vec<String>,
+    /// The mime type glob patterns, example `application/*json`.
+    pub mime_types: Vec<String>,
+    /// The max size of a body in bytes. Larger responses are skipped.
+    pub max_body_size: Option<usize>,
+}
+
+impl ResponseCaptureConfig {
+    /// Capture the JSON responses of the XHR and fetch requests.
+    pub fn json() -> Self {
+        Self {
+            resource_types: vec!["XHR".into(), "Fetch".into()],
+            mime_types: vec!["application/json".into(), "application/*+json".into()],
+            ..Default::default()
+        }
+    }
+
+    /// Capture the urls matching the glob patterns.
+    pub fn with_url_globs(mut self, url_globs: &[&str]) -> Self {
+        self.url_globs = url_globs.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Capture the urls matching the regex patterns.
+    pub fn with_url_regex(mut self, url_regex: &[&str]) -> Self {
+        self.url_regex = url_regex.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Capture the chrome resource types like `XHR`, `Fetch`, `Script` or `Document`.
+    pub fn with_resource_types(mut self, resource_types: &[&str]) -> Self {
+        self.resource_types = resource_types.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Capture the mime types matching the glob patterns.
+    pub fn with_mime_types(mut self, mime_types: &[&str]) -> Self {
+        self.mime_types = mime_types.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Skip the bodies larger than the amount of bytes.
+    pub fn with_max_body_size(mut self, max_body_size: Option<usize>) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+}
+
+/// A response captured while rendering the page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CapturedResponse {
+    /// The url of the request.
+    pub url: String,
+    /// The request method.
+    pub method: String,
+    /// The response status.
+    pub status: u16,
+    /// The response headers.
+    pub headers: HashMap<String, String>,
+    /// The mime type of the response.
+    pub mime_type: String,
+    /// The chrome resource type of the request.
+    pub resource_type: String,
+    /// The response body. Binary bodies are base64 encoded.
+    pub body: String,
+    /// The body is base64 encoded.
+    pub base64_encoded: bool,
+}
+
+impl CapturedResponse {
+    /// The decoded body bytes.
+    pub fn body_bytes(&self) -> Vec<u8> {
+        if self.base64_encoded {
+            chromiumoxide::utils::base64::decode(&self.body).unwrap_or_default()
+        } else {
+            self.body.as_bytes().to_vec()
+        }
+    }
+}
+
+/// The compiled capture patterns.
+#[derive(Debug)]
+pub struct ResponseFilter {
+    /// The url patterns.
+    urls: Option<RegexSet>,
+    /// The mime type patterns.
+    mime_types: Option<RegexSet>,
+    /// The lowercase resource types.
+    resource_types: Vec<String>,
+}
+
+impl ResponseFilter {
+    /// Compile the patterns of the configuration. Returns the error of an invalid regex.
+    pub fn new(config: &ResponseCaptureConfig) -> Result<Self, regex::Error> {
+        let urls: Vec<String> = config
+            .url_globs
+            .iter()
+            .map(|glob| glob_to_regex(glob))
+            .chain(config.url_regex.iter().cloned())
+            .collect();
+
+        Ok(Self {
+            urls: if urls.is_empty() {
+                None
+            } else {
+                Some(RegexSet::new(urls)?)
+            },
+            mime_types: if config.mime_types.is_empty() {
+                None
+            } else {
+                Some(RegexSet::new(
+                    config.mime_types.iter().map(|glob| glob_to_regex(glob)),
+                )?)
+            },
+            resource_types: config
+                .resource_types
+                .iter()
+                .map(|t| t.to_ascii_lowercase())
+                .collect(),
+        })
+    }
+
+    /// The response matches the filter.
+    pub fn matches(&self, url: &str, resource_type: &str, mime_type: &str) -> bool {
+        (self.resource_types.is_empty()
+            || self
+                .resource_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(resource_type)))
+            && self
+                .mime_types
+                .as_ref()
+                .is_none_or(|m| m.is_match(mime_type))
+            && self.urls.as_ref().is_none_or(|u| u.is_match(url))
+    }
+}
+
+/// Convert the glob pattern into an anchored regex.
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::with_capacity(glob.len() + 8);
+
+    pattern.push_str("(?i)^");
+
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+
+    pattern.push('$');
+    pattern
+}
+
+/// Record the responses matching the filter with the bodies from `Network.getResponseBody`.
+pub struct ResponseRecorder {
+    /// Stop the recording.
+    stop: oneshot::Sender<()>,
+    /// The recording task.
+    handle: JoinHandle<Vec<CapturedResponse>>,
+}
+
+impl ResponseRecorder {
+    /// Start recording the responses of the page. Returns `None` when the patterns do not compile or the events can not be listened to.
+    pub async fn new(page: &Page, config: &ResponseCaptureConfig) -> Option<Self> {
+        let filter = match ResponseFilter::new(config) {
+            Ok(filter) => filter,
+            Err(e) => {
+                log::error!("invalid response capture pattern {e}");
+                return None;
+            }
+        };
+        let max_body_size = config.max_body_size;
+
+        let (sent, received, finished) = tokio::join!(
+            page.event_listener::<EventRequestWillBeSent>(),
+            page.event_listener::<EventResponseReceived>(),
+            page.event_listener::<EventLoadingFinished>(),
+        );
+
+        let (mut sent, mut received, mut finished) = (sent.ok()?, received.ok()?, finished.ok()?);
+        let (stop, mut stop_rx) = oneshot::channel::<()>();
+        let page = page.clone();
+
+        let handle = crate::utils::spawn_task("response_capture", async move {
+            let mut methods: HashMap<String, String> = HashMap::new();
+            let mut matched: HashMap<String, CapturedResponse> = HashMap::new();
+            let mut captured = Vec::new();
+
+            loop {
+                tokio::select! {
+                    biased;
+                    Some(event) = sent.next() => {
+                        methods.insert(event.request_id.inner().clone(), event.request.method.clone());
+                    }
+                    Some(event) = received.next() => {
+                        let resource_type = event.r#type.as_ref();
+                        let response = &event.response;
+
+                        if filter.matches(&response.url, resource_type, &response.mime_type) {
+                            let id = event.request_id.inner();
+
+                            matched.insert(
+                                id.clone(),
+                                CapturedResponse {
+                                    url: response.url.clone(),
+                                    method: methods.get(id).cloned().unwrap_or_else(|| "GET".into()),
+                                    status: u16::try_from(response.status).unwrap_or_default(),
+                                    headers: response
+                                        .headers
+                                        .inner()
+                                        .as_object()
+                                        .map(|headers| {
+                                            headers
+                                                .iter()
+                                                .map(|(k, v)| {
+                                                    (k.clone(), v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))
+                                                })
+                                                .collect()
+                                        })
+                                        .unwrap_or_default(),
+                                    mime_type: response.mime_type.clone(),
+                                    resource_type: resource_type.into(),
+                                    ..Default::default()
+                                },
+                            );
+                        }
+                    }
+                    Some(event) = finished.next() => {
+                        let id = event.request_id.inner();
+
+                        methods.remove(id);
+
+                        if let Some(mut response) = matched.remove(id) {
+                            if max_body_size.is_some_and(|max| event.encoded_data_length as usize > max) {
+                                continue;
+                            }
+
+                            if let Ok(body) = page
+                                .execute(GetResponseBodyParams::new(event.request_id.clone()))
+                                .await
+                            {
+                                let size = if body.base64_encoded {
+                                    body.body.len() / 4 * 3
+                                } else {
+                                    body.body.len()
+                                };
+
+                                if max_body_size.is_none_or(|max| size <= max) {
+                                    response.base64_encoded = body.base64_encoded;
+                                    response.body.clone_from(&body.body);
+                                    captured.push(response);
+                                }
+                            }
+                        }
+                    }
+                    _ = &mut stop_rx => break,
+                    else => break,
+                }
+            }
+
+            captured
+        });
+
+        Some(Self { stop, handle })
+    }
+
+    /// Stop the recording and return the responses in the order they finished loading.
+    pub async fn finish(self) -> Vec<CapturedResponse> {
+        let _ = self.stop.send(());
+        self.handle.await.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_filter() {
+        let filter = ResponseFilter::new(
+            &ResponseCaptureConfig::json()
+                .with_url_globs(&["*/api/*"])
+                .with_url_regex(&[r"graphql\?"]),
+        )
+        .expect("valid patterns");
+
+        assert!(filter.matches("https://spider.cloud/api/pages", "XHR", "application/json"));
+        assert!(filter.matches(
+            "https://spider.cloud/graphql?query=1",
+            "Fetch",
+            "application/ld+json"
+        ));
+        assert!(!filter.matches(
+            "https://spider.cloud/api/pages",
+            "Script",
+            "application/json"
+        ));
+        assert!(!filter.matches("https://spider.cloud/api/pages", "XHR", "text/html"));
+        assert!(!filter.matches("https://spider.cloud/pages", "XHR", "application/json"));
+
+        let filter = ResponseFilter::new(&ResponseCaptureConfig::default()).expect("valid");
+
+        assert!(filter.matches("https://spider.cloud", "Document", "text/html"));
+        assert!(
+            ResponseFilter::new(&ResponseCaptureConfig::default().with_url_regex(&["("])).is_err()
+        );
+    }
+}
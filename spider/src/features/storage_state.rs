@@ -0,0 +1,644 @@
+use crate::client::cookie::{CookieStore, Jar};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// A cookie of the storage state.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageCookie {
+    /// The cookie name.
+    pub name: String,
+    /// The cookie value.
+    pub value: String,
+    /// The cookie domain. A leading dot includes the subdomains, otherwise the cookie is sent to the host only.
+    pub domain: String,
+    /// The cookie path.
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// The expiration in seconds since the epoch. `-1` is a session cookie.
+    #[serde(default = "default_expires")]
+    pub expires: f64,
+    /// The cookie is hidden from scripts.
+    #[serde(default)]
+    pub http_only: bool,
+    /// The cookie is only sent over https.
+    #[serde(default)]
+    pub secure: bool,
+    /// The same site policy `Strict`, `Lax` or `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub same_site: Option<String>,
+}
+
+/// The default cookie path.
+fn default_path() -> String {
+    "/".into()
+}
+
+/// The expiration of a session cookie.
+fn default_expires() -> f64 {
+    -1.0
+}
+
+impl StorageCookie {
+    /// The cookie expired.
+    pub fn is_expired(&self, now: f64) -> bool {
+        self.expires > 0.0 && self.expires <= now
+    }
+
+    /// The cookie is sent to the host. A leading dot domain also matches the subdomains of the host.
+    pub fn domain_matches(&self, host: &str) -> bool {
+        let domain = self.domain.trim_start_matches('.');
+
+        host.eq_ignore_ascii_case(domain)
+            || self.domain.starts_with('.')
+                && host.len() > domain.len()
+                && host.is_char_boundary(host.len() - domain.len())
+                && host[host.len() - domain.len()..].eq_ignore_ascii_case(domain)
+                && host[..host.len() - domain.len()].ends_with('.')
+    }
+
+    /// The cookie as a `Set-Cookie` header with the url it is set from.
+    fn set_cookie(&self, now: f64) -> Option<(String, Url)> {
+        let host = self.domain.trim_start_matches('.');
+        let url = Url::parse(&format!(
+            "{}://{}{}",
+            if self.secure { "https" } else { "http" },
+            host,
+            self.path
+        ))
+        .ok()?;
+
+        let mut cookie = format!("{}={}; Path={}", self.name, self.value, self.path);
+
+        if self.domain.starts_with('.') {
+            cookie.push_str("; Domain=");
+            cookie.push_str(host);
+        }
+        if self.expires > 0.0 {
+            cookie.push_str(&format!(
+                "; Max-Age={}",
+                (self.expires - now).max(0.0) as u64
+            ));
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        if self.http_only {
+            cookie.push_str("; HttpOnly");
+        }
+        if let Some(ref same_site) = self.same_site {
+            cookie.push_str("; SameSite=");
+            cookie.push_str(same_site);
+        }
+
+        Some((cookie, url))
+    }
+}
+
+/// A storage item.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StorageItem {
+    /// The item key.
+    pub name: String,
+    /// The item value.
+    pub value: String,
+}
+
+/// The web storage of an origin.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginStorage {
+    /// The origin like `https://example.com`.
+    pub origin: String,
+    /// The `localStorage` items.
+    #[serde(default)]
+    pub local_storage: Vec<StorageItem>,
+    /// The `sessionStorage` items. This is not part of the Playwright format and is skipped when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub session_storage: Vec<StorageItem>,
+}
+
+impl OriginStorage {
+    /// The local or session storage items.
+    fn items_mut(&mut self, local: bool) -> &mut Vec<StorageItem> {
+        if local {
+            &mut self.local_storage
+        } else {
+            &mut self.session_storage
+        }
+    }
+}
+
+/// The cookies and web storage of a browser session in the Playwright storage state format.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StorageState {
+    /// The cookies.
+    #[serde(default)]
+    pub cookies: Vec<StorageCookie>,
+    /// The web storage per origin.
+    #[serde(default)]
+    pub origins: Vec<OriginStorage>,
+}
+
+impl StorageState {
+    /// Parse the storage state JSON. Expired cookies are removed.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mut state: Self = serde_json::from_str(json)?;
+        state.remove_expired();
+        Ok(state)
+    }
+
+    /// The storage state as JSON. Expired cookies are skipped.
+    pub fn to_json(&self) -> String {
+        let now = unix_now();
+
+        if self.cookies.iter().any(|c| c.is_expired(now)) {
+            let mut state = self.clone();
+            state.remove_expired();
+            serde_json::to_string_pretty(&state).unwrap_or_default()
+        } else {
+            serde_json::to_string_pretty(self).unwrap_or_default()
+        }
+    }
+
+    /// Remove the expired cookies.
+    pub fn remove_expired(&mut self) {
+        let now = unix_now();
+        self.cookies.retain(|c| !c.is_expired(now));
+    }
+
+    /// Read the storage state file.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?).map_err(std::io::Error::from)
+    }
+
+    /// Write the storage state file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// Insert or replace the cookie with the same name, domain and path. Expired cookies are removed.
+    pub fn set_cookie(&mut self, cookie: StorageCookie) {
+        self.cookies.retain(|c| {
+            !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path)
+        });
+
+        if !cookie.is_expired(unix_now()) {
+            self.cookies.push(cookie);
+        }
+    }
+
+    /// Set the local or session storage item of the origin.
+    pub fn set_item(&mut self, origin: &str, local: bool, name: &str, value: &str) {
+        let items = self.origin_mut(origin).items_mut(local);
+
+        match items.iter_mut().find(|item| item.name == name) {
+            Some(item) => item.value = value.into(),
+            _ => items.push(StorageItem {
+                name: name.into(),
+                value: value.into(),
+            }),
+        }
+    }
+
+    /// Remove the local or session storage item of the origin.
+    pub fn remove_item(&mut self, origin: &str, local: bool, name: &str) {
+        if let Some(o) = self.origins.iter_mut().find(|o| o.origin == origin) {
+            o.items_mut(local).retain(|item| item.name != name);
+        }
+    }
+
+    /// Clear the local or session storage of the origin.
+    pub fn clear_items(&mut self, origin: &str, local: bool) {
+        if let Some(o) = self.origins.iter_mut().find(|o| o.origin == origin) {
+            o.items_mut(local).clear();
+        }
+    }
+
+    /// The storage of the origin, inserted when missing.
+    fn origin_mut(&mut self, origin: &str) -> &mut OriginStorage {
+        match self.origins.iter().position(|o| o.origin == origin) {
+            Some(i) => &mut self.origins[i],
+            _ => {
+                self.origins.push(OriginStorage {
+                    origin: origin.into(),
+                    ..Default::default()
+                });
+                let last = self.origins.len() - 1;
+                &mut self.origins[last]
+            }
+        }
+    }
+
+    /// Add the cookies that did not expire to the HTTP cookie jar.
+    pub fn add_to_jar(&self, jar: &Jar) {
+        let now = unix_now();
+
+        for cookie in self.cookies.iter().filter(|c| !c.is_expired(now)) {
+            if let Some((set_cookie, url)) = cookie.set_cookie(now) {
+                jar.add_cookie_str(&set_cookie, &url);
+            }
+        }
+    }
+
+    /// Add the cookies of the HTTP cookie jar sent to the url and update the values of the cookies known. The jar only exposes the names and values so the new cookies are scoped to the host of the url.
+    pub fn merge_jar(&mut self, jar: &Jar, url: &Url) {
+        let host = match url.host_str() {
+            Some(host) => host,
+            _ => return,
+        };
+
+        if let Some(header) = jar.cookies(url) {
+            for pair in header.to_str().unwrap_or_default().split(';') {
+                if let Some((name, value)) = pair.trim().split_once('=') {
+                    let known = self
+                        .cookies
+                        .iter_mut()
+                        .find(|c| c.name == name && c.domain_matches(host));
+
+                    // the server can rotate the value of a known cookie like a session id.
+                    if let Some(cookie) = known {
+                        cookie.value = value.into();
+                    } else {
+                        self.cookies.push(StorageCookie {
+                            name: name.into(),
+                            value: value.into(),
+                            domain: host.into(),
+                            path: default_path(),
+                            expires: default_expires(),
+                            http_only: false,
+                            secure: url.scheme() == "https",
+                            same_site: None,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The storage state shared by the HTTP client and the chrome pages of a crawl. The state starts from the imported file and follows the cookies and web storage set while crawling.
+#[derive(Debug, Default, Clone)]
+pub struct StorageStateHandle {
+    /// The current state.
+    state: Arc<Mutex<StorageState>>,
+    /// The HTTP cookie jar sharing the cookies.
+    jar: Arc<Mutex<Option<Arc<Jar>>>>,
+    /// The file the state is saved to at the end of the crawl.
+    path: Option<PathBuf>,
+}
+
+impl StorageStateHandle {
+    /// A new handle starting from the state.
+    pub fn new(state: StorageState) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            ..Default::default()
+        }
+    }
+
+    /// A handle loading the file when it exists and saving the state to it at the end of the crawl.
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Self {
+        let path = path.into();
+        let state = if path.exists() {
+            match StorageState::load(&path) {
+                Ok(state) => state,
+                Err(e) => {
+                    log::error!("failed to load the storage state {}: {e}", path.display());
+                    Default::default()
+                }
+            }
+        } else {
+            Default::default()
+        };
+
+        Self {
+            path: Some(path),
+            ..Self::new(state)
+        }
+    }
+
+    /// The file the state is saved to.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Update the state.
+    pub fn update<F: FnOnce(&mut StorageState)>(&self, f: F) {
+        if let Ok(mut state) = self.state.lock() {
+            f(&mut state)
+        }
+    }
+
+    /// Share the state with the HTTP cookie jar. The cookies of the state are added to the jar.
+    pub fn set_jar(&self, jar: Arc<Jar>) {
+        if let Ok(state) = self.state.lock() {
+            state.add_to_jar(&jar);
+        }
+        if let Ok(mut shared) = self.jar.lock() {
+            shared.replace(jar);
+        }
+    }
+
+    /// A snapshot of the state with the HTTP cookies sent to the url.
+    pub fn snapshot(&self, url: Option<&Url>) -> StorageState {
+        let mut state = self
+            .state
+            .lock()
+            .map(|state| state.clone())
+            .unwrap_or_default();
+
+        if let (Some(url), Ok(jar)) = (url, self.jar.lock()) {
+            if let Some(jar) = jar.as_ref() {
+                state.merge_jar(jar, url);
+            }
+        }
+
+        state
+    }
+
+    /// Save the snapshot to the file of the handle.
+    pub fn save(&self, url: Option<&Url>) -> std::io::Result<()> {
+        match self.path {
+            Some(ref path) => self.snapshot(url).save(path),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl PartialEq for StorageStateHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state) || self.snapshot(None) == other.snapshot(None)
+    }
+}
+
+impl serde::Serialize for StorageStateHandle {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.snapshot(None).serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for StorageStateHandle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StorageState::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// The seconds since the epoch.
+fn unix_now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "chrome")]
+mod chrome {
+    use super::{StorageCookie, StorageStateHandle};
+    use chromiumoxide::cdp::browser_protocol::dom_storage::{
+        EnableParams, EventDomStorageItemAdded, EventDomStorageItemRemoved,
+        EventDomStorageItemUpdated, EventDomStorageItemsCleared, StorageId,
+    };
+    use chromiumoxide::cdp::browser_protocol::network::{
+        Cookie, CookieParam, CookieSameSite, TimeSinceEpoch,
+    };
+    use chromiumoxide::cdp::browser_protocol::page::EventLoadEventFired;
+    use chromiumoxide::Page;
+    use tokio_stream::StreamExt;
+
+    impl From<&Cookie> for StorageCookie {
+        fn from(cookie: &Cookie) -> Self {
+            Self {
+                name: cookie.name.clone(),
+                value: cookie.value.clone(),
+                domain: cookie.domain.clone(),
+                path: cookie.path.clone(),
+                expires: if cookie.session { -1.0 } else { cookie.expires },
+                http_only: cookie.http_only,
+                secure: cookie.secure,
+                same_site: cookie.same_site.as_ref().map(|s| s.as_ref().into()),
+            }
+        }
+    }
+
+    impl From<&StorageCookie> for CookieParam {
+        fn from(cookie: &StorageCookie) -> Self {
+            let mut param = CookieParam::new(cookie.name.clone(), cookie.value.clone());
+
+            param.domain = Some(cookie.domain.clone());
+            param.path = Some(cookie.path.clone());
+            param.secure = Some(cookie.secure);
+            param.http_only = Some(cookie.http_only);
+            param.same_site = cookie
+                .same_site
+                .as_deref()
+                .and_then(|s| s.parse::<CookieSameSite>().ok());
+            if cookie.expires > 0.0 {
+                param.expires = Some(TimeSinceEpoch::new(cookie.expires));
+            }
+
+            param
+        }
+    }
+
+    /// The origin of the storage.
+    fn storage_origin(id: &StorageId) -> Option<String> {
+        id.security_origin
+            .clone()
+            .or_else(|| id.storage_key.as_ref().map(|key| key.inner().clone()))
+            .map(|origin| origin.trim_end_matches('/').to_string())
+            .filter(|origin| !origin.is_empty() && origin != "null")
+    }
+
+    impl StorageStateHandle {
+        /// The cookies to set on the browser context.
+        pub fn cookie_params(&self) -> Vec<CookieParam> {
+            self.snapshot(None).cookies.iter().map(Into::into).collect()
+        }
+
+        /// The script seeding the web storage of the origin on each new document. The items already set on the page are kept.
+        pub fn init_script(&self) -> Option<String> {
+            let state = self.snapshot(None);
+            let origins: serde_json::Map<String, serde_json::Value> = state
+                .origins
+                .iter()
+                .filter(|o| !o.local_storage.is_empty() || !o.session_storage.is_empty())
+                .map(|o| {
+                    let items = |items: &[super::StorageItem]| {
+                        items
+                            .iter()
+                            .map(|item| serde_json::json!([item.name, item.value]))
+                            .collect::<Vec<_>>()
+                    };
+                    (
+                        o.origin.clone(),
+                        serde_json::json!([items(&o.local_storage), items(&o.session_storage)]),
+                    )
+                })
+                .collect();
+
+            if origins.is_empty() {
+                None
+            } else {
+                Some(format!(
+                    r#"(()=>{{const s=({})[location.origin];if(!s)return;const a=(t,e)=>{{try{{for(const[k,v]of e)if(t.getItem(k)===null)t.setItem(k,v)}}catch(e){{}}}};a(localStorage,s[0]);a(sessionStorage,s[1])}})();"#,
+                    serde_json::Value::Object(origins)
+                ))
+            }
+        }
+
+        /// Follow the web storage changes and the cookies after each load of the page until it closes.
+        pub async fn track_page(&self, page: &Page) {
+            let _ = page.execute(EnableParams::default()).await;
+
+            let (added, updated, removed, cleared, loaded) = tokio::join!(
+                page.event_listener::<EventDomStorageItemAdded>(),
+                page.event_listener::<EventDomStorageItemUpdated>(),
+                page.event_listener::<EventDomStorageItemRemoved>(),
+                page.event_listener::<EventDomStorageItemsCleared>(),
+                page.event_listener::<EventLoadEventFired>(),
+            );
+
+            let (mut added, mut updated, mut removed, mut cleared, mut loaded) =
+                match (added, updated, removed, cleared, loaded) {
+                    (Ok(a), Ok(u), Ok(r), Ok(c), Ok(l)) => (a, u, r, c, l),
+                    _ => return,
+                };
+
+            let handle = self.clone();
+            let page = page.clone();
+
+            crate::utils::spawn_task("storage_state", async move {
+                loop {
+                    tokio::select! {
+                        Some(event) = added.next() => {
+                            if let Some(origin) = storage_origin(&event.storage_id) {
+                                handle.update(|s| s.set_item(&origin, event.storage_id.is_local_storage, &event.key, &event.new_value));
+                            }
+                        }
+                        Some(event) = updated.next() => {
+                            if let Some(origin) = storage_origin(&event.storage_id) {
+                                handle.update(|s| s.set_item(&origin, event.storage_id.is_local_storage, &event.key, &event.new_value));
+                            }
+                        }
+                        Some(event) = removed.next() => {
+                            if let Some(origin) = storage_origin(&event.storage_id) {
+                                handle.update(|s| s.remove_item(&origin, event.storage_id.is_local_storage, &event.key));
+                            }
+                        }
+                        Some(event) = cleared.next() => {
+                            if let Some(origin) = storage_origin(&event.storage_id) {
+                                handle.update(|s| s.clear_items(&origin, event.storage_id.is_local_storage));
+                            }
+                        }
+                        Some(_) = loaded.next() => {
+                            if let Ok(cookies) = page.get_cookies().await {
+                                handle.update(|s| {
+                                    for cookie in cookies.iter() {
+                                        s.set_cookie(cookie.into());
+                                    }
+                                });
+                            }
+                        }
+                        else => break,
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_state() {
+        let mut state = StorageState::from_json(
+            r#"{
+                "cookies": [
+                    {"name": "sid", "value": "abc", "domain": ".spider.cloud", "path": "/", "expires": -1, "httpOnly": true, "secure": true, "sameSite": "Lax"},
+                    {"name": "old", "value": "1", "domain": "spider.cloud", "path": "/", "expires": 1, "httpOnly": false, "secure": false}
+                ],
+                "origins": [
+                    {"origin": "https://spider.cloud", "localStorage": [{"name": "token", "value": "t1"}]}
+                ]
+            }"#,
+        )
+        .expect("valid storage state");
+
+        assert_eq!(state.cookies.len(), 1, "the expired cookie is removed");
+
+        state.set_item("https://spider.cloud", true, "token", "t2");
+        state.set_item("https://spider.cloud", false, "tab", "1");
+        state.set_item("https://docs.spider.cloud", true, "theme", "dark");
+        state.remove_item("https://docs.spider.cloud", true, "theme");
+
+        assert_eq!(state.origins[0].local_storage[0].value, "t2");
+        assert_eq!(state.origins[0].session_storage.len(), 1);
+        assert!(state.origins[1].local_storage.is_empty());
+
+        let jar = Jar::default();
+        let url = Url::parse("https://www.spider.cloud/pricing").expect("valid url");
+
+        state.add_to_jar(&jar);
+
+        let header = jar.cookies(&url).expect("cookies");
+        assert_eq!(header.to_str().unwrap_or_default(), "sid=abc");
+
+        state.set_cookie(StorageCookie {
+            name: "sid".into(),
+            value: "def".into(),
+            domain: ".spider.cloud".into(),
+            path: "/".into(),
+            expires: -1.0,
+            http_only: true,
+            secure: true,
+            same_site: None,
+        });
+        assert_eq!(state.cookies.len(), 1);
+        assert_eq!(state.cookies[0].value, "def");
+
+        let json = state.to_json();
+        assert!(json.contains(r#""localStorage""#));
+        assert!(json.contains(r#""httpOnly": true"#));
+        assert_eq!(StorageState::from_json(&json).ok(), Some(state));
+    }
+
+    #[test]
+    fn test_storage_state_merge_jar() {
+        let mut state = StorageState::from_json(
+            r#"{"cookies": [{"name": "sid", "value": "abc", "domain": ".spider.cloud", "path": "/", "expires": -1, "httpOnly": true, "secure": true}]}"#,
+        )
+        .expect("valid storage state");
+
+        let jar = Jar::default();
+        let url = Url::parse("https://www.spider.cloud/pricing").expect("valid url");
+
+        state.add_to_jar(&jar);
+        state.merge_jar(&jar, &url);
+        assert_eq!(state.cookies.len(), 1, "the round trip keeps the cookies");
+        assert_eq!(state.cookies[0].value, "abc");
+
+        jar.add_cookie_str("sid=rotated; Domain=spider.cloud; Path=/", &url);
+        jar.add_cookie_str("theme=dark; Path=/", &url);
+        state.merge_jar(&jar, &url);
+
+        assert_eq!(state.cookies.len(), 2);
+        assert_eq!(state.cookies[0].value, "rotated");
+        assert_eq!(state.cookies[0].domain, ".spider.cloud");
+        assert_eq!(state.cookies[1].name, "theme");
+        assert_eq!(state.cookies[1].domain, "www.spider.cloud");
+        assert!(state.cookies[1].secure);
+
+        let url = Url::parse("https://spider.cloud").expect("valid url");
+
+        jar.add_cookie_str("sid=apex; Domain=spider.cloud; Path=/", &url);
+        state.merge_jar(&jar, &url);
+
+        assert_eq!(state.cookies.len(), 2, "the apex host matches the domain");
+        assert_eq!(state.cookies[0].value, "apex");
+        assert!(!state.cookies[0].domain_matches("notspider.cloud"));
+    }
+}
@@ -0,0 +1,581 @@
+use crate::Client;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// The consecutive failures before a worker is ejected from the rotation.
+const MAX_FAILURES: usize = 3;
+/// The time an ejected worker waits before it is tried again.
+const EJECTION_COOLDOWN: Duration = Duration::from_secs(30);
+/// The interval to check the worker file for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+lazy_static! {
+    /// The global pool of workers. The pool starts with the `SPIDER_WORKER` and `SPIDER_WORKER_SCRAPER` workers,
+    /// or the workers in the file set with `SPIDER_WORKER_FILE`.
+    pub static ref WORKER_POOL: WorkerPool = {
+        let pool = WorkerPool::new(crate::website::calc_limits(3));
+
+        let mut workers = Vec::new();
+
+        for worker in std::env::var("SPIDER_WORKER_SCRAPER")
+            .unwrap_or_else(|_| "http://127.0.0.1:3031".to_string())
+            .split(",")
+            .chain(
+                std::env::var("SPIDER_WORKER")
+                    .unwrap_or_else(|_| "http://127.0.0.1:3030".to_string())
+                    .split(","),
+            )
+        {
+            workers.push(worker.to_string());
+        }
+
+        match std::env::var("SPIDER_WORKER_FILE") {
+            Ok(path) if pool.load_file(&path).is_ok() && !pool.is_empty() => (),
+            _ => pool.sync(&workers),
+        }
+
+        pool
+    };
+}
+
+/// A worker in the pool.
+#[derive(Debug)]
+pub struct Worker {
+    /// The url of the worker.
+    url: String,
+    /// The request currently handled by the worker.
+    in_flight: AtomicUsize,
+    /// The consecutive failed request.
+    failures: AtomicUsize,
+    /// The time the worker was ejected from the rotation.
+    ejected_at: Mutex<Option<Instant>>,
+}
+
+impl Worker {
+    /// A new worker.
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.into(),
+            in_flight: AtomicUsize::new(0),
+            failures: AtomicUsize::new(0),
+            ejected_at: Mutex::new(None),
+        }
+    }
+
+    /// The url of the worker.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The amount of request currently handled by the worker.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// The worker is in the rotation.
+    pub fn is_healthy(&self) -> bool {
+        self.ejected_at().is_none()
+    }
+
+    /// The time the worker was ejected.
+    fn ejected_at(&self) -> Option<Instant> {
+        match self.ejected_at.lock() {
+            Ok(ejected_at) => *ejected_at,
+            _ => None,
+        }
+    }
+
+    /// Set the time the worker was ejected.
+    fn set_ejected_at(&self, ejected_at: Option<Instant>) {
+        if let Ok(mut e) = self.ejected_at.lock() {
+            *e = ejected_at;
+        }
+    }
+}
+
+/// A worker leased for a request. The in-flight count is released when dropped.
+#[derive(Debug)]
+pub struct WorkerLease {
+    /// The worker handling the request.
+    worker: Arc<Worker>,
+}
+
+impl WorkerLease {
+    /// Lease the worker for a request.
+    fn new(worker: Arc<Worker>) -> Self {
+        worker.in_flight.fetch_add(1, Ordering::Relaxed);
+        Self { worker }
+    }
+
+    /// The url of the worker.
+    pub fn url(&self) -> &str {
+        self.worker.url()
+    }
+
+    /// The request succeeded, add the worker back into the rotation.
+    pub fn success(&self) {
+        self.worker.failures.store(0, Ordering::Relaxed);
+        self.worker.set_ejected_at(None);
+    }
+
+    /// The worker could not be reached. The worker is ejected after too many consecutive failures.
+    pub fn failure(&self) {
+        let failures = self.worker.failures.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if failures >= MAX_FAILURES {
+            self.worker.set_ejected_at(Some(Instant::now()));
+        }
+    }
+}
+
+impl Drop for WorkerLease {
+    fn drop(&mut self) {
+        self.worker.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A permit of the pool for a request. The permit is forgotten on release while the pool owes the permits of removed workers.
+#[derive(Debug)]
+pub struct WorkerPermit<'a> {
+    /// The semaphore permit.
+    permit: Option<SemaphorePermit<'a>>,
+    /// The pool of the permit.
+    pool: &'a WorkerPool,
+}
+
+impl Drop for WorkerPermit<'_> {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            if self.pool.settle_owed_permits(1) == 1 {
+                permit.forget();
+            }
+        }
+    }
+}
+
+/// A pool of workers that can change while crawling. Request are routed to the healthy worker with the least in-flight request.
+#[derive(Debug)]
+pub struct WorkerPool {
+    /// The workers in the pool.
+    workers: RwLock<Vec<Arc<Worker>>>,
+    /// The concurrency limit across all workers.
+    semaphore: Semaphore,
+    /// The permits added for each worker.
+    permits_per_worker: usize,
+    /// The permits of the removed workers held by request. The permits are forgotten when released.
+    owed_permits: AtomicUsize,
+    /// The worker file is watched.
+    watching: AtomicBool,
+}
+
+impl WorkerPool {
+    /// A new empty pool allowing the amount of concurrent request per worker.
+    pub fn new(permits_per_worker: usize) -> Self {
+        let permits_per_worker = permits_per_worker.max(1);
+
+        Self {
+            workers: RwLock::new(Vec::new()),
+            // keep the permits of one worker so request wait instead of blocking when the pool is empty.
+            semaphore: Semaphore::new(permits_per_worker),
+            permits_per_worker,
+            owed_permits: AtomicUsize::new(0),
+            watching: AtomicBool::new(false),
+        }
+    }
+
+    /// Add a worker to the pool. Returns `false` if the worker already exist.
+    pub fn register(&self, url: &str) -> bool {
+        let url = url.trim();
+
+        if url.is_empty() {
+            return false;
+        }
+
+        let added = match self.workers.write() {
+            Ok(mut workers) if !workers.iter().any(|w| w.url == url) => {
+                workers.push(Arc::new(Worker::new(url)));
+                Some(workers.len() > 1)
+            }
+            _ => None,
+        };
+
+        match added {
+            Some(grow) => {
+                if grow {
+                    let settled = self.settle_owed_permits(self.permits_per_worker);
+                    self.semaphore
+                        .add_permits(self.permits_per_worker - settled);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove a worker from the pool. Returns `false` if the worker does not exist.
+    pub fn remove(&self, url: &str) -> bool {
+        let url = url.trim();
+
+        let removed = match self.workers.write() {
+            Ok(mut workers) => match workers.iter().position(|w| w.url == url) {
+                Some(index) => {
+                    workers.remove(index);
+                    Some(!workers.is_empty())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some(release) = removed {
+            if release {
+                let forgotten = self.semaphore.forget_permits(self.permits_per_worker);
+                self.owed_permits
+                    .fetch_add(self.permits_per_worker - forgotten, Ordering::Relaxed);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set the workers of the pool, keeping the state of the workers that remain.
+    pub fn sync<T: AsRef<str>>(&self, urls: &[T]) {
+        for worker in self.urls() {
+            if !urls.iter().any(|u| u.as_ref().trim() == worker) {
+                self.remove(&worker);
+            }
+        }
+        for url in urls {
+            self.register(url.as_ref());
+        }
+    }
+
+    /// The pool contains the worker.
+    pub fn contains(&self, url: &str) -> bool {
+        match self.workers.read() {
+            Ok(workers) => workers.iter().any(|w| w.url == url),
+            _ => false,
+        }
+    }
+
+    /// The urls of the workers in the pool.
+    pub fn urls(&self) -> Vec<String> {
+        match self.workers.read() {
+            Ok(workers) => workers.iter().map(|w| w.url.clone()).collect(),
+            _ => Default::default(),
+        }
+    }
+
+    /// The workers in the pool.
+    pub fn workers(&self) -> Vec<Arc<Worker>> {
+        match self.workers.read() {
+            Ok(workers) => workers.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// The amount of workers in the pool.
+    pub fn len(&self) -> usize {
+        match self.workers.read() {
+            Ok(workers) => workers.len(),
+            _ => 0,
+        }
+    }
+
+    /// The pool does not have any workers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The semaphore limiting the concurrent request across the workers.
+    pub fn semaphore(&self) -> &Semaphore {
+        &self.semaphore
+    }
+
+    /// Acquire a permit to send a request. Returns `None` when the semaphore is closed.
+    pub async fn acquire(&self) -> Option<WorkerPermit<'_>> {
+        self.semaphore
+            .acquire()
+            .await
+            .ok()
+            .map(|permit| WorkerPermit {
+                permit: Some(permit),
+                pool: self,
+            })
+    }
+
+    /// Take up to the amount from the permits owed. Returns the amount settled.
+    fn settle_owed_permits(&self, amount: usize) -> usize {
+        match self
+            .owed_permits
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |owed| {
+                Some(owed - owed.min(amount))
+            }) {
+            Ok(owed) => owed.min(amount),
+            _ => 0,
+        }
+    }
+
+    /// Lease the healthy worker with the least in-flight request skipping the workers excluded.
+    /// Ejected workers are tried again after the cooldown or when every other worker failed.
+    pub fn lease(&self, exclude: &[String]) -> Option<WorkerLease> {
+        let workers = self.workers.read().ok()?;
+        let candidates = workers.iter().filter(|w| !exclude.contains(&w.url));
+
+        let mut healthy: Option<&Arc<Worker>> = None;
+        let mut ejected: Option<(&Arc<Worker>, Instant)> = None;
+
+        for worker in candidates {
+            match worker.ejected_at() {
+                Some(at) if at.elapsed() < EJECTION_COOLDOWN => {
+                    ejected = match ejected {
+                        Some((_, e)) if e <= at => ejected,
+                        _ => Some((worker, at)),
+                    };
+                }
+                _ => {
+                    healthy = match healthy {
+                        Some(h) if h.in_flight() <= worker.in_flight() => healthy,
+                        _ => Some(worker),
+                    };
+                }
+            }
+        }
+
+        healthy
+            .or(ejected.map(|(worker, _)| worker))
+            .map(|worker| WorkerLease::new(worker.clone()))
+    }
+
+    /// Set the workers from a file with a worker url per line or comma separated.
+    pub fn load_file(&self, path: &str) -> std::io::Result<()> {
+        let workers = parse_workers(&std::fs::read_to_string(path)?);
+        self.sync(&workers);
+        Ok(())
+    }
+
+    /// Reload the workers when the file changes. The file is only watched once per pool.
+    pub fn watch_file(&'static self, path: String) {
+        if self.watching.swap(true, Ordering::Relaxed) {
+            return;
+        }
+
+        crate::utils::spawn_task("worker_watch", async move {
+            let modified = |path: &str| -> Option<SystemTime> {
+                std::fs::metadata(path).and_then(|m| m.modified()).ok()
+            };
+            let mut last_modified = modified(&path);
+            let mut interval = tokio::time::interval(WATCH_INTERVAL);
+
+            loop {
+                interval.tick().await;
+                let current = modified(&path);
+
+                if current.is_some() && current != last_modified {
+                    last_modified = current;
+                    if let Err(e) = self.load_file(&path) {
+                        log::warn!("unable to reload the workers {} - {:?}", path, e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Watch the file set with `SPIDER_WORKER_FILE` for worker changes.
+    pub fn watch_env_file(&'static self) {
+        if let Ok(path) = std::env::var("SPIDER_WORKER_FILE") {
+            self.watch_file(path);
+        }
+    }
+}
+
+/// Parse the worker urls separated by lines or commas. Lines starting with `#` are ignored.
+pub fn parse_workers(input: &str) -> Vec<String> {
+    let mut workers: Vec<String> = Vec::new();
+
+    for worker in input
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(|line| line.split(','))
+        .map(|worker| worker.trim())
+    {
+        if !worker.is_empty() && !workers.iter().any(|w| w == worker) {
+            workers.push(worker.to_string());
+        }
+    }
+
+    workers
+}
+
+/// The target url to send to the worker. Workers without TLS receive the `http` url.
+pub fn worker_target(worker: &str, target_url: &str) -> String {
+    if worker.starts_with("http:") && target_url.starts_with("https") {
+        target_url.replacen("https", "http", 1)
+    } else {
+        target_url.to_string()
+    }
+}
+
+/// The HTTP clients of a crawl for each worker in the pool. The clients are built as workers join the pool.
+pub struct WorkerClients {
+    /// The clients by worker url.
+    clients: RwLock<hashbrown::HashMap<String, Client>>,
+    /// Build the client for the worker.
+    builder: Box<dyn Fn(&str) -> Client + Send + Sync>,
+}
+
+impl std::fmt::Debug for WorkerClients {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerClients")
+            .field("clients", &self.clients)
+            .finish()
+    }
+}
+
+impl WorkerClients {
+    /// The worker clients built with the builder.
+    pub fn new(builder: impl Fn(&str) -> Client + Send + Sync + 'static) -> Self {
+        Self {
+            clients: Default::default(),
+            builder: Box::new(builder),
+        }
+    }
+
+    /// Get the client for the worker.
+    pub fn get(&self, worker: &str) -> Client {
+        if let Ok(clients) = self.clients.read() {
+            if let Some(client) = clients.get(worker) {
+                return client.clone();
+            }
+        }
+
+        let client = (self.builder)(worker);
+
+        if let Ok(mut clients) = self.clients.write() {
+            clients.insert(worker.to_string(), client.clone());
+        }
+
+        client
+    }
+
+    /// Send the request to the least loaded worker of the pool. The request returns `None` when the worker
+    /// could not be reached and is retried on a different worker until every worker was tried.
+    pub async fn fetch<T, F, Fut>(
+        &self,
+        pool: &WorkerPool,
+        target_url: &str,
+        request: F,
+    ) -> Option<T>
+    where
+        F: Fn(Client, String) -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
+        let mut tried = Vec::new();
+
+        while let Some(lease) = pool.lease(&tried) {
+            let client = self.get(lease.url());
+
+            match request(client, worker_target(lease.url(), target_url)).await {
+                Some(result) => {
+                    lease.success();
+                    return Some(result);
+                }
+                _ => {
+                    lease.failure();
+                    tried.push(lease.url().to_string());
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[test]
+fn test_parse_workers() {
+    let workers = parse_workers(
+        "# workers\nhttp://127.0.0.1:3030, http://127.0.0.1:3031\n\nhttp://127.0.0.1:3030\n",
+    );
+    assert_eq!(
+        workers,
+        vec!["http://127.0.0.1:3030", "http://127.0.0.1:3031"]
+    );
+}
+
+#[test]
+fn test_worker_pool_lease() {
+    let pool = WorkerPool::new(2);
+    pool.sync(&["http://a:3030", "http://b:3030"]);
+    assert_eq!(pool.semaphore().available_permits(), 4);
+
+    let first = pool.lease(&[]).unwrap();
+    let second = pool.lease(&[]).unwrap();
+    assert_ne!(
+        first.url(),
+        second.url(),
+        "route to the least loaded worker"
+    );
+
+    for _ in 0..MAX_FAILURES {
+        first.failure();
+    }
+    drop(second);
+
+    assert!(!pool.workers()[0].is_healthy());
+    assert_eq!(pool.lease(&[]).unwrap().url(), "http://b:3030");
+    assert_eq!(
+        pool.lease(&["http://b:3030".into()]).unwrap().url(),
+        "http://a:3030",
+        "fallback to the ejected worker"
+    );
+
+    first.success();
+    assert!(pool.workers()[0].is_healthy());
+
+    assert!(pool.remove("http://a:3030"));
+    assert!(!pool.remove("http://a:3030"));
+    assert_eq!(pool.urls(), vec!["http://b:3030"]);
+    assert_eq!(pool.semaphore().available_permits(), 2);
+}
+
+#[tokio::test]
+async fn test_worker_pool_owed_permits() {
+    let pool = WorkerPool::new(2);
+    pool.sync(&["http://a:3030", "http://b:3030", "http://c:3030"]);
+
+    let mut permits = Vec::new();
+
+    for _ in 0..5 {
+        permits.push(pool.acquire().await.unwrap());
+    }
+
+    assert_eq!(pool.semaphore().available_permits(), 1);
+    assert!(pool.remove("http://a:3030"));
+    assert_eq!(pool.semaphore().available_permits(), 0);
+
+    drop(permits);
+    assert_eq!(
+        pool.semaphore().available_permits(),
+        4,
+        "the held permits of the removed worker are forgotten"
+    );
+
+    let mut permits = Vec::new();
+
+    for _ in 0..4 {
+        permits.push(pool.acquire().await.unwrap());
+    }
+
+    assert!(pool.remove("http://b:3030"));
+    assert!(pool.register("http://b:3030"));
+    drop(permits);
+    assert_eq!(
+        pool.semaphore().available_permits(),
+        4,
+        "the owed permits are settled by the worker added back"
+    );
+}
@@ -152,6 +152,8 @@ pub extern crate spider_firewall;
 pub mod client;
 /// Configuration structure for `Website`.
 pub mod configuration;
+/// Crawl multiple websites together sharing resources.
+pub mod crawl_group;
 /// Optional features to use.
 pub mod features;
 /// Internal packages customized.
@@ -300,6 +300,33 @@ pub struct Page {
     #[cfg(feature = "chrome")]
     /// All of the request events mapped with the time period of the event sent.
     pub request_map: Option<hashbrown::HashMap<String, f64>>,
+    #[cfg(feature = "chrome")]
+    /// The HAR of the page network activity. This is only collected with the `har` event tracker enabled.
+    pub har: Option<Box<crate::features::har::Har>>,
+    #[cfg(feature = "chrome")]
+    /// The responses captured while rendering. This is only collected with the `response_capture` event tracker set.
+    pub captured_responses: Option<Vec<crate::features::response_capture::CapturedResponse>>,
+    #[cfg(feature = "chrome")]
+    /// The console output, uncaught exceptions, browser logs and dialogs of the page. This is only collected with the `console_logs` event tracker enabled.
+    pub console_logs: Option<Vec<crate::features::console_logs::ConsoleLog>>,
+    #[cfg(feature = "chrome")]
+    /// The values stored by the `Extract` automation steps of the page.
+    pub automation_data: Option<crate::features::chrome_common::AutomationData>,
+    #[cfg(feature = "chrome")]
+    /// The PDF of the page. This is only set with the `PdfConfig` bytes enabled.
+    pub pdf_bytes: Option<Vec<u8>>,
+    #[cfg(feature = "chrome")]
+    /// The MHTML snapshot of the page. This is only set with the `MhtmlConfig` bytes enabled.
+    pub mhtml_bytes: Option<Vec<u8>>,
+    #[cfg(feature = "chrome")]
+    /// The accessibility tree of the page. This is only collected with the `accessibility_tree` event tracker enabled.
+    pub accessibility_tree: Option<Vec<crate::features::page_snapshot::AccessibilityNode>>,
+    #[cfg(feature = "chrome")]
+    /// The visible text of the page with the bounding boxes. This is only collected with the `visible_text` event tracker enabled.
+    pub visible_text: Option<Vec<crate::features::page_snapshot::TextBox>>,
+    #[cfg(feature = "metadata")]
+    /// The embedded metadata of the page. This is only collected with the `metadata` configuration enabled.
+    pub metadata: Option<Box<crate::features::metadata::PageMetadata>>,
 }
 
 /// Represent a page visited.
@@ -343,6 +370,9 @@ pub struct Page {
     pub blocked_crawl: bool,
     /// The signature of the page to de-duplicate content.
     pub signature: Option<u64>,
+    #[cfg(feature = "metadata")]
+    /// The embedded metadata of the page. This is only collected with the `metadata` configuration enabled.
+    pub metadata: Option<Box<crate::features::metadata::PageMetadata>>,
 }
 
 /// Validate link and push into the map
@@ -785,6 +815,24 @@ pub fn build(url: &str, res: PageResponse) -> Page {
         response_map: res.response_map,
         #[cfg(feature = "chrome")]
         request_map: res.request_map,
+        #[cfg(feature = "chrome")]
+        har: res.har,
+        #[cfg(feature = "chrome")]
+        captured_responses: res.captured_responses,
+        #[cfg(feature = "chrome")]
+        console_logs: res.console_logs,
+        #[cfg(feature = "chrome")]
+        automation_data: res.automation_data,
+        #[cfg(feature = "chrome")]
+        pdf_bytes: res.pdf_bytes,
+        #[cfg(feature = "chrome")]
+        mhtml_bytes: res.mhtml_bytes,
+        #[cfg(feature = "chrome")]
+        accessibility_tree: res.accessibility_tree,
+        #[cfg(feature = "chrome")]
+        visible_text: res.visible_text,
+        #[cfg(feature = "metadata")]
+        metadata: res.metadata,
     }
 }
 
@@ -829,6 +877,8 @@ pub struct PageLinkBuildSettings {
     pub subdomains: bool,
     /// De-duplication signature.
     pub normalize: bool,
+    /// Collect the embedded metadata of the page. This does nothing without the flag `metadata` enabled.
+    pub metadata: bool,
 }
 
 impl PageLinkBuildSettings {
@@ -848,6 +898,7 @@ impl PageLinkBuildSettings {
         subdomains: bool,
         tld: bool,
         normalize: bool,
+        metadata: bool,
     ) -> Self {
         Self {
             ssg_build,
@@ -855,6 +906,7 @@ impl PageLinkBuildSettings {
             subdomains,
             tld,
             normalize,
+            metadata,
         }
     }
 }
@@ -1057,8 +1109,22 @@ impl Page {
                     }));
                 }
 
+                #[cfg(feature = "metadata")]
+                let metadata_collector = if r_settings.metadata {
+                    let collector = crate::features::metadata::MetadataCollector::new();
+                    element_content_handlers.extend(collector.element_content_handlers());
+                    Some(collector)
+                } else {
+                    None
+                };
+
                 let settings = lol_html::send::Settings {
                     element_content_handlers,
+                    #[cfg(feature = "metadata")]
+                    document_content_handlers: match metadata_collector {
+                        Some(ref collector) => collector.document_content_handlers(),
+                        _ => Vec::new(),
+                    },
                     adjust_charset_on_meta_tag,
                     encoding,
                     ..lol_html::send::Settings::new_for_handler_types()
@@ -1088,6 +1154,11 @@ impl Page {
                     let _ = rewriter.end();
                 }
 
+                #[cfg(feature = "metadata")]
+                if let Some(collector) = metadata_collector {
+                    response.0.metadata = Some(Box::new(collector.finish()));
+                }
+
                 if r_settings.normalize {
                     response.0.signature = Some(hash_html(&collected_bytes).await);
                 }
@@ -1230,68 +1301,85 @@ impl Page {
     }
 
     /// Instantiate a new page and gather the links.
-    #[cfg(all(feature = "decentralized", not(feature = "headers")))]
+    #[cfg(feature = "decentralized")]
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn new(url: &str, client: &Client) -> Self {
-        Self::new_links_only(url, client).await
+        Self::new_worker(url, client).await.unwrap_or_default()
+    }
+
+    /// Instantiate a new page and gather the links. Returns `None` when the worker could not be reached or did not handle the request.
+    #[cfg(all(feature = "decentralized", not(feature = "headers")))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn new_worker(url: &str, client: &Client) -> Option<Self> {
+        Self::new_links_only_worker(url, client).await
     }
 
-    /// Instantiate a new page and gather the headers and links.
+    /// Instantiate a new page and gather the headers and links. Returns `None` when the worker could not be reached or did not handle the request.
     #[cfg(all(feature = "decentralized", feature = "headers"))]
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all,))]
-    pub async fn new(url: &str, client: &Client) -> Self {
-        use crate::serde::Deserialize;
-        use bytes::Buf;
-
-        match crate::utils::fetch_page_and_headers(&url, &client).await {
+    pub async fn new_worker(url: &str, client: &Client) -> Option<Self> {
+        let page = match crate::utils::fetch_page_and_headers(&url, &client).await {
             FetchPageResult::Success(headers, page_content) => {
-                let links = match page_content {
-                    Some(b) => match flexbuffers::Reader::get_root(b) {
-                        Ok(buf) => match HashSet::<CaseInsensitiveString>::deserialize(buf) {
-                            Ok(link) => link,
-                            _ => Default::default(),
-                        },
-                        _ => Default::default(),
-                    },
-                    _ => Default::default(),
-                };
-                Page {
-                    html: None,
-                    headers: Some(headers),
-                    links,
-                    ..Default::default()
-                }
+                let mut page = Self::from_worker_payload(page_content.as_deref());
+                page.headers = Some(headers);
+                page
             }
-            FetchPageResult::NoSuccess(headers) => Page {
-                headers: Some(headers),
-                ..Default::default()
-            },
-            FetchPageResult::FetchError => Default::default(),
-        }
+            FetchPageResult::NoSuccess(_) | FetchPageResult::FetchError => return None,
+        };
+
+        Some(page)
     }
 
     /// Instantiate a new page and gather the links.
     #[cfg(all(feature = "decentralized"))]
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all,))]
     pub async fn new_links_only(url: &str, client: &Client) -> Self {
+        Self::new_links_only_worker(url, client)
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Instantiate a new page and gather the links. Returns `None` when the worker could not be reached or did not handle the request.
+    #[cfg(feature = "decentralized")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all,))]
+    pub async fn new_links_only_worker(url: &str, client: &Client) -> Option<Self> {
+        let payload = crate::utils::fetch_page_worker(url, client).await.ok()?;
+
+        Some(Self::from_worker_payload(payload.as_deref()))
+    }
+
+    /// Build the page from the worker payload. The payload contains the links found or the rendered page of a chrome worker.
+    #[cfg(feature = "decentralized")]
+    fn from_worker_payload(payload: Option<&[u8]>) -> Self {
         use crate::serde::Deserialize;
 
-        let links = match crate::utils::fetch_page(&url, &client).await {
-            Some(b) => match flexbuffers::Reader::get_root(b.as_slice()) {
-                Ok(buf) => match HashSet::<CaseInsensitiveString>::deserialize(buf) {
-                    Ok(link) => link,
-                    _ => Default::default(),
-                },
-                _ => Default::default(),
-            },
-            _ => Default::default(),
-        };
+        let mut page = Page::default();
 
-        Page {
-            html: None,
-            links,
-            ..Default::default()
+        if let Some(Ok(buf)) = payload.map(flexbuffers::Reader::get_root) {
+            match HashSet::<CaseInsensitiveString>::deserialize(buf.clone()) {
+                Ok(links) => page.links = links,
+                #[cfg(feature = "decentralized_headers")]
+                _ => {
+                    if let Ok(rendered) =
+                        crate::features::decentralized_headers::WorkerRenderedPage::deserialize(buf)
+                    {
+                        page.links = rendered.links;
+                        page.html = rendered.html.map(Box::new);
+                        #[cfg(feature = "chrome")]
+                        {
+                            page.screenshot_bytes = rendered.screenshot;
+                        }
+                        if let Ok(status_code) = StatusCode::from_u16(rendered.status_code) {
+                            page.status_code = status_code;
+                        }
+                    }
+                }
+                #[cfg(not(feature = "decentralized_headers"))]
+                _ => (),
+            }
         }
+
+        page
     }
 
     #[cfg(not(all(not(feature = "decentralized"), feature = "chrome")))]
@@ -1595,6 +1683,12 @@ impl Page {
         }
     }
 
+    /// Extract the embedded metadata from the html. Use the `metadata` field when the metadata was collected while streaming.
+    #[cfg(feature = "metadata")]
+    pub fn extract_metadata(&self) -> crate::features::metadata::PageMetadata {
+        crate::features::metadata::extract_metadata(self.get_html_bytes_u8())
+    }
+
     /// Get the response events mapped.
     #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
     pub fn get_responses(&self) -> &Option<hashbrown::HashMap<String, f64>> {
@@ -1607,6 +1701,66 @@ impl Page {
         &self.request_map
     }
 
+    /// Get the HAR of the page network activity.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_har(&self) -> Option<&crate::features::har::Har> {
+        self.har.as_deref()
+    }
+
+    /// Get the responses captured while rendering like the JSON of the XHR and fetch requests.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_captured_responses(
+        &self,
+    ) -> Option<&[crate::features::response_capture::CapturedResponse]> {
+        self.captured_responses.as_deref()
+    }
+
+    /// Get the console output, uncaught exceptions, browser logs and dialogs of the page.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_console_logs(&self) -> Option<&[crate::features::console_logs::ConsoleLog]> {
+        self.console_logs.as_deref()
+    }
+
+    /// Get the PDF of the page.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_pdf_bytes(&self) -> Option<&[u8]> {
+        self.pdf_bytes.as_deref()
+    }
+
+    /// Get the MHTML snapshot of the page.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_mhtml_bytes(&self) -> Option<&[u8]> {
+        self.mhtml_bytes.as_deref()
+    }
+
+    /// Get the accessibility tree of the page.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_accessibility_tree(
+        &self,
+    ) -> Option<&[crate::features::page_snapshot::AccessibilityNode]> {
+        self.accessibility_tree.as_deref()
+    }
+
+    /// Get the visible text of the page with the bounding boxes in the layout order.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_visible_text(&self) -> Option<&[crate::features::page_snapshot::TextBox]> {
+        self.visible_text.as_deref()
+    }
+
+    /// Get the visible text of the page as plain text with a line for each row of text.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_visible_text_dump(&self) -> Option<String> {
+        self.visible_text
+            .as_deref()
+            .map(crate::features::page_snapshot::visible_text_dump)
+    }
+
+    /// Get the values stored by the `Extract` automation steps by name.
+    #[cfg(all(feature = "chrome", not(feature = "decentralized")))]
+    pub fn get_automation_data(&self) -> Option<&crate::features::chrome_common::AutomationData> {
+        self.automation_data.as_ref()
+    }
+
     /// Html getter for getting the content with proper encoding. Pass in a proper encoding label like SHIFT_JIS. This fallsback to get_html without the `encoding` flag enabled.
     #[cfg(feature = "encoding")]
     pub fn get_html_encoded(&self, label: &str) -> String {
@@ -140,6 +140,31 @@ where
         }
     }
 
+    /// Remove the link from the bucket.
+    #[inline(always)]
+    pub fn remove(&mut self, link: &K) -> bool {
+        #[cfg(any(
+            feature = "string_interner_bucket_backend",
+            feature = "string_interner_string_backend",
+            feature = "string_interner_buffer_backend",
+        ))]
+        {
+            match self.interner.get(link.as_ref()) {
+                Some(symbol) => self.links_visited.remove(&symbol),
+                _ => false,
+            }
+        }
+
+        #[cfg(not(any(
+            feature = "string_interner_bucket_backend",
+            feature = "string_interner_string_backend",
+            feature = "string_interner_buffer_backend",
+        )))]
+        {
+            self.links_visited.remove(link)
+        }
+    }
+
     /// Does the bucket contain the link.
     #[inline(always)]
     pub fn contains(&self, link: &K) -> bool {
@@ -270,6 +270,33 @@ pub struct PageResponse {
     #[cfg(feature = "chrome")]
     /// All of the request events mapped with the time period of the event sent.
     pub request_map: Option<HashMap<String, f64>>,
+    #[cfg(feature = "chrome")]
+    /// The HAR of the page network activity.
+    pub har: Option<Box<crate::features::har::Har>>,
+    #[cfg(feature = "chrome")]
+    /// The responses captured with the response capture filter.
+    pub captured_responses: Option<Vec<crate::features::response_capture::CapturedResponse>>,
+    #[cfg(feature = "chrome")]
+    /// The console output, exceptions, browser logs and dialogs of the page.
+    pub console_logs: Option<Vec<crate::features::console_logs::ConsoleLog>>,
+    #[cfg(feature = "chrome")]
+    /// The values stored by the `Extract` automation steps.
+    pub automation_data: Option<crate::features::chrome_common::AutomationData>,
+    #[cfg(feature = "chrome")]
+    /// The PDF of the page.
+    pub pdf_bytes: Option<Vec<u8>>,
+    #[cfg(feature = "chrome")]
+    /// The MHTML snapshot of the page.
+    pub mhtml_bytes: Option<Vec<u8>>,
+    #[cfg(feature = "chrome")]
+    /// The accessibility tree of the page.
+    pub accessibility_tree: Option<Vec<crate::features::page_snapshot::AccessibilityNode>>,
+    #[cfg(feature = "chrome")]
+    /// The visible text of the page with the bounding boxes.
+    pub visible_text: Option<Vec<crate::features::page_snapshot::TextBox>>,
+    #[cfg(feature = "metadata")]
+    /// The embedded metadata collected while streaming the page.
+    pub metadata: Option<Box<crate::features::metadata::PageMetadata>>,
 }
 
 /// wait for event with timeout
@@ -1155,6 +1182,30 @@ pub async fn fetch_page_html_chrome_base(
         },
     );
 
+    let har_recorder = match track_events {
+        Some(tracker) if tracker.har => {
+            crate::features::har::HarRecorder::new(page, tracker.har_body_limit).await
+        }
+        _ => None,
+    };
+
+    let response_recorder = match track_events {
+        Some(tracker) => match tracker.response_capture {
+            Some(ref capture) => {
+                crate::features::response_capture::ResponseRecorder::new(page, capture).await
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let console_recorder = match track_events {
+        Some(tracker) if tracker.console_logs => {
+            crate::features::console_logs::ConsoleLogRecorder::new(page).await
+        }
+        _ => None,
+    };
+
     let (tx, rx) = oneshot::channel::<bool>();
 
     // Listen for network events. todo: capture the last values endtime to track period.
@@ -1481,6 +1532,8 @@ pub async fn fetch_page_html_chrome_base(
 
             base_timeout = sub_duration(base_timeout_measurement, start_time.elapsed());
 
+            let mut automation_data = crate::features::chrome_common::AutomationData::default();
+
             if execution_scripts.is_some() || automation_scripts.is_some() {
                 let target_url = if final_url.is_some() {
                     match final_url.as_ref() {
@@ -1493,7 +1546,7 @@ pub async fn fetch_page_html_chrome_base(
                     source.to_string()
                 };
 
-                if let Err(elasped) = tokio::time::timeout(base_timeout, async {
+                match tokio::time::timeout(base_timeout, async {
                     tokio::join!(
                         crate::features::chrome_common::eval_execution_scripts(
                             &page,
@@ -1505,11 +1558,13 @@ pub async fn fetch_page_html_chrome_base(
                             &target_url,
                             &automation_scripts
                         )
-                    );
+                    )
+                    .1
                 })
                 .await
                 {
-                    log::warn!("eval scripts timeout exceeded {elasped}");
+                    Ok(data) => automation_data = data,
+                    Err(elasped) => log::warn!("eval scripts timeout exceeded {elasped}"),
                 }
             }
 
@@ -1545,6 +1600,10 @@ pub async fn fetch_page_html_chrome_base(
                 final_url,
             );
 
+            if !automation_data.is_empty() {
+                page_response.automation_data = Some(automation_data);
+            }
+
             base_timeout = sub_duration(base_timeout_measurement, start_time.elapsed());
 
             let _ = tokio::time::timeout(
@@ -1578,6 +1637,38 @@ pub async fn fetch_page_html_chrome_base(
                 .await;
             }
 
+            if let Some(tracker) = track_events {
+                if tracker.pdf.is_some() || tracker.mhtml.is_some() {
+                    let _ = tokio::time::timeout(
+                        tokio::time::Duration::from_secs(30),
+                        perform_page_archive(source, page, tracker, &mut page_response),
+                    )
+                    .await;
+                }
+
+                if tracker.accessibility_tree {
+                    if let Ok(tree) = tokio::time::timeout(
+                        tokio::time::Duration::from_secs(30),
+                        crate::features::page_snapshot::accessibility_tree(page),
+                    )
+                    .await
+                    {
+                        page_response.accessibility_tree = tree;
+                    }
+                }
+
+                if tracker.visible_text {
+                    if let Ok(text) = tokio::time::timeout(
+                        tokio::time::Duration::from_secs(30),
+                        crate::features::page_snapshot::visible_text(page),
+                    )
+                    .await
+                    {
+                        page_response.visible_text = text;
+                    }
+                }
+            }
+
             page_response
         } else {
             let res = if !block_bytes {
@@ -1697,6 +1788,20 @@ pub async fn fetch_page_html_chrome_base(
         page_response.status_code = StatusCode::OK;
     }
 
+    if let Some(har_recorder) = har_recorder {
+        page_response.har = Some(Box::new(
+            har_recorder.finish(url_target.unwrap_or(source)).await,
+        ));
+    }
+
+    if let Some(response_recorder) = response_recorder {
+        page_response.captured_responses = Some(response_recorder.finish().await);
+    }
+
+    if let Some(console_recorder) = console_recorder {
+        page_response.console_logs = Some(console_recorder.finish().await);
+    }
+
     // run initial handling hidden anchors
     // if let Ok(new_links) = page.evaluate(crate::features::chrome::ANCHOR_EVENTS).await {
     //     if let Ok(results) = new_links.into_value::<hashbrown::HashSet<CaseInsensitiveString>>() {
@@ -1869,6 +1974,71 @@ async fn set_page_response_cookies(page_response: &mut PageResponse, page: &chro
 async fn set_page_response_cookies(_page_response: &mut PageResponse, _page: &chromiumoxide::Page) {
 }
 
+/// Print the PDF and capture the MHTML snapshot of the page.
+#[cfg(feature = "chrome")]
+pub async fn perform_page_archive(
+    target_url: &str,
+    page: &chromiumoxide::Page,
+    track_events: &crate::configuration::ChromeEventTracker,
+    page_response: &mut PageResponse,
+) {
+    if let Some(ref pdf) = track_events.pdf {
+        match page.pdf(pdf.params.clone().into()).await {
+            Ok(b) => {
+                if pdf.save {
+                    let output_path = create_output_path(
+                        &pdf.output_dir
+                            .clone()
+                            .unwrap_or_else(|| "./storage/".into()),
+                        target_url,
+                        ".pdf",
+                    )
+                    .await;
+                    let _ = tokio::fs::write(output_path, &b).await;
+                }
+                if pdf.bytes {
+                    page_response.pdf_bytes = Some(b);
+                }
+            }
+            Err(e) => log::error!("failed to print pdf: {:?} - {:?}", e, target_url),
+        }
+    }
+
+    if let Some(ref mhtml) = track_events.mhtml {
+        match page
+            .execute(
+                chromiumoxide::cdp::browser_protocol::page::CaptureSnapshotParams::builder()
+                    .format(
+                        chromiumoxide::cdp::browser_protocol::page::CaptureSnapshotFormat::Mhtml,
+                    )
+                    .build(),
+            )
+            .await
+        {
+            Ok(snapshot) => {
+                let b = snapshot.result.data.into_bytes();
+
+                if mhtml.save {
+                    let output_path = create_output_path(
+                        &mhtml
+                            .output_dir
+                            .clone()
+                            .unwrap_or_else(|| "./storage/".into()),
+                        target_url,
+                        ".mhtml",
+                    )
+                    .await;
+                    let _ = tokio::fs::write(output_path, &b).await;
+                }
+                if mhtml.bytes {
+                    page_response.mhtml_bytes = Some(b);
+                }
+            }
+            Err(e) => log::error!("failed to capture mhtml: {:?} - {:?}", e, target_url),
+        }
+    }
+}
+
 /// Perform a screenshot shortcut.
 #[cfg(feature = "chrome")]
 pub async fn perform_screenshot(
@@ -2236,18 +2406,42 @@ pub async fn fetch_page_html_raw_only_html(target_url: &str, client: &Client) ->
 /// Perform a network request to a resource extracting all content as text.
 #[cfg(feature = "decentralized")]
 pub async fn fetch_page(target_url: &str, client: &Client) -> Option<Vec<u8>> {
+    fetch_page_worker(target_url, client)
+        .await
+        .unwrap_or_default()
+}
+
+/// The worker could not handle the request.
+#[cfg(feature = "decentralized")]
+#[derive(Debug)]
+pub enum WorkerError {
+    /// The worker could not be reached.
+    Request(crate::client::Error),
+    /// The worker or the gateway in front of it responded with a non success status. The worker responds with a success status and the status of the target in the headers.
+    Status(StatusCode),
+}
+
+/// Perform a network request to a resource through a worker. The error is returned when the worker could not be reached or did not handle the request.
+#[cfg(feature = "decentralized")]
+pub async fn fetch_page_worker(
+    target_url: &str,
+    client: &Client,
+) -> Result<Option<Vec<u8>>, WorkerError> {
     match client.get(target_url).send().await {
         Ok(res) if res.status().is_success() => match res.bytes().await {
-            Ok(text) => Some(text.into()),
+            Ok(text) => Ok(Some(text.into())),
             Err(_) => {
                 log("- error fetching {}", &target_url);
-                None
+                Ok(None)
             }
         },
-        Ok(_) => None,
-        Err(_) => {
+        Ok(res) => {
+            log("- error worker status {}", res.status().as_str());
+            Err(WorkerError::Status(res.status()))
+        }
+        Err(e) => {
             log("- error parsing html bytes {}", &target_url);
-            None
+            Err(WorkerError::Request(e))
         }
     }
 }
@@ -2270,7 +2464,7 @@ pub async fn fetch_page_and_headers(target_url: &str, client: &Client) -> FetchP
         Ok(res) if res.status().is_success() => {
             let headers = res.headers().clone();
             let b = match res.bytes().await {
-                Ok(text) => Some(text),
+                Ok(text) => Some(text.into()),
                 Err(_) => {
                     log("- error fetching {}", &target_url);
                     None
@@ -3460,6 +3654,56 @@ pub fn clean_html_slim(html: &str) -> String {
     html.into()
 }
 
+/// Decode the named html entities `&amp;`, `&lt;`, `&gt;`, `&quot;`, `&apos;`, `&nbsp;` and the numeric character references. The text is scanned once so `&amp;lt;` stays `&lt;`.
+pub fn decode_html_entities(text: &str) -> std::borrow::Cow<'_, str> {
+    if !text.contains('&') {
+        return std::borrow::Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => match entity.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16)
+                        .ok()
+                        .and_then(char::from_u32),
+                    Some(dec) => dec.parse().ok().and_then(char::from_u32),
+                    _ => None,
+                },
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+
+    std::borrow::Cow::Owned(out)
+}
+
 /// Log to console if configuration verbose.
 pub fn log(message: &'static str, data: impl AsRef<str>) {
     if log_enabled!(Level::Info) {
@@ -3658,6 +3902,33 @@ pub fn prepare_url(u: &str) -> String {
     }
 }
 
+/// Parse a list of urls separated by new lines, ex: the contents of a file or stdin. Empty lines and lines starting with `#` are ignored.
+/// Urls without a scheme are prefixed with https and duplicates are removed while keeping the original order.
+pub fn parse_seed_list(input: &str) -> Vec<String> {
+    let mut seen = hashbrown::HashSet::new();
+    let mut seeds = Vec::new();
+
+    for line in input.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let url = if networking_capable(line) {
+            line.to_string()
+        } else {
+            prepare_url(line)
+        };
+
+        if seen.insert(url.clone()) {
+            seeds.push(url);
+        }
+    }
+
+    seeds
+}
+
 /// normalize the html markup to prevent Maliciousness.
 pub(crate) async fn normalize_html(html: &[u8]) -> Vec<u8> {
     use lol_html::{element, send::Settings};
@@ -3881,3 +4152,17 @@ pub fn emit_log_shutdown(link: &str) {
 pub fn emit_log_shutdown(link: &str) {
     log::info!("shutdown {}", &link);
 }
+
+#[test]
+fn test_parse_seed_list() {
+    let seeds = parse_seed_list(
+        "# docs\nhttps://choosealicense.com\n\n  example.com/a  \nhttps://choosealicense.com\n",
+    );
+    assert_eq!(
+        seeds,
+        vec![
+            "https://choosealicense.com".to_string(),
+            "https://example.com/a".to_string()
+        ]
+    );
+}
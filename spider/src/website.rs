@@ -9,6 +9,8 @@ use crate::features::chrome::OnceBrowser;
 use crate::features::chrome_common::RequestInterceptConfiguration;
 #[cfg(feature = "disk")]
 use crate::features::disk::DatabaseHandler;
+#[cfg(feature = "decentralized")]
+use crate::features::worker_pool::{WorkerClients, WORKER_POOL};
 use crate::packages::robotparser::parser::RobotFileParser;
 use crate::page::{Page, PageLinkBuildSettings};
 use crate::utils::abs::{convert_abs_url, parse_absolute_url};
@@ -155,30 +157,18 @@ lazy_static! {
 
 #[cfg(feature = "decentralized")]
 lazy_static! {
-    /// The global worker count.
-    static ref WORKERS: HashSet<String> = {
-        let mut set: HashSet<_> = HashSet::new();
-
-        for worker in std::env::var("SPIDER_WORKER_SCRAPER")
-            .unwrap_or_else(|_| "http://127.0.0.1:3031".to_string())
-            .split(",")
-        {
-            set.insert(worker.to_string());
-        }
-
-        for worker in std::env::var("SPIDER_WORKER")
-            .unwrap_or_else(|_| "http://127.0.0.1:3030".to_string())
-            .split(",")
-        {
-            set.insert(worker.to_string());
-        }
-
-        set
-    };
-    static ref SEM: Semaphore = {
-        let sem_limit = calc_limits(3);
-        Semaphore::const_new(sem_limit * WORKERS.len())
-    };
+    /// The shared secret to authenticate with the workers set with `SPIDER_WORKER_SECRET`.
+    static ref WORKER_SECRET: Option<reqwest::header::HeaderValue> = std::env::var("SPIDER_WORKER_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .and_then(|secret| {
+            reqwest::header::HeaderValue::from_str(&string_concat!("Bearer ", secret))
+                .ok()
+                .map(|mut value| {
+                    value.set_sensitive(true);
+                    value
+                })
+        });
 }
 
 // const INVALID_URL: &str = "The domain should be a valid URL, refer to <https://www.w3.org/TR/2011/WD-html5-20110525/urls.html#valid-url>.";
@@ -238,6 +228,17 @@ pub enum CronType {
     Scrape,
 }
 
+/// How a list of seed urls is handled when crawling with `crawl_seeds`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum::EnumString, strum::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeedMode {
+    #[default]
+    /// Only fetch the urls in the list without following links.
+    List,
+    /// Crawl the website starting from each url in the list.
+    Seed,
+}
+
 /// Represents a website to crawl and gather all links or page content.
 /// ```rust
 /// use spider::website::Website;
@@ -289,6 +290,11 @@ pub struct Website {
     shutdown: bool,
     /// The request client. Stored for re-use between runs.
     client: Option<Client>,
+    #[cfg(feature = "cookies")]
+    /// The cookie jar of the HTTP client shared with the login flow and the storage state.
+    cookie_jar: Option<Arc<crate::client::cookie::Jar>>,
+    /// The pagination state of the crawl.
+    paginator: Option<Arc<crate::features::pagination::Paginator>>,
     #[cfg(feature = "disk")]
     /// The disk handler to use.
     sqlite: DatabaseHandler,
@@ -1386,6 +1392,11 @@ impl Website {
         client: crate::client::ClientBuilder,
     ) -> crate::client::ClientBuilder {
         let client = client.cookie_store(true);
+
+        if let Some(ref cookie_store) = self.cookie_jar {
+            return client.cookie_provider(cookie_store.clone());
+        }
+
         if !self.configuration.cookie_str.is_empty() && self.domain_parsed.is_some() {
             match self.domain_parsed.clone() {
                 Some(p) => {
@@ -1431,8 +1442,23 @@ impl Website {
     }
 
     /// Configure http client for decentralization.
-    #[cfg(all(feature = "decentralized", not(feature = "cache_request")))]
+    #[cfg(feature = "decentralized")]
     pub fn configure_http_client(&self) -> Client {
+        self.configure_worker_http_client(None)
+    }
+
+    /// The HTTP clients for each worker of the pool using the website configuration.
+    #[cfg(feature = "decentralized")]
+    pub fn configure_worker_clients(&self) -> WorkerClients {
+        let mut website = Website::new(self.url.inner());
+        website.configuration = self.configuration.clone();
+
+        WorkerClients::new(move |worker| website.configure_worker_http_client(Some(worker)))
+    }
+
+    /// Configure http client proxied through the worker or all the workers of the pool.
+    #[cfg(all(feature = "decentralized", not(feature = "cache_request")))]
+    pub fn configure_worker_http_client(&self, worker: Option<&str>) -> Client {
         use reqwest::header::{HeaderMap, HeaderValue};
 
         let mut headers = HeaderMap::new();
@@ -1478,9 +1504,17 @@ impl Website {
             }
         }
 
-        for worker in WORKERS.iter() {
+        let workers = match worker {
+            Some(worker) => vec![worker.to_string()],
+            _ => WORKER_POOL.urls(),
+        };
+
+        for worker in workers.iter() {
             if let Ok(worker) = crate::client::Proxy::all(worker) {
-                client = client.proxy(worker);
+                client = client.proxy(match WORKER_SECRET.as_ref() {
+                    Some(secret) => worker.custom_http_auth(secret.clone()),
+                    _ => worker,
+                });
             }
         }
 
@@ -1496,9 +1530,9 @@ impl Website {
         }
     }
 
-    /// Configure http client for decentralization.
+    /// Configure http client proxied through the worker or all the workers of the pool.
     #[cfg(all(feature = "decentralized", feature = "cache_request"))]
-    pub fn configure_http_client(&mut self) -> Client {
+    pub fn configure_worker_http_client(&self, worker: Option<&str>) -> Client {
         use reqwest::header::HeaderMap;
         use reqwest::header::HeaderValue;
         use reqwest_middleware::ClientBuilder;
@@ -1546,9 +1580,17 @@ impl Website {
             }
         }
 
-        for worker in WORKERS.iter() {
+        let workers = match worker {
+            Some(worker) => vec![worker.to_string()],
+            _ => WORKER_POOL.urls(),
+        };
+
+        for worker in workers.iter() {
             if let Ok(worker) = crate::client::Proxy::all(worker) {
-                client = client.proxy(worker);
+                client = client.proxy(match WORKER_SECRET.as_ref() {
+                    Some(secret) => worker.custom_http_auth(secret.clone()),
+                    _ => worker,
+                });
             }
         }
 
@@ -1650,6 +1692,8 @@ impl Website {
     fn setup_base(&mut self) -> (Client, Option<(Arc<AtomicI8>, tokio::task::JoinHandle<()>)>) {
         self.determine_limits();
         self.setup_disk();
+        self.setup_cookie_jar();
+        self.setup_paginator();
         crate::utils::connect::init_background_runtime();
 
         let client = match self.client.take() {
@@ -1660,6 +1704,111 @@ impl Website {
         (client, self.configure_handler())
     }
 
+    /// Create the cookie jar of the HTTP client when the cookies are shared with the login flow or the storage state.
+    #[cfg(feature = "cookies")]
+    fn setup_cookie_jar(&mut self) {
+        #[cfg(feature = "chrome")]
+        let shared = self.configuration.login_flow.is_some();
+        #[cfg(not(feature = "chrome"))]
+        let shared = false;
+        #[cfg(feature = "storage_state")]
+        let shared = shared || self.configuration.storage_state.is_some();
+
+        if shared && self.cookie_jar.is_none() {
+            let cookie_store = crate::client::cookie::Jar::default();
+
+            if let Some(ref p) = self.domain_parsed {
+                if !self.configuration.cookie_str.is_empty() {
+                    cookie_store.add_cookie_str(&self.configuration.cookie_str, p);
+                }
+            }
+
+            let cookie_store = Arc::new(cookie_store);
+
+            #[cfg(feature = "storage_state")]
+            if let Some(ref storage_state) = self.configuration.storage_state {
+                storage_state.set_jar(cookie_store.clone());
+            }
+
+            self.cookie_jar = Some(cookie_store);
+        }
+    }
+
+    /// Create the cookie jar of the HTTP client. This does nothing without the `cookies` flag enabled.
+    #[cfg(not(feature = "cookies"))]
+    fn setup_cookie_jar(&mut self) {}
+
+    /// Reset the pagination state for the crawl.
+    fn setup_paginator(&mut self) {
+        self.paginator = self.configuration.pagination.as_ref().map(|pagination| {
+            Arc::new(crate::features::pagination::Paginator::new(
+                pagination.as_ref().clone(),
+            ))
+        });
+    }
+
+    /// Send the pagination states captured with chrome returning the links of the states.
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    async fn send_pagination_states(
+        states: Vec<Page>,
+        selectors: &RelativeSelectors,
+        domain_parsed: &Option<Box<Url>>,
+        full_resources: bool,
+        channel: &Option<(broadcast::Sender<Page>, Arc<broadcast::Receiver<Page>>)>,
+        channel_guard: &Option<ChannelGuard>,
+    ) -> HashSet<CaseInsensitiveString> {
+        let mut links = HashSet::new();
+
+        for mut state in states {
+            state.base = domain_parsed.as_deref().cloned();
+
+            links.extend(if full_resources {
+                state.links_full(selectors, domain_parsed).await
+            } else {
+                state.links(selectors, domain_parsed).await
+            });
+
+            channel_send_page(channel, state, channel_guard);
+        }
+
+        links
+    }
+
+    /// Mark the urls of the pagination states captured with chrome as visited.
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    async fn insert_pagination_states(&mut self) {
+        if let Some(states) = self.paginator.as_ref().map(|p| p.drain_states()) {
+            for state in states {
+                self.insert_link(state).await;
+            }
+        }
+    }
+
+    /// The login session of the crawl shared by the chrome pages.
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    fn login_session(&self) -> Option<Arc<crate::features::login_flow::LoginSession>> {
+        self.configuration.login_flow.as_ref().map(|flow| {
+            Arc::new(crate::features::login_flow::LoginSession::new(
+                flow.as_ref().clone(),
+                #[cfg(feature = "cookies")]
+                self.cookie_jar.clone(),
+            ))
+        })
+    }
+
+    /// Run the login flow on the page before the crawl.
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    async fn run_login_flow(
+        login: &Option<Arc<crate::features::login_flow::LoginSession>>,
+        page: &chromiumoxide::Page,
+    ) {
+        if let Some(login) = login {
+            if !login.login(page).await {
+                log::error!("login flow failed {}", login.flow().url);
+            }
+        }
+    }
+
     /// Setup config for crawl.
     async fn setup(&mut self) -> (Client, Option<(Arc<AtomicI8>, tokio::task::JoinHandle<()>)>) {
         let setup = self.setup_base();
@@ -1724,6 +1873,7 @@ impl Website {
             page_links_settings.subdomains = self.configuration.subdomains;
             page_links_settings.tld = self.configuration.tld;
             page_links_settings.normalize = self.configuration.normalize;
+            page_links_settings.metadata = self.configuration.metadata;
 
             let mut domain_parsed = self.domain_parsed.take();
 
@@ -1849,6 +1999,14 @@ impl Website {
                 }
             }
 
+            if let Some(ref paginator) = self.paginator {
+                links.extend(paginator.next_links(
+                    self.url.inner(),
+                    page.get_html_bytes_u8(),
+                    true,
+                ));
+            }
+
             channel_send_page(&self.channel, page, &self.channel_guard);
 
             links
@@ -1970,6 +2128,15 @@ impl Website {
                 }
             }
 
+            let pagination_states = match self.paginator {
+                Some(ref paginator) => {
+                    paginator
+                        .follow(chrome_page, self.url.inner(), page.get_html_bytes_u8())
+                        .await
+                }
+                _ => Default::default(),
+            };
+
             if let Some(h) = intercept_handle {
                 let abort_handle = h.abort_handle();
                 if let Err(elasped) =
@@ -2018,12 +2185,20 @@ impl Website {
                 page.page_links = Some(Box::new(Default::default()));
             }
 
-            let links = if !page.is_empty() {
+            let mut links = if !page.is_empty() {
                 page.links_ssg(&base, &client, &self.domain_parsed).await
             } else {
                 Default::default()
             };
 
+            if let Some(ref paginator) = self.paginator {
+                links.extend(paginator.template_links(
+                    self.url.inner(),
+                    page.get_html_bytes_u8(),
+                    true,
+                ));
+            }
+
             self.initial_status_code = page.status_code;
 
             if page.status_code == reqwest::StatusCode::FORBIDDEN {
@@ -2046,6 +2221,20 @@ impl Website {
 
             channel_send_page(&self.channel, page, &self.channel_guard);
 
+            self.insert_pagination_states().await;
+
+            links.extend(
+                Website::send_pagination_states(
+                    pagination_states,
+                    base,
+                    &self.domain_parsed,
+                    self.configuration.full_resources,
+                    &self.channel,
+                    &self.channel_guard,
+                )
+                .await,
+            );
+
             links
         } else {
             HashSet::new()
@@ -2217,26 +2406,20 @@ impl Website {
     #[cfg(all(not(feature = "glob"), feature = "decentralized"))]
     async fn crawl_establish(
         &mut self,
-        client: &Client,
+        clients: &WorkerClients,
         _: &(CompactString, smallvec::SmallVec<[CompactString; 2]>),
-        http_worker: bool,
     ) -> HashSet<CaseInsensitiveString> {
         // base_domain name passed here is for primary url determination and not subdomain.tld placement
         let links: HashSet<CaseInsensitiveString> = if self
             .is_allowed_default(&self.get_base_link())
             .eq(&ProcessLinkStatus::Allowed)
         {
-            let link = self.url.inner();
-
-            let mut page = Page::new(
-                &if http_worker && link.starts_with("https") {
-                    link.replacen("https", "http", 1)
-                } else {
-                    link.to_string()
-                },
-                &client,
-            )
-            .await;
+            let mut page = clients
+                .fetch(&WORKER_POOL, self.url.inner(), |client, target_url| async move {
+                    Page::new_worker(&target_url, &client).await
+                })
+                .await
+                .unwrap_or_default();
 
             if let Some(sid) = page.signature {
                 self.insert_signature(sid).await;
@@ -2285,9 +2468,8 @@ impl Website {
     #[cfg(all(feature = "glob", feature = "decentralized"))]
     async fn crawl_establish(
         &mut self,
-        client: &Client,
+        clients: &WorkerClients,
         _: &(CompactString, smallvec::SmallVec<[CompactString; 2]>),
-        http_worker: bool,
     ) -> HashSet<CaseInsensitiveString> {
         let mut links: HashSet<CaseInsensitiveString> = HashSet::new();
         let expanded = self.get_expanded_links(&self.url.inner().as_str());
@@ -2303,15 +2485,12 @@ impl Website {
                 continue;
             }
 
-            let mut page = Page::new(
-                &if http_worker && link.as_ref().starts_with("https") {
-                    link.inner().replacen("https", "http", 1).to_string()
-                } else {
-                    link.inner().to_string()
-                },
-                &client,
-            )
-            .await;
+            let mut page = clients
+                .fetch(&WORKER_POOL, link.inner(), |client, target_url| async move {
+                    Page::new_worker(&target_url, &client).await
+                })
+                .await
+                .unwrap_or_default();
 
             let u = page.get_url();
             let u = if u.is_empty() { link } else { u.into() };
@@ -2442,6 +2621,7 @@ impl Website {
                 page_links_settings.subdomains = self.configuration.subdomains;
                 page_links_settings.tld = self.configuration.tld;
                 page_links_settings.normalize = self.configuration.normalize;
+                page_links_settings.metadata = self.configuration.metadata;
 
                 let mut domain_parsed = self.domain_parsed.take();
 
@@ -2823,6 +3003,7 @@ impl Website {
             self.crawl_concurrent(&client, &handle).await;
             self.sitemap_crawl_chain(&client, &handle, false).await;
             self.set_crawl_status();
+            self.save_storage_state();
             if let Some(h) = join_handle {
                 h.abort()
             }
@@ -2841,6 +3022,7 @@ impl Website {
             };
             self.sitemap_crawl(&client, &handle, false).await;
             self.set_crawl_status();
+            self.save_storage_state();
             if let Some(h) = join_handle {
                 h.abort()
             }
@@ -2864,6 +3046,7 @@ impl Website {
             };
             self.sitemap_crawl_chrome(&client, &handle, false).await;
             self.set_crawl_status();
+            self.save_storage_state();
             if let Some(h) = join_handle {
                 h.abort()
             }
@@ -3011,6 +3194,7 @@ impl Website {
             };
             self.crawl_concurrent_smart(&client, &handle).await;
             self.set_crawl_status();
+            self.save_storage_state();
             if let Some(h) = join_handle {
                 h.abort()
             }
@@ -3036,6 +3220,7 @@ impl Website {
             self.crawl_concurrent_raw(&client, &handle).await;
             self.sitemap_crawl_chain(&client, &handle, false).await;
             self.set_crawl_status();
+            self.save_storage_state();
             if let Some(h) = join_handle {
                 h.abort()
             }
@@ -3043,6 +3228,91 @@ impl Website {
         }
     }
 
+    /// Crawl a list of urls re-using the configuration, HTTP client and subscription channel of the website.
+    /// Use `SeedMode::List` to only fetch the urls given or `SeedMode::Seed` to crawl starting from each url.
+    /// The amount of urls handled at once follows the concurrency limit. In seed mode each crawl shares the global queue.
+    pub async fn crawl_seeds<I, T>(&mut self, seeds: I, mode: SeedMode)
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.start();
+        self.status = CrawlStatus::Active;
+
+        let (client, handle) = self.setup_base();
+        let semaphore = Arc::new(Semaphore::const_new(
+            self.configuration
+                .concurrency_limit
+                .unwrap_or(*DEFAULT_PERMITS),
+        ));
+        let mut set: JoinSet<HashSet<CaseInsensitiveString>> = JoinSet::new();
+
+        for seed in seeds {
+            if self.shutdown {
+                break;
+            }
+
+            let seed = seed.as_ref().trim();
+
+            if seed.is_empty() {
+                continue;
+            }
+
+            let mut website = Website::new(seed);
+
+            if website.status.eq(&CrawlStatus::FirewallBlocked) {
+                continue;
+            }
+
+            website.configuration = self.configuration.clone();
+            website.on_link_find_callback = self.on_link_find_callback;
+            website.on_should_crawl_callback = self.on_should_crawl_callback;
+            website.crawl_id = self.crawl_id.clone();
+            website.channel = self.channel.clone();
+            website.channel_guard = self.channel_guard.clone();
+            website.client = Some(client.clone());
+
+            match mode {
+                SeedMode::List => {
+                    website.with_limit(1);
+                }
+                SeedMode::Seed => {
+                    website.with_shared_queue(true);
+                }
+            }
+
+            if let Ok(permit) = semaphore.clone().acquire_owned().await {
+                spawn_set("crawl_seed", &mut set, async move {
+                    website.crawl().await;
+                    drop(permit);
+                    website.get_links()
+                });
+            }
+
+            while let Some(result) = set.try_join_next() {
+                if let Ok(links) = result {
+                    for link in links {
+                        self.insert_link(link).await;
+                    }
+                }
+            }
+        }
+
+        while let Some(result) = set.join_next().await {
+            if let Ok(links) = result {
+                for link in links {
+                    self.insert_link(link).await;
+                }
+            }
+        }
+
+        self.status = CrawlStatus::Idle;
+        if let Some(h) = handle {
+            h.1.abort()
+        }
+        self.client.replace(client);
+    }
+
     /// Start to scrape/download website with async concurrency.
     pub async fn scrape(&mut self) {
         if !self.status.eq(&CrawlStatus::FirewallBlocked) {
@@ -3236,8 +3506,10 @@ impl Website {
                     self.configuration.subdomains,
                     self.configuration.tld,
                     self.configuration.normalize,
+                    self.configuration.metadata,
                 ),
                 self.domain_parsed.clone(),
+                self.paginator.clone(),
             ));
 
             let mut set: JoinSet<(HashSet<CaseInsensitiveString>, Option<u64>)> = JoinSet::new();
@@ -3383,6 +3655,10 @@ impl Website {
                                         page.page_links = links_pages.filter(|pages| !pages.is_empty()).map(Box::new);
                                     }
 
+                                    if let Some(ref paginator) = shared.9 {
+                                        links.extend(paginator.next_links(target_url, page.get_html_bytes_u8(), false));
+                                    }
+
                                     if let Some(cb) = on_should_crawl_callback {
                                         if !cb(&page) {
                                             page.blocked_crawl = true;
@@ -3452,6 +3728,12 @@ impl Website {
         use crate::features::chrome::attempt_navigation;
         self.start();
 
+        if let Some(browser_pool) = self.configuration.browser_pool.clone() {
+            return self
+                .crawl_concurrent_pooled(client, handle, browser_pool)
+                .await;
+        }
+
         match self.setup_browser().await {
             Some(mut b) => {
                 match attempt_navigation(
@@ -3465,8 +3747,11 @@ impl Website {
                 {
                     Ok(new_page) => {
                         let mut selectors = self.setup_selectors();
+                        let login = self.login_session();
                         self.status = CrawlStatus::Active;
 
+                        Website::run_login_flow(&login, &new_page).await;
+
                         if self.single_page() {
                             self.crawl_establish(&client, &mut selectors, false, &new_page)
                                 .await;
@@ -3474,9 +3759,6 @@ impl Website {
                             self.subscription_guard().await;
                             b.dispose();
                         } else {
-                            let semaphore: Arc<Semaphore> = self.setup_semaphore();
-                            let (mut interval, throttle) = self.setup_crawl();
-
                             let mut q = self.channel_queue.as_ref().map(|q| q.0.subscribe());
 
                             let base_links = self
@@ -3485,298 +3767,255 @@ impl Website {
 
                             drop(new_page);
 
-                            let mut links: HashSet<CaseInsensitiveString> =
-                                self.drain_extra_links().collect();
+                            let tabs = ChromeTabs::Browser(
+                                b.browser.0.clone(),
+                                self.url.inner().to_string(),
+                                b.browser.2.clone(),
+                            );
 
-                            links.extend(base_links);
+                            self.crawl_concurrent_tabs(
+                                handle,
+                                &mut q,
+                                base_links,
+                                self.chrome_page_task(client, selectors, login),
+                                tabs,
+                                Default::default(),
+                            )
+                            .await;
 
-                            self.configuration.configure_allowlist();
+                            b.dispose();
+                        }
+                    }
+                    Err(err) => {
+                        b.dispose();
+                        log::error!("{}", err)
+                    }
+                }
+            }
+            _ => log::error!("Chrome initialization failed."),
+        }
+    }
 
-                            let mut set: JoinSet<(HashSet<CaseInsensitiveString>, Option<u64>)> =
-                                JoinSet::new();
+    /// Start to crawl website concurrently with a pool of browsers. The pages in flight when a browser crashes are re-queued.
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    async fn crawl_concurrent_pooled(
+        &mut self,
+        client: &Client,
+        handle: &Option<Arc<AtomicI8>>,
+        browser_pool: crate::configuration::BrowserPoolConfig,
+    ) {
+        use crate::features::chrome_pool::{BrowserPool, Requeued};
 
-                            let shared = Arc::new((
-                                client.to_owned(),
-                                selectors,
-                                self.channel.clone(),
-                                self.configuration.external_domains_caseless.clone(),
-                                self.channel_guard.clone(),
-                                b.browser.0.clone(),
-                                self.configuration.clone(),
-                                self.url.inner().to_string(),
-                                b.browser.2.clone(),
-                                self.domain_parsed.clone(),
-                            ));
+        let pool = Arc::new(BrowserPool::new(
+            browser_pool,
+            &self.configuration,
+            self.get_url_parsed(),
+        ));
 
-                            let add_external = shared.3.len() > 0;
-                            let on_link_find_callback = self.on_link_find_callback;
-                            let on_should_crawl_callback = self.on_should_crawl_callback;
-                            let full_resources = self.configuration.full_resources;
-                            let return_page_links = self.configuration.return_page_links;
-                            let mut exceeded_budget = false;
-                            let concurrency = throttle.is_zero();
+        let tab = match pool.acquire().await {
+            Some(tab) => tab,
+            _ => {
+                log::error!("Chrome initialization failed.");
+                return;
+            }
+        };
 
-                            self.dequeue(&mut q, &mut links, &mut exceeded_budget).await;
+        let mut selectors = self.setup_selectors();
+        let login = self.login_session();
+        self.status = CrawlStatus::Active;
 
-                            if !concurrency && !links.is_empty() {
-                                tokio::time::sleep(*throttle).await;
-                            }
+        Website::run_login_flow(&login, &tab.page).await;
 
-                            let crawl_breaker = if self.configuration.crawl_timeout.is_some() {
-                                Some(Instant::now())
-                            } else {
-                                None
-                            };
+        if self.single_page() {
+            self.crawl_establish(client, &mut selectors, false, &tab.page)
+                .await;
+            pool.release(tab).await;
+            self.subscription_guard().await;
+        } else {
+            let mut q = self.channel_queue.as_ref().map(|q| q.0.subscribe());
 
-                            'outer: loop {
-                                let mut stream = tokio_stream::iter::<HashSet<CaseInsensitiveString>>(
-                                    links.drain().collect(),
-                                );
+            let base_links = self
+                .crawl_establish(client, &mut selectors, false, &tab.page)
+                .await;
 
-                                loop {
-                                    if !concurrency {
-                                        tokio::time::sleep(*throttle).await;
-                                    }
+            pool.release(tab).await;
 
-                                    let semaphore =
-                                        get_semaphore(&semaphore, !self.configuration.shared_queue)
-                                            .await;
+            let requeued = Requeued::new(pool.config().max_requeue);
 
-                                    tokio::select! {
-                                        biased;
-                                        Some(link) = stream.next(), if semaphore.available_permits() > 0 && !crawl_duration_expired(&self.configuration.crawl_timeout, &crawl_breaker)  => {
-                                            if !self
-                                                .handle_process(
-                                                    handle,
-                                                    &mut interval,
-                                                    async {
-                                                        emit_log_shutdown(&link.inner());
-                                                        let permits = set.len();
-                                                        set.shutdown().await;
-                                                        semaphore.add_permits(permits);
-                                                    },
-                                                )
-                                                .await
-                                            {
-                                                break 'outer;
-                                            }
-
-                                            let allowed = self.is_allowed(&link);
-
-                                            if allowed
-                                                .eq(&ProcessLinkStatus::BudgetExceeded)
-                                            {
-                                                exceeded_budget = true;
-                                                break;
-                                            }
-                                            if allowed.eq(&ProcessLinkStatus::Blocked) || !self.is_allowed_disk(&link).await {
-                                                continue;
-                                            }
-
-                                            emit_log(&link.inner());
-
-                                            self.insert_link(link.clone()).await;
-
-                                            if let Ok(permit) = semaphore.clone().acquire_owned().await {
-                                                let shared = shared.clone();
-
-                                                spawn_set("page_fetch", &mut set, async move {
-                                                    let results = match attempt_navigation("about:blank", &shared.5, &shared.6.request_timeout, &shared.8, &shared.6.viewport).await {
-                                                        Ok(new_page) => {
-                                                            crate::features::chrome::setup_chrome_events(&new_page, &shared.6).await;
-
-                                                            let intercept_handle = crate::features::chrome::setup_chrome_interception_base(
-                                                                &new_page,
-                                                                shared.6.chrome_intercept.enabled,
-                                                                &shared.6.auth_challenge_response,
-                                                                shared.6.chrome_intercept.block_visuals,
-                                                                &shared.7,
-                                                            )
-                                                            .await;
+            self.crawl_concurrent_tabs(
+                handle,
+                &mut q,
+                base_links,
+                self.chrome_page_task(client, selectors, login),
+                ChromeTabs::Pool(pool.clone()),
+                requeued,
+            )
+            .await;
+        }
 
-                                                            let link_result =
-                                                                match on_link_find_callback {
-                                                                    Some(cb) => cb(link, None),
-                                                                    _ => (link, None),
-                                                                };
+        pool.close().await;
+    }
 
-                                                            let target_url = link_result.0.as_ref();
+    /// The shared state of the chrome page tasks of the crawl.
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    fn chrome_page_task(
+        &self,
+        client: &Client,
+        selectors: RelativeSelectors,
+        login: Option<Arc<crate::features::login_flow::LoginSession>>,
+    ) -> ChromePageTask {
+        ChromePageTask {
+            client: client.to_owned(),
+            selectors,
+            channel: self.channel.clone(),
+            channel_guard: self.channel_guard.clone(),
+            configuration: self.configuration.clone(),
+            domain_parsed: self.domain_parsed.clone(),
+            login,
+            paginator: self.paginator.clone(),
+            on_link_find_callback: self.on_link_find_callback,
+            on_should_crawl_callback: self.on_should_crawl_callback,
+        }
+    }
 
-                                                            let mut page = Page::new(
-                                                                &target_url,
-                                                                &shared.0,
-                                                                &new_page,
-                                                                &shared.6.wait_for,
-                                                                &shared.6.screenshot,
-                                                                false,
-                                                                &shared.6.openai_config,
-                                                                &shared.6.execution_scripts,
-                                                                &shared.6.automation_scripts,
-                                                                &shared.6.viewport,
-                                                                &shared.6.request_timeout,
-                                                                &shared.6.track_events,
+    /// Crawl the links concurrently with the chrome tabs. The links returned by the tasks are re-queued up to the max times.
+    #[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+    async fn crawl_concurrent_tabs(
+        &mut self,
+        handle: &Option<Arc<AtomicI8>>,
+        q: &mut Option<broadcast::Receiver<String>>,
+        base_links: HashSet<CaseInsensitiveString>,
+        task: ChromePageTask,
+        tabs: ChromeTabs,
+        mut requeued: crate::features::chrome_pool::Requeued,
+    ) {
+        let semaphore: Arc<Semaphore> = self.setup_semaphore();
+        let (mut interval, throttle) = self.setup_crawl();
+        let mut links: HashSet<CaseInsensitiveString> = self.drain_extra_links().collect();
 
-                                                            )
-                                                            .await;
+        links.extend(base_links);
 
-                                                            let mut retry_count = shared.6.retry;
+        self.configuration.configure_allowlist();
 
-                                                            while page.should_retry && retry_count > 0 {
-                                                                retry_count -= 1;
-                                                                if let Some(timeout) = page.get_timeout() {
-                                                                    tokio::time::sleep(timeout).await;
-                                                                }
-                                                                if page.status_code == StatusCode::GATEWAY_TIMEOUT {
-                                                                    if let Err(elasped) = tokio::time::timeout(BACKOFF_MAX_DURATION, async {
-                                                                        let p = Page::new(
-                                                                            &target_url,
-                                                                            &shared.0,
-                                                                            &new_page,
-                                                                            &shared.6.wait_for,
-                                                                            &shared.6.screenshot,
-                                                                            false,
-                                                                            &shared.6.openai_config,
-                                                                            &shared.6.execution_scripts,
-                                                                            &shared.6.automation_scripts,
-                                                                            &shared.6.viewport,
-                                                                            &shared.6.request_timeout,
-                                                                            &shared.6.track_events,
+        let mut set: JoinSet<(
+            HashSet<CaseInsensitiveString>,
+            Option<u64>,
+            Option<CaseInsensitiveString>,
+        )> = JoinSet::new();
 
-                                                                        ).await;
-                                                                        page.clone_from(&p);
+        let shared = Arc::new((task, tabs));
 
-                                                                    }).await {
-                                                                        log::info!("{target_url} backoff gateway timeout exceeded {elasped}");
-                                                                    }
-                                                                } else {
-                                                                    page.clone_from(
-                                                                        &Page::new(
-                                                                            &target_url,
-                                                                            &shared.0,
-                                                                            &new_page,
-                                                                            &shared.6.wait_for,
-                                                                            &shared.6.screenshot,
-                                                                            false,
-                                                                            &shared.6.openai_config,
-                                                                            &shared.6.execution_scripts,
-                                                                            &shared.6.automation_scripts,
-                                                                            &shared.6.viewport,
-                                                                            &shared.6.request_timeout,
-                                                                            &shared.6.track_events,
-
-                                                                        )
-                                                                        .await,
-                                                                    );
-                                                                }
-                                                            }
+        let mut exceeded_budget = false;
+        let concurrency = throttle.is_zero();
 
-                                                            if let Some(h) = intercept_handle {
-                                                                let abort_handle = h.abort_handle();
-                                                                if let Err(elasped) = tokio::time::timeout(tokio::time::Duration::from_secs(10), h).await {
-                                                                    log::warn!("Handler timeout exceeded {elasped}");
-                                                                    abort_handle.abort();
-                                                                }
-                                                            }
+        self.dequeue(q, &mut links, &mut exceeded_budget).await;
 
-                                                            if add_external {
-                                                                page.set_external(shared.3.clone());
-                                                            }
-
-                                                            let prev_domain = page.base;
+        if !concurrency && !links.is_empty() {
+            tokio::time::sleep(*throttle).await;
+        }
 
-                                                            page.base = shared.9.as_deref().cloned();
+        let crawl_breaker = if self.configuration.crawl_timeout.is_some() {
+            Some(Instant::now())
+        } else {
+            None
+        };
 
-                                                            if return_page_links {
-                                                                page.page_links = Some(Default::default());
-                                                            }
+        'outer: loop {
+            let mut stream =
+                tokio_stream::iter::<HashSet<CaseInsensitiveString>>(links.drain().collect());
 
-                                                            let links = if full_resources {
-                                                                page.links_full(&shared.1, &shared.9).await
-                                                            } else {
-                                                                page.links(&shared.1, &shared.9).await
-                                                            };
+            loop {
+                if !concurrency {
+                    tokio::time::sleep(*throttle).await;
+                }
 
-                                                            page.base = prev_domain;
+                let semaphore = get_semaphore(&semaphore, !self.configuration.shared_queue).await;
 
-                                                            if shared.6.normalize {
-                                                                page.signature.replace(crate::utils::hash_html(&page.get_html_bytes_u8()).await);
-                                                            }
+                tokio::select! {
+                    biased;
+                    Some(link) = stream.next(), if semaphore.available_permits() > 0 && !crawl_duration_expired(&self.configuration.crawl_timeout, &crawl_breaker) => {
+                        if !self
+                            .handle_process(handle, &mut interval, async {
+                                emit_log_shutdown(link.inner());
+                                let permits = set.len();
+                                set.shutdown().await;
+                                semaphore.add_permits(permits);
+                            })
+                            .await
+                        {
+                            break 'outer;
+                        }
 
-                                                            if let Some(cb) = on_should_crawl_callback {
-                                                                if !cb(&page) {
-                                                                    page.blocked_crawl = true;
-                                                                    channel_send_page(&shared.2, page, &shared.4);
-                                                                    drop(permit);
-                                                                    return Default::default()
-                                                                }
-                                                            }
+                        let allowed = self.is_allowed(&link);
 
-                                                            let signature = page.signature;
+                        if allowed.eq(&ProcessLinkStatus::BudgetExceeded) {
+                            exceeded_budget = true;
+                            break;
+                        }
+                        if allowed.eq(&ProcessLinkStatus::Blocked) || !self.is_allowed_disk(&link).await {
+                            continue;
+                        }
 
-                                                            channel_send_page(
-                                                                &shared.2, page, &shared.4,
-                                                            );
+                        emit_log(link.inner());
 
-                                                            (links, signature)
-                                                        }
-                                                        _ => Default::default(),
-                                                    };
+                        self.insert_link(link.clone()).await;
 
+                        if let Ok(permit) = semaphore.clone().acquire_owned().await {
+                            let shared = shared.clone();
 
-                                                    drop(permit);
+                            spawn_set("page_fetch", &mut set, async move {
+                                let results = shared.1.crawl(&shared.0, link).await;
 
-                                                    results
-                                                });
-                                            }
+                                drop(permit);
 
-                                            self.dequeue(&mut q, &mut links, &mut exceeded_budget).await;
-                                        }
-                                        Some(result) = set.join_next(), if !set.is_empty() => {
-                                            if let Ok(res) = result {
-                                                match res.1 {
-                                                    Some(signature) => {
-                                                        if self.is_signature_allowed(signature).await {
-                                                            self.insert_signature(signature).await;
-                                                            self.links_visited.extend_links(&mut links, res.0);
-                                                        }
-                                                    }
-                                                    _ => {
-                                                        self.links_visited.extend_links(&mut links, res.0);
-                                                    }
-                                                }
-                                            } else{
-                                                break
-                                            }
-                                        }
-                                        else => break,
-                                    };
+                                results
+                            });
+                        }
 
-                                    if links.is_empty() && set.is_empty() || exceeded_budget {
-                                        if exceeded_budget {
-                                            while set.join_next().await.is_some() {}
-                                        }
-                                        break 'outer;
+                        self.dequeue(q, &mut links, &mut exceeded_budget).await;
+                    }
+                    Some(result) = set.join_next(), if !set.is_empty() => {
+                        if let Ok(res) = result {
+                            self.insert_pagination_states().await;
+                            if let Some(link) = res.2 {
+                                if requeued.requeue(&link) {
+                                    self.links_visited.remove(&link);
+                                    links.insert(link);
+                                }
+                            }
+                            match res.1 {
+                                Some(signature) => {
+                                    if self.is_signature_allowed(signature).await {
+                                        self.insert_signature(signature).await;
+                                        self.links_visited.extend_links(&mut links, res.0);
                                     }
                                 }
-
-                                self.subscription_guard().await;
-                                self.dequeue(&mut q, &mut links, &mut exceeded_budget).await;
-
-                                if links.is_empty() && set.is_empty() {
-                                    break;
+                                _ => {
+                                    self.links_visited.extend_links(&mut links, res.0);
                                 }
                             }
-
-                            b.dispose();
+                        } else {
+                            break
                         }
                     }
-                    Err(err) => {
-                        b.dispose();
-                        log::error!("{}", err)
+                    else => break,
+                };
+
+                if links.is_empty() && set.is_empty() || exceeded_budget {
+                    if exceeded_budget {
+                        while set.join_next().await.is_some() {}
                     }
+                    break 'outer;
                 }
             }
-            _ => log::error!("Chrome initialization failed."),
+
+            self.subscription_guard().await;
+            self.dequeue(q, &mut links, &mut exceeded_budget).await;
+
+            if links.is_empty() && set.is_empty() {
+                break;
+            }
         }
     }
 
@@ -3848,8 +4087,10 @@ impl Website {
                     self.configuration.subdomains,
                     self.configuration.tld,
                     self.configuration.normalize,
+                    self.configuration.metadata,
                 ),
                 self.domain_parsed.clone(),
+                website.paginator.clone(),
             ));
 
             let mut set: JoinSet<(HashSet<CaseInsensitiveString>, Option<u64>)> = JoinSet::new();
@@ -3997,6 +4238,10 @@ impl Website {
                                         page.page_links = links_pages.filter(|pages| !pages.is_empty()).map(Box::new);
                                     }
 
+                                    if let Some(ref paginator) = shared.9 {
+                                        links.extend(paginator.next_links(target_url, page.get_html_bytes_u8(), false));
+                                    }
+
                                     if let Some(cb) = on_should_crawl_callback {
                                         if !cb(&page) {
                                             page.blocked_crawl = true;
@@ -4501,25 +4746,19 @@ impl Website {
     /// Start to crawl website concurrently.
     #[cfg(feature = "decentralized")]
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    async fn crawl_concurrent(&mut self, client: &Client, handle: &Option<Arc<AtomicI8>>) {
+    async fn crawl_concurrent(&mut self, _client: &Client, handle: &Option<Arc<AtomicI8>>) {
         let mut q = self.channel_queue.as_ref().map(|q| q.0.subscribe());
 
         self.configuration.configure_allowlist();
+        WORKER_POOL.watch_env_file();
         let domain = self.url.inner().as_str();
         let mut interval = Box::pin(tokio::time::interval(Duration::from_millis(10)));
         let throttle = Box::pin(self.get_delay());
         let on_link_find_callback = self.on_link_find_callback;
-        // http worker verify
-        let http_worker = std::env::var("SPIDER_WORKER")
-            .unwrap_or_else(|_| "http:".to_string())
-            .starts_with("http:");
+        let clients = Arc::new(self.configure_worker_clients());
 
         let mut links: HashSet<CaseInsensitiveString> = self
-            .crawl_establish(
-                &client,
-                &mut (domain.into(), Default::default()),
-                http_worker,
-            )
+            .crawl_establish(&clients, &mut (domain.into(), Default::default()))
             .await;
 
         let mut set: JoinSet<HashSet<CaseInsensitiveString>> = JoinSet::new();
@@ -4560,24 +4799,24 @@ impl Website {
 
                         self.insert_link(link.clone()).await;
 
-                        if let Ok(permit) = SEM.acquire().await {
-                            let client = client.clone();
+                        if let Some(permit) = WORKER_POOL.acquire().await {
+                            let clients = clients.clone();
 
                             spawn_set("page_fetch", &mut set, async move {
                                 let link_results = match on_link_find_callback {
                                     Some(cb) => cb(link, None),
                                     _ => (link, None),
                                 };
-                                let link_results = link_results.0.as_ref();
-                                let page = Page::new_links_only(
-                                    &if http_worker && link_results.starts_with("https") {
-                                        link_results.replacen("https", "http", 1).to_string()
-                                    } else {
-                                        link_results.to_string()
-                                    },
-                                    &client,
-                                )
-                                .await;
+                                let page = clients
+                                    .fetch(
+                                        &WORKER_POOL,
+                                        link_results.0.as_ref(),
+                                        |client, target_url| async move {
+                                            Page::new_links_only_worker(&target_url, &client).await
+                                        },
+                                    )
+                                    .await
+                                    .unwrap_or_default();
 
                                 drop(permit);
 
@@ -5847,6 +6086,36 @@ impl Website {
         self
     }
 
+    #[cfg(feature = "chrome")]
+    /// Crawl with a pool of browsers with the max tabs each. The tabs are reset and reused, the browsers are relaunched after a crash, a max amount of pages or a memory threshold and the pages in flight during a crash are re-queued.
+    /// The pool is used by `crawl` and `scrape`. The `crawl_smart`, `crawl_chrome_send`, and sitemap crawls keep using a single browser.
+    pub fn with_browser_pool(
+        &mut self,
+        browser_pool: Option<crate::configuration::BrowserPoolConfig>,
+    ) -> &mut Self {
+        self.configuration.with_browser_pool(browser_pool);
+        self
+    }
+
+    /// Collect the console output, uncaught exceptions, browser logs and dialogs of each page into `Page::console_logs`. This does nothing without the `chrome` flag enabled.
+    pub fn with_console_logs(&mut self, console_logs: bool) -> &mut Self {
+        self.configuration.with_console_logs(console_logs);
+        self
+    }
+
+    /// Get the accessibility tree of each page into `Page::accessibility_tree`. This does nothing without the `chrome` flag enabled.
+    pub fn with_accessibility_tree(&mut self, accessibility_tree: bool) -> &mut Self {
+        self.configuration
+            .with_accessibility_tree(accessibility_tree);
+        self
+    }
+
+    /// Get the visible text of each page with the bounding boxes into `Page::visible_text`. The hidden elements are left out. This does nothing without the `chrome` flag enabled.
+    pub fn with_visible_text(&mut self, visible_text: bool) -> &mut Self {
+        self.configuration.with_visible_text(visible_text);
+        self
+    }
+
     /// Set HTTP headers for request using [reqwest::header::HeaderMap](https://docs.rs/reqwest/latest/reqwest/header/struct.HeaderMap.html).
     pub fn with_headers(&mut self, headers: Option<reqwest::header::HeaderMap>) -> &mut Self {
         self.configuration.with_headers(headers);
@@ -5911,6 +6180,46 @@ impl Website {
         self
     }
 
+    #[cfg(feature = "storage_state")]
+    /// Start the crawl from the cookies and web storage. The state is shared by the HTTP client and the chrome pages.
+    pub fn with_storage_state(
+        &mut self,
+        storage_state: Option<crate::features::storage_state::StorageState>,
+    ) -> &mut Self {
+        self.configuration.with_storage_state(storage_state);
+        self
+    }
+
+    #[cfg(feature = "storage_state")]
+    /// Load the cookies and web storage from the Playwright storage state file when it exists and save the state to it at the end of the crawl.
+    pub fn with_storage_state_file<P: Into<std::path::PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.configuration.with_storage_state_file(path);
+        self
+    }
+
+    #[cfg(feature = "storage_state")]
+    /// Get the cookies and web storage of the crawl including the HTTP cookies sent to the website.
+    pub fn get_storage_state(&self) -> Option<crate::features::storage_state::StorageState> {
+        self.configuration
+            .storage_state
+            .as_ref()
+            .map(|state| state.snapshot(self.get_url_parsed().as_deref()))
+    }
+
+    /// Save the storage state to the file it was loaded from.
+    #[cfg(feature = "storage_state")]
+    fn save_storage_state(&self) {
+        if let Some(ref state) = self.configuration.storage_state {
+            if let Err(e) = state.save(self.get_url_parsed().as_deref()) {
+                log::error!("failed to save the storage state: {e}");
+            }
+        }
+    }
+
+    /// Save the storage state to the file it was loaded from. This does nothing without the `storage_state` flag enabled.
+    #[cfg(not(feature = "storage_state"))]
+    fn save_storage_state(&self) {}
+
     /// Setup cron jobs to run. This does nothing without the `cron` flag enabled.
     pub fn with_cron(&mut self, cron_str: &str, cron_type: CronType) -> &mut Self {
         self.configuration.with_cron(cron_str, cron_type);
@@ -6060,6 +6369,18 @@ impl Website {
         self
     }
 
+    /// Print each page to a PDF stored on `Page::pdf_bytes` or saved to the output directory. This does nothing without the `chrome` flag enabled.
+    pub fn with_pdf(&mut self, pdf: Option<configuration::PdfConfig>) -> &mut Self {
+        self.configuration.with_pdf(pdf);
+        self
+    }
+
+    /// Capture a single file MHTML snapshot of each page stored on `Page::mhtml_bytes` or saved to the output directory. This does nothing without the `chrome` flag enabled.
+    pub fn with_mhtml(&mut self, mhtml: Option<configuration::MhtmlConfig>) -> &mut Self {
+        self.configuration.with_mhtml(mhtml);
+        self
+    }
+
     /// Set the chrome screenshot configuration. This does nothing without the `chrome` flag enabled.
     pub fn with_screenshot(
         &mut self,
@@ -6117,6 +6438,24 @@ impl Website {
         self
     }
 
+    /// Follow the next pages of the listings with a next link, a load more button or a url template. The pages are enqueued with HTTP and chrome clicks the next link or load more button capturing each state as a page.
+    pub fn with_pagination(
+        &mut self,
+        pagination: Option<crate::features::pagination::Pagination>,
+    ) -> &mut Self {
+        self.configuration.with_pagination(pagination);
+        self
+    }
+
+    /// Log in with the automation steps on the login url before the crawl. The cookies are shared with the chrome pages and the HTTP client and the login re-runs when a page redirects to the login url. This method does nothing if the `chrome` is not enabled.
+    pub fn with_login_flow(
+        &mut self,
+        login_flow: Option<crate::features::chrome_common::LoginFlow>,
+    ) -> &mut Self {
+        self.configuration.with_login_flow(login_flow);
+        self
+    }
+
     /// Block assets from loading from the network. Focus primarly on HTML documents.
     pub fn with_block_assets(&mut self, only_html: bool) -> &mut Self {
         self.configuration.with_block_assets(only_html);
@@ -6129,6 +6468,12 @@ impl Website {
         self
     }
 
+    /// Collect the embedded metadata of the pages while streaming: title, description, canonical, OpenGraph, Twitter cards, JSON-LD, microdata, RDFa and hreflang alternates. This does nothing without the flag `metadata` enabled.
+    pub fn with_metadata(&mut self, metadata: bool) -> &mut Self {
+        self.configuration.with_metadata(metadata);
+        self
+    }
+
     /// Set the configuration for the website directly.
     pub fn with_config(&mut self, config: Configuration) -> &mut Self {
         self.configuration = config.into();
@@ -6408,6 +6753,280 @@ fn channel_send_page(
     }
 }
 
+/// The shared state of the chrome page tasks of the crawl.
+#[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+struct ChromePageTask {
+    /// The request client.
+    client: Client,
+    /// The selectors to find the links.
+    selectors: RelativeSelectors,
+    /// The subscription channel of the pages.
+    channel: Option<(broadcast::Sender<Page>, Arc<broadcast::Receiver<Page>>)>,
+    /// The guard of the subscription channel.
+    channel_guard: Option<ChannelGuard>,
+    /// The crawl configuration.
+    configuration: Box<Configuration>,
+    /// The domain url parsed.
+    domain_parsed: Option<Box<Url>>,
+    /// The login session shared by the pages.
+    login: Option<Arc<crate::features::login_flow::LoginSession>>,
+    /// The pagination state of the crawl.
+    paginator: Option<Arc<crate::features::pagination::Paginator>>,
+    /// The callback when a link is found.
+    on_link_find_callback: Option<
+        fn(CaseInsensitiveString, Option<String>) -> (CaseInsensitiveString, Option<String>),
+    >,
+    /// The callback to use if a page should be ignored.
+    on_should_crawl_callback: Option<fn(&Page) -> bool>,
+}
+
+#[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+impl ChromePageTask {
+    /// Load the page with the tab.
+    async fn load(&self, target_url: &str, tab: &chromiumoxide::Page) -> Page {
+        Page::new(
+            target_url,
+            &self.client,
+            tab,
+            &self.configuration.wait_for,
+            &self.configuration.screenshot,
+            false,
+            &self.configuration.openai_config,
+            &self.configuration.execution_scripts,
+            &self.configuration.automation_scripts,
+            &self.configuration.viewport,
+            &self.configuration.request_timeout,
+            &self.configuration.track_events,
+        )
+        .await
+    }
+
+    /// Fetch the link with the tab retrying the failed loads and logging in again after a login redirect. Set `apply_login` when the tab does not share the context of the login. The pagination states are captured before the tab is left. Returns the page, the target url and the pagination states.
+    async fn fetch(
+        &self,
+        link: CaseInsensitiveString,
+        tab: &chromiumoxide::Page,
+        apply_login: bool,
+    ) -> (Page, CaseInsensitiveString, Vec<Page>) {
+        let target_url = match self.on_link_find_callback {
+            Some(cb) => cb(link, None).0,
+            _ => link,
+        };
+
+        let login_generation = match self.login {
+            Some(ref login) => {
+                if apply_login {
+                    login.apply(tab).await;
+                }
+                login.generation()
+            }
+            _ => 0,
+        };
+
+        let mut page = self.load(target_url.inner(), tab).await;
+        let mut retry_count = self.configuration.retry;
+
+        while page.should_retry && retry_count > 0 {
+            retry_count -= 1;
+            if let Some(timeout) = page.get_timeout() {
+                tokio::time::sleep(timeout).await;
+            }
+            let retry = self.load(target_url.inner(), tab);
+            if page.status_code == StatusCode::GATEWAY_TIMEOUT {
+                match tokio::time::timeout(BACKOFF_MAX_DURATION, retry).await {
+                    Ok(p) => page.clone_from(&p),
+                    Err(elasped) => {
+                        log::info!("{target_url} backoff gateway timeout exceeded {elasped}");
+                    }
+                }
+            } else {
+                page.clone_from(&retry.await);
+            }
+        }
+
+        if let Some(ref login) = self.login {
+            if login.is_login_redirect(&page) && login.relogin(tab, login_generation).await {
+                // the login may have run on a tab of another browser.
+                if apply_login {
+                    login.apply(tab).await;
+                }
+                page.clone_from(&self.load(target_url.inner(), tab).await);
+            }
+        }
+
+        let pagination_states = match self.paginator {
+            Some(ref paginator) => {
+                paginator
+                    .follow(tab, target_url.inner(), page.get_html_bytes_u8())
+                    .await
+            }
+            _ => Default::default(),
+        };
+
+        (page, target_url, pagination_states)
+    }
+
+    /// Send the page and the pagination states to the subscribers. Returns the links found and the signature of the page.
+    async fn finish(
+        &self,
+        mut page: Page,
+        target_url: &str,
+        pagination_states: Vec<Page>,
+    ) -> (HashSet<CaseInsensitiveString>, Option<u64>) {
+        let full_resources = self.configuration.full_resources;
+
+        if !self.configuration.external_domains_caseless.is_empty() {
+            page.set_external(self.configuration.external_domains_caseless.clone());
+        }
+
+        let prev_domain = page.base;
+
+        page.base = self.domain_parsed.as_deref().cloned();
+
+        if self.configuration.return_page_links {
+            page.page_links = Some(Default::default());
+        }
+
+        let mut links = if full_resources {
+            page.links_full(&self.selectors, &self.domain_parsed).await
+        } else {
+            page.links(&self.selectors, &self.domain_parsed).await
+        };
+
+        if let Some(ref paginator) = self.paginator {
+            links.extend(paginator.template_links(target_url, page.get_html_bytes_u8(), false));
+        }
+
+        page.base = prev_domain;
+
+        if self.configuration.normalize {
+            page.signature
+                .replace(crate::utils::hash_html(page.get_html_bytes_u8()).await);
+        }
+
+        if let Some(cb) = self.on_should_crawl_callback {
+            if !cb(&page) {
+                page.blocked_crawl = true;
+                channel_send_page(&self.channel, page, &self.channel_guard);
+                return Default::default();
+            }
+        }
+
+        let signature = page.signature;
+
+        channel_send_page(&self.channel, page, &self.channel_guard);
+
+        links.extend(
+            Website::send_pagination_states(
+                pagination_states,
+                &self.selectors,
+                &self.domain_parsed,
+                full_resources,
+                &self.channel,
+                &self.channel_guard,
+            )
+            .await,
+        );
+
+        (links, signature)
+    }
+}
+
+/// The chrome tabs used by the concurrent crawl.
+#[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+enum ChromeTabs {
+    /// A new tab of the browser for each page with the host name and the browser context.
+    Browser(
+        Arc<chromiumoxide::Browser>,
+        String,
+        Option<chromiumoxide::cdp::browser_protocol::browser::BrowserContextId>,
+    ),
+    /// The tabs of the pool of browsers.
+    Pool(Arc<crate::features::chrome_pool::BrowserPool>),
+}
+
+#[cfg(all(not(feature = "decentralized"), feature = "chrome"))]
+impl ChromeTabs {
+    /// Crawl the link with a tab. Returns the links found, the signature of the page, and the link to re-queue when a tab of the pool is not available or the browser crashed.
+    async fn crawl(
+        &self,
+        task: &ChromePageTask,
+        link: CaseInsensitiveString,
+    ) -> (
+        HashSet<CaseInsensitiveString>,
+        Option<u64>,
+        Option<CaseInsensitiveString>,
+    ) {
+        match self {
+            ChromeTabs::Browser(browser, host_name, context_id) => {
+                let new_page = match crate::features::chrome::attempt_navigation(
+                    "about:blank",
+                    browser,
+                    &task.configuration.request_timeout,
+                    context_id,
+                    &task.configuration.viewport,
+                )
+                .await
+                {
+                    Ok(new_page) => new_page,
+                    _ => return Default::default(),
+                };
+
+                crate::features::chrome::setup_chrome_events(&new_page, &task.configuration).await;
+
+                let intercept_handle = crate::features::chrome::setup_chrome_interception_base(
+                    &new_page,
+                    task.configuration.chrome_intercept.enabled,
+                    &task.configuration.auth_challenge_response,
+                    task.configuration.chrome_intercept.block_visuals,
+                    host_name,
+                )
+                .await;
+
+                let (page, target_url, pagination_states) =
+                    task.fetch(link, &new_page, false).await;
+
+                if let Some(h) = intercept_handle {
+                    let abort_handle = h.abort_handle();
+                    if let Err(elasped) =
+                        tokio::time::timeout(tokio::time::Duration::from_secs(10), h).await
+                    {
+                        log::warn!("Handler timeout exceeded {elasped}");
+                        abort_handle.abort();
+                    }
+                }
+
+                let (links, signature) = task
+                    .finish(page, target_url.inner(), pagination_states)
+                    .await;
+
+                (links, signature, None)
+            }
+            ChromeTabs::Pool(pool) => {
+                let tab = match pool.acquire().await {
+                    Some(tab) => tab,
+                    _ => return (Default::default(), None, Some(link)),
+                };
+
+                // the browsers of the pool do not share a context.
+                let (page, target_url, pagination_states) =
+                    task.fetch(link.clone(), &tab.page, true).await;
+
+                // the browser crashed while loading the page.
+                if pool.release(tab).await {
+                    return (Default::default(), None, Some(link));
+                }
+
+                let (links, signature) = task
+                    .finish(page, target_url.inner(), pagination_states)
+                    .await;
+
+                (links, signature, None)
+            }
+        }
+    }
+}
+
 /// Guard a channel from closing until all concurrent operations are done.
 #[derive(Debug, Clone)]
 pub struct ChannelGuard(Arc<(AtomicBool, AtomicUsize, AtomicUsize)>);
@@ -6933,3 +7552,19 @@ async fn test_cache() {
         cached_duration
     );
 }
+
+#[cfg(not(feature = "decentralized"))]
+#[tokio::test]
+async fn crawl_seeds_list() {
+    let mut website: Website = Website::new("https://choosealicense.com");
+    website
+        .crawl_seeds(
+            [
+                "https://choosealicense.com",
+                "https://choosealicense.com/licenses/",
+            ],
+            SeedMode::List,
+        )
+        .await;
+    assert_eq!(website.size(), 2, "{:?}", website.links_visited);
+}
@@ -662,6 +662,21 @@ impl Page {
         Ok(self)
     }
 
+    /// Dispatches a `keydown` and `keyup` event of the key to the focused
+    /// element. The `key` is the name of the key in the
+    /// [`keys::USKEYBOARD_LAYOUT`](crate::keys::USKEYBOARD_LAYOUT) like
+    /// `Enter`, `ArrowDown` or `a`.
+    pub async fn press_key(&self, key: impl AsRef<str>) -> Result<&Self> {
+        self.inner.press_key(key).await?;
+        Ok(self)
+    }
+
+    /// Types the input into the focused element as a series of keystrokes.
+    pub async fn type_str(&self, input: impl AsRef<str>) -> Result<&Self> {
+        self.inner.type_str(input).await?;
+        Ok(self)
+    }
+
     /// Take a screenshot of the current page
     pub async fn screenshot(&self, params: impl Into<ScreenshotParams>) -> Result<Vec<u8>> {
         self.inner.screenshot(params).await
@@ -23,10 +23,18 @@ use spider::hashbrown::HashMap;
 use spider::string_concat::string_concat;
 use spider::string_concat::string_concat_impl;
 use spider::tokio;
-use spider::utils::log;
-use spider::website::Website;
+use spider::utils::{log, parse_seed_list};
+use spider::website::{SeedMode, Website};
 use std::path::{Path, PathBuf};
 
+/// Crawl the website or the list of urls passed in.
+async fn crawl(website: &mut Website, seeds: Option<(Vec<String>, SeedMode)>) {
+    match seeds {
+        Some((seeds, mode)) => website.crawl_seeds(seeds, mode).await,
+        _ => website.crawl().await,
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
@@ -40,10 +48,51 @@ async fn main() {
         env_logger::init_from_env(env);
     }
 
-    let url = if cli.url.starts_with("http") {
-        cli.url
+    let mut seeds = match cli.url_file {
+        Some(ref path) => {
+            let input = if path == "-" {
+                std::io::read_to_string(std::io::stdin())
+            } else {
+                std::fs::read_to_string(path)
+            };
+
+            match input {
+                Ok(input) => parse_seed_list(&input),
+                Err(e) => {
+                    eprintln!("Unable to read the url file {path}: {e}");
+                    return;
+                }
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    let url = match cli.url {
+        Some(url) => {
+            let url = if url.starts_with("http") {
+                url
+            } else {
+                string_concat!("https://", url)
+            };
+            if !seeds.contains(&url) {
+                seeds.insert(0, url.clone());
+            }
+            url
+        }
+        _ => seeds.first().cloned().unwrap_or_default(),
+    };
+
+    let seeds = if cli.url_file.is_some() || cli.list_mode {
+        Some((
+            seeds,
+            if cli.list_mode {
+                SeedMode::List
+            } else {
+                SeedMode::Seed
+            },
+        ))
     } else {
-        string_concat!("https://", cli.url)
+        None
     };
 
     let mut website = Website::new(&url);
@@ -102,7 +151,7 @@ async fn main() {
                     let mut stdout = tokio::io::stdout();
 
                     tokio::spawn(async move {
-                        website.crawl().await;
+                        crawl(&mut website, seeds).await;
                     });
 
                     if output_links {
@@ -125,7 +174,7 @@ async fn main() {
                     let download_path = PathBuf::from(tmp_path);
 
                     tokio::spawn(async move {
-                        website.crawl().await;
+                        crawl(&mut website, seeds).await;
                     });
 
                     while let Ok(res) = rx2.recv().await {
@@ -183,7 +232,7 @@ async fn main() {
                     }
 
                     tokio::spawn(async move {
-                        website.crawl().await;
+                        crawl(&mut website, seeds).await;
                     });
 
                     while let Ok(res) = rx2.recv().await {
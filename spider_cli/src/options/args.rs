@@ -10,8 +10,14 @@ pub struct Cli {
     #[clap(subcommand)]
     pub command: Option<Commands>,
     /// The website URL to crawl.
-    #[clap(short, long)]
-    pub url: String,
+    #[clap(short, long, required_unless_present = "url_file")]
+    pub url: Option<String>,
+    /// Path to a file of new line separated URLs to crawl. Use "-" to read from stdin.
+    #[clap(short = 'U', long)]
+    pub url_file: Option<String>,
+    /// Only fetch the URLs passed in without following links.
+    #[clap(short = 'L', long)]
+    pub list_mode: bool,
     /// Respect robots.txt file
     #[clap(short, long)]
     pub respect_robots_txt: bool,
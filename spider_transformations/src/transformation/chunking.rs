@@ -219,11 +219,14 @@ fn chunk_by_lines(text: &str, lines_per_chunk: usize) -> Vec<String> {
 fn chunk_by_character_length(text: &str, char_length: usize) -> Vec<String> {
     let mut result = Vec::new();
     let mut current_chunk = String::new();
+    let mut current_length = 0;
     for c in text.chars() {
         current_chunk.push(c);
-        if current_chunk.len() >= char_length {
+        current_length += 1;
+        if current_length >= char_length {
             result.push(current_chunk.clone());
             current_chunk.clear();
+            current_length = 0;
         }
     }
     if !current_chunk.is_empty() {
@@ -231,3 +234,268 @@ fn chunk_by_character_length(text: &str, char_length: usize) -> Vec<String> {
     }
     result
 }
+
+/// Count the tokens of the text for chunking.
+pub trait Tokenizer {
+    /// The amount of tokens in the text.
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// Tokenizer counting the whitespace separated words.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WordTokenizer;
+
+impl Tokenizer for WordTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+}
+
+impl<F: Fn(&str) -> usize> Tokenizer for F {
+    fn count_tokens(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+#[cfg(feature = "tiktoken")]
+impl Tokenizer for tiktoken_rs::CoreBPE {
+    fn count_tokens(&self, text: &str) -> usize {
+        self.encode_ordinary(text).len()
+    }
+}
+
+/// A chunk of the text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+    /// The text of the chunk.
+    pub text: String,
+    /// The start byte offset in the source text.
+    pub start: usize,
+    /// The end byte offset in the source text.
+    pub end: usize,
+    /// The url of the source page.
+    pub url: Option<String>,
+    /// The markdown heading path of the chunk from the top level heading.
+    pub headings: Vec<String>,
+}
+
+/// The options for the token chunkers.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkOptions {
+    /// The max tokens per chunk.
+    pub max_tokens: usize,
+    /// The tokens repeated from the end of the previous chunk.
+    pub overlap: usize,
+    /// The url of the source page.
+    pub url: Option<String>,
+}
+
+impl ChunkOptions {
+    /// New chunk options with the max tokens per chunk.
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            max_tokens,
+            ..Default::default()
+        }
+    }
+
+    /// Set the tokens repeated from the end of the previous chunk.
+    pub fn with_overlap(mut self, overlap: usize) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Set the url of the source page.
+    pub fn with_url(mut self, url: Option<String>) -> Self {
+        self.url = url;
+        self
+    }
+}
+
+/// A word of the text with the byte offsets and token count.
+struct Segment {
+    /// The start of the word.
+    start: usize,
+    /// The end of the word.
+    end: usize,
+    /// The tokens of the word with the leading whitespace.
+    tokens: usize,
+}
+
+/// Split the range of the text into words counting the tokens.
+fn segments<T: Tokenizer + ?Sized>(
+    text: &str,
+    range: std::ops::Range<usize>,
+    tokenizer: &T,
+) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut prev_end = range.start;
+    let mut word_start = None;
+
+    for (i, c) in text[range.clone()].char_indices() {
+        let i = range.start + i;
+
+        if c.is_whitespace() {
+            if let Some(start) = word_start.take() {
+                segments.push(Segment {
+                    start,
+                    end: i,
+                    tokens: tokenizer.count_tokens(&text[prev_end..i]),
+                });
+                prev_end = i;
+            }
+        } else if word_start.is_none() {
+            word_start = Some(i);
+        }
+    }
+
+    if let Some(start) = word_start {
+        segments.push(Segment {
+            start,
+            end: range.end,
+            tokens: tokenizer.count_tokens(&text[prev_end..range.end]),
+        });
+    }
+
+    segments
+}
+
+/// Chunk the range of the text by tokens.
+fn chunk_range_by_tokens<T: Tokenizer + ?Sized>(
+    text: &str,
+    range: std::ops::Range<usize>,
+    tokenizer: &T,
+    options: &ChunkOptions,
+    headings: &[String],
+) -> Vec<Chunk> {
+    let segments = segments(text, range, tokenizer);
+    let max_tokens = options.max_tokens.max(1);
+    let overlap = options.overlap.min(max_tokens - 1);
+    let mut chunks = Vec::new();
+    let mut first = 0;
+
+    while first < segments.len() {
+        let mut last = first;
+        let mut tokens = segments[first].tokens;
+
+        while last + 1 < segments.len() && tokens + segments[last + 1].tokens <= max_tokens {
+            last += 1;
+            tokens += segments[last].tokens;
+        }
+
+        let (start, end) = (segments[first].start, segments[last].end);
+
+        chunks.push(Chunk {
+            text: text[start..end].to_string(),
+            start,
+            end,
+            url: options.url.clone(),
+            headings: headings.to_vec(),
+        });
+
+        if last + 1 >= segments.len() {
+            break;
+        }
+
+        let mut next = last + 1;
+        let mut overlap_tokens = 0;
+
+        while next > first + 1 && overlap_tokens + segments[next - 1].tokens <= overlap {
+            next -= 1;
+            overlap_tokens += segments[next].tokens;
+        }
+
+        first = next;
+    }
+
+    chunks
+}
+
+/// Chunk the text by the token count of the tokenizer. Chunks split between words and repeat the `overlap` tokens from the end of the previous chunk.
+pub fn chunk_by_tokens<T: Tokenizer + ?Sized>(
+    text: &str,
+    tokenizer: &T,
+    options: &ChunkOptions,
+) -> Vec<Chunk> {
+    chunk_range_by_tokens(text, 0..text.len(), tokenizer, options, &[])
+}
+
+/// Get the level and title of the markdown ATX heading line.
+fn markdown_heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_start_matches(' ');
+    let level = line.bytes().take_while(|b| *b == b'#').count();
+
+    if (1..=6).contains(&level) {
+        let rest = &line[level..];
+
+        if rest.is_empty() || rest.starts_with([' ', '\t']) {
+            return Some((level, rest.trim().trim_end_matches('#').trim_end()));
+        }
+    }
+
+    None
+}
+
+/// Chunk the markdown by the headings keeping the heading path with each chunk. Sections above the `max_tokens` are split by tokens with the `overlap`.
+pub fn chunk_markdown<T: Tokenizer + ?Sized>(
+    text: &str,
+    tokenizer: &T,
+    options: &ChunkOptions,
+) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut section_headings: Vec<String> = Vec::new();
+    let mut section_start = 0;
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                continue;
+            }
+            _ => {
+                if trimmed.starts_with("```") {
+                    fence = Some("```");
+                    continue;
+                } else if trimmed.starts_with("~~~") {
+                    fence = Some("~~~");
+                    continue;
+                }
+            }
+        }
+
+        if let Some((level, title)) = markdown_heading(line.trim_end()) {
+            chunks.extend(chunk_range_by_tokens(
+                text,
+                section_start..line_start,
+                tokenizer,
+                options,
+                &section_headings,
+            ));
+
+            headings.retain(|(l, _)| *l < level);
+            headings.push((level, title.to_string()));
+            section_headings = headings.iter().map(|(_, h)| h.clone()).collect();
+            section_start = line_start;
+        }
+    }
+
+    chunks.extend(chunk_range_by_tokens(
+        text,
+        section_start..text.len(),
+        tokenizer,
+        options,
+        &section_headings,
+    ));
+
+    chunks
+}
@@ -1,3 +1,7 @@
+use super::content_info::{content_info, ContentFilter, ContentInfo};
+use super::main_content::{extract_main_content, MainContentConfig};
+use super::markdown::{rewrite_markdown, MarkdownOptions};
+use super::text_extract::{extract_text_with_links, CitedText};
 use crate::html2xml::convert_html_to_xml;
 use aho_corasick::AhoCorasick;
 use html2md;
@@ -99,8 +103,12 @@ pub struct TransformConfig {
     pub clean_html: bool,
     /// Filter svgs.
     pub filter_svg: bool,
-    /// Main content for the page. Exclude the nav, footer, and etc.
+    /// Main content for the page. Exclude the nav, footer, and etc and extract the main content with the text and link density scoring.
     pub main_content: bool,
+    /// The markdown rendering options for the `Markdown` and `CommonMark` formats. The default html2md output is used when not set.
+    pub markdown_options: Option<MarkdownOptions>,
+    /// Add the `[1]` reference markers after the links and the footnotes of the absolute urls to the `Text` and `Html2Text` formats.
+    pub citations: bool,
 }
 
 /// Select elements to show or hide using a CSS selector.
@@ -245,6 +253,15 @@ pub fn transform_markdown(html: &str, commonmark: bool) -> String {
     html2md::rewrite_html_custom_with_url(html, &None, commonmark, &None)
 }
 
+/// transform the content to markdown shortcut with the rendering options
+pub fn transform_markdown_with_options(
+    html: &str,
+    commonmark: bool,
+    options: &MarkdownOptions,
+) -> String {
+    rewrite_markdown(html, commonmark, &None, &None, options)
+}
+
 /// transform the content to markdown shortcut send
 pub async fn transform_markdown_send(html: &str, commonmark: bool) -> String {
     html2md::rewrite_html_custom_with_url_streaming(html, &None, commonmark, &None).await
@@ -271,6 +288,13 @@ fn get_html(res: &Page, encoding: &Option<String>) -> String {
     }
 }
 
+/// The selector configuration has a root selector.
+fn has_root_selector(selector_config: &Option<SelectorConfiguration>) -> bool {
+    selector_config
+        .as_ref()
+        .is_some_and(|config| config.root_selector.is_some())
+}
+
 /// get the html with the root selector
 fn get_html_with_selector(
     res: &Page,
@@ -316,21 +340,48 @@ fn get_html_with_selector(
     html
 }
 
-/// Transform format the content.
-pub fn transform_content(
+/// Extract the text of the pdf, docx, or xlsx for the text and markdown formats.
+#[cfg(feature = "documents")]
+fn transform_document(res: &Page, c: &TransformConfig) -> Option<String> {
+    use super::documents::{detect_document_type, extract_document};
+
+    let markdown = match c.return_format {
+        ReturnFormat::Text | ReturnFormat::Html2Text => false,
+        ReturnFormat::Markdown | ReturnFormat::CommonMark => true,
+        _ => return None,
+    };
+
+    let bytes = res.get_html_bytes_u8();
+    let content_type = res
+        .headers
+        .as_ref()
+        .and_then(|headers| headers.get("content-type"))
+        .and_then(|content_type| content_type.to_str().ok());
+    let document = extract_document(bytes, detect_document_type(bytes, content_type)?)?;
+
+    Some(if markdown {
+        document.to_markdown()
+    } else {
+        document.to_text()
+    })
+}
+
+/// Extract the text of the pdf, docx, or xlsx for the text and markdown formats.
+#[cfg(not(feature = "documents"))]
+fn transform_document(_res: &Page, _c: &TransformConfig) -> Option<String> {
+    None
+}
+
+/// Get the html after the selectors, the ignored elements, the main content, readability and the cleaning.
+fn prepare_html(
     res: &Page,
     c: &TransformConfig,
+    main_content_config: &Option<MainContentConfig>,
     encoding: &Option<String>,
     selector_config: &Option<SelectorConfiguration>,
     ignore_tags: &Option<Vec<String>>,
 ) -> String {
     let base_html = get_html_with_selector(res, encoding, selector_config);
-
-    // prevent transforming binary files or re-encoding it
-    if is_binary_file(res.get_html_bytes_u8()) {
-        return base_html;
-    }
-
     let url_parsed = res.get_url_parsed_ref();
 
     let base_html = {
@@ -347,6 +398,15 @@ pub fn transform_content(
         }
     };
 
+    let base_html = if c.main_content && !has_root_selector(selector_config) {
+        match main_content_config {
+            Some(config) => extract_main_content(&base_html, url_parsed.as_ref(), config).html,
+            _ => extract_main_content(&base_html, url_parsed.as_ref(), &Default::default()).html,
+        }
+    } else {
+        base_html
+    };
+
     // process readability
     let base_html = if c.readability {
         match llm_readability::extractor::extract(
@@ -363,12 +423,15 @@ pub fn transform_content(
         base_html
     };
 
-    let base_html = if c.clean_html {
+    if c.clean_html {
         clean_html(&base_html)
     } else {
         base_html
-    };
+    }
+}
 
+/// The set of elements to ignore for the converters.
+fn ignore_tag_set(ignore_tags: &Option<Vec<String>>) -> Option<std::collections::HashSet<String>> {
     let mut tag_factory = None;
 
     if let Some(ignore) = ignore_tags {
@@ -379,14 +442,69 @@ pub fn transform_content(
         tag_factory.replace(tag_factor);
     }
 
+    tag_factory
+}
+
+/// Transform format the content.
+pub fn transform_content(
+    res: &Page,
+    c: &TransformConfig,
+    encoding: &Option<String>,
+    selector_config: &Option<SelectorConfiguration>,
+    ignore_tags: &Option<Vec<String>>,
+) -> String {
+    transform_content_with_main_content(res, c, &None, encoding, selector_config, ignore_tags)
+}
+
+/// Transform format the content with the per domain selectors, min confidence and readability fallback of the `main_content` extraction. The defaults are used when not set.
+pub fn transform_content_with_main_content(
+    res: &Page,
+    c: &TransformConfig,
+    main_content_config: &Option<MainContentConfig>,
+    encoding: &Option<String>,
+    selector_config: &Option<SelectorConfiguration>,
+    ignore_tags: &Option<Vec<String>>,
+) -> String {
+    if let Some(content) = transform_document(res, c) {
+        return content;
+    }
+
+    // prevent transforming binary files or re-encoding it
+    if is_binary_file(res.get_html_bytes_u8()) {
+        return get_html_with_selector(res, encoding, selector_config);
+    }
+
+    let url_parsed = res.get_url_parsed_ref();
+    let base_html = prepare_html(
+        res,
+        c,
+        main_content_config,
+        encoding,
+        selector_config,
+        ignore_tags,
+    );
+    let tag_factory = ignore_tag_set(ignore_tags);
+
     match c.return_format {
         ReturnFormat::Raw | ReturnFormat::Bytes => base_html,
+        ReturnFormat::Markdown | ReturnFormat::CommonMark if c.markdown_options.is_some() => {
+            rewrite_markdown(
+                &base_html,
+                c.return_format == ReturnFormat::CommonMark,
+                url_parsed,
+                &tag_factory,
+                &c.markdown_options.unwrap_or_default(),
+            )
+        }
         ReturnFormat::CommonMark => {
             html2md::rewrite_html_custom_with_url(&base_html, &tag_factory, true, url_parsed)
         }
         ReturnFormat::Markdown => {
             html2md::rewrite_html_custom_with_url(&base_html, &tag_factory, false, url_parsed)
         }
+        ReturnFormat::Text | ReturnFormat::Html2Text if c.citations => {
+            extract_text_with_links(&base_html, url_parsed, &tag_factory).text
+        }
         ReturnFormat::Html2Text => {
             if !base_html.is_empty() {
                 crate::html2text::from_read(base_html.as_bytes(), base_html.len())
@@ -415,63 +533,30 @@ pub async fn transform_content_send(
     selector_config: &Option<SelectorConfiguration>,
     ignore_tags: &Option<Vec<String>>,
 ) -> String {
-    let base_html = get_html_with_selector(res, encoding, selector_config);
+    if let Some(content) = transform_document(res, c) {
+        return content;
+    }
 
     // prevent transforming binary files or re-encoding it
     if is_binary_file(res.get_html_bytes_u8()) {
-        return base_html;
+        return get_html_with_selector(res, encoding, selector_config);
     }
 
     let url_parsed = res.get_url_parsed_ref();
-
-    let base_html = {
-        let mut ignore_list = build_static_vector(c);
-
-        if let Some(ignore) = ignore_tags {
-            ignore_list.extend(ignore.iter().map(|s| s.as_str()));
-        }
-
-        if ignore_list.is_empty() {
-            base_html
-        } else {
-            clean_html_elements(&base_html, ignore_list)
-        }
-    };
-
-    // process readability
-    let base_html = if c.readability {
-        match llm_readability::extractor::extract(
-            &mut base_html.as_bytes(),
-            match url_parsed {
-                Some(u) => u,
-                _ => &EXAMPLE_URL,
-            },
-        ) {
-            Ok(product) => product.content,
-            _ => base_html,
-        }
-    } else {
-        base_html
-    };
-
-    let base_html = if c.clean_html {
-        clean_html(&base_html)
-    } else {
-        base_html
-    };
-
-    let mut tag_factory = None;
-
-    if let Some(ignore) = ignore_tags {
-        let mut tag_factor = std::collections::HashSet::with_capacity(ignore.len());
-        for ignore_tag_name in ignore {
-            tag_factor.insert(ignore_tag_name.into());
-        }
-        tag_factory.replace(tag_factor);
-    }
+    let base_html = prepare_html(res, c, &None, encoding, selector_config, ignore_tags);
+    let tag_factory = ignore_tag_set(ignore_tags);
 
     match c.return_format {
         ReturnFormat::Raw | ReturnFormat::Bytes => base_html,
+        ReturnFormat::Markdown | ReturnFormat::CommonMark if c.markdown_options.is_some() => {
+            rewrite_markdown(
+                &base_html,
+                c.return_format == ReturnFormat::CommonMark,
+                url_parsed,
+                &tag_factory,
+                &c.markdown_options.unwrap_or_default(),
+            )
+        }
         ReturnFormat::CommonMark => {
             html2md::rewrite_html_custom_with_url_streaming(
                 &base_html,
@@ -490,6 +575,9 @@ pub async fn transform_content_send(
             )
             .await
         }
+        ReturnFormat::Text | ReturnFormat::Html2Text if c.citations => {
+            extract_text_with_links(&base_html, url_parsed, &tag_factory).text
+        }
         ReturnFormat::Html2Text => {
             if !base_html.is_empty() {
                 crate::html2text::from_read(base_html.as_bytes(), base_html.len())
@@ -512,6 +600,63 @@ pub async fn transform_content_send(
     }
 }
 
+/// Transform the content to text with the `[1]` reference markers and footnotes. The links cited are returned with the text.
+pub fn transform_content_with_links(
+    res: &Page,
+    c: &TransformConfig,
+    encoding: &Option<String>,
+    selector_config: &Option<SelectorConfiguration>,
+    ignore_tags: &Option<Vec<String>>,
+) -> CitedText {
+    let text_config = TransformConfig {
+        return_format: ReturnFormat::Text,
+        ..*c
+    };
+
+    if let Some(text) = transform_document(res, &text_config) {
+        return CitedText {
+            text,
+            links: Vec::new(),
+        };
+    }
+
+    if is_binary_file(res.get_html_bytes_u8()) {
+        return CitedText::default();
+    }
+
+    let base_html = prepare_html(res, c, &None, encoding, selector_config, ignore_tags);
+
+    extract_text_with_links(
+        &base_html,
+        res.get_url_parsed_ref(),
+        &ignore_tag_set(ignore_tags),
+    )
+}
+
+/// Transform format the content with the language and size info of the page. Returns `None` without transforming the page when the info does not match the content filter.
+pub fn transform_content_with_info(
+    res: &Page,
+    c: &TransformConfig,
+    content_filter: &Option<ContentFilter>,
+    encoding: &Option<String>,
+    selector_config: &Option<SelectorConfiguration>,
+    ignore_tags: &Option<Vec<String>>,
+) -> Option<(String, ContentInfo)> {
+    let info = content_info(res);
+
+    if content_filter
+        .as_ref()
+        .is_some_and(|filter| !filter.matches(&info))
+    {
+        return None;
+    }
+
+    Some((
+        transform_content(res, c, encoding, selector_config, ignore_tags),
+        info,
+    ))
+}
+
 /// transform the content to bytes to prevent loss of precision.
 pub fn transform_content_to_bytes(
     res: &Page,
@@ -522,7 +667,9 @@ pub fn transform_content_to_bytes(
 ) -> Vec<u8> {
     if is_binary_file(res.get_html_bytes_u8()) {
         let b = res.get_bytes();
-        if let Some(b) = b {
+        if let Some(content) = transform_document(res, c) {
+            content.into()
+        } else if let Some(b) = b {
             b.clone()
         } else {
             Default::default()
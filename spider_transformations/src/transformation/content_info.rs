@@ -0,0 +1,388 @@
+use super::main_content::content_ratio;
+use spider::auto_encoder::encoding_rs::Encoding;
+use spider::page::Page;
+
+/// The reading speed used for the reading time estimate.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// The min stopwords found to detect the language of latin text.
+const MIN_STOPWORDS: usize = 2;
+
+/// The common words of the languages using the latin script.
+const STOPWORDS: [(&str, &[&str]); 10] = [
+    (
+        "en",
+        &[
+            "the", "and", "of", "to", "is", "that", "with", "for", "this", "are", "was", "you",
+            "it", "have", "from", "be",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "und", "das", "ist", "nicht", "mit", "den", "ein", "eine", "zu", "sich",
+            "auf", "für", "auch", "ich",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "des", "est", "une", "du", "pour", "dans", "que", "qui",
+            "pas", "sur", "avec", "au",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "y", "del", "que", "es", "por", "una", "con", "para", "como",
+            "pero", "sus", "está", "muy",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "di", "che", "è", "per", "gli", "della", "sono", "non", "una", "con", "anche",
+            "nel", "alla", "questo", "più",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "da", "do", "que", "não", "uma", "com", "para", "são", "em", "mais", "dos",
+            "pelo", "você", "também",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "van", "is", "niet", "dat", "op", "te", "zijn", "voor",
+            "met", "ook", "maar", "wij",
+        ],
+    ),
+    (
+        "sv",
+        &[
+            "och", "att", "det", "som", "är", "en", "på", "för", "med", "inte", "av", "den",
+            "till", "har", "jag", "om",
+        ],
+    ),
+    (
+        "pl",
+        &[
+            "i", "w", "nie", "na", "się", "jest", "z", "do", "że", "to", "jak", "ale", "od", "dla",
+            "są", "przez",
+        ],
+    ),
+    (
+        "tr",
+        &[
+            "ve", "bir", "bu", "için", "ile", "da", "de", "çok", "olarak", "daha", "gibi", "ne",
+            "olan", "değil", "ama", "her",
+        ],
+    ),
+];
+
+/// The language and size of the page content.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ContentInfo {
+    /// The language of the page. The html `lang` is used first, then the `Content-Language` and the detected language.
+    pub language: Option<String>,
+    /// The primary language subtag of the html `lang` ex: `en`.
+    pub html_lang: Option<String>,
+    /// The primary language subtag of the `Content-Language` header.
+    pub content_language: Option<String>,
+    /// The language detected from the text.
+    pub detected_language: Option<String>,
+    /// The words of the text. Each CJK character counts as a word.
+    pub word_count: usize,
+    /// The reading time estimate in seconds.
+    pub reading_time_secs: u64,
+    /// The main content text length over the total text length from `0.0` to `1.0`.
+    pub main_content_ratio: f64,
+    /// The encoding of the page bytes ex: `UTF-8`.
+    pub encoding: Option<String>,
+}
+
+impl ContentInfo {
+    /// The language is one of the languages. The primary subtags are compared ex: `en` matches `en-US`.
+    pub fn language_in<S: AsRef<str>>(&self, languages: &[S]) -> bool {
+        self.language.as_deref().is_some_and(|language| {
+            languages
+                .iter()
+                .any(|l| primary_language(l.as_ref()).as_deref() == Some(language))
+        })
+    }
+}
+
+/// Filter pages by the content info. Use it in the subscribe loop or the `on_should_crawl_callback`.
+#[derive(Debug, Default, Clone)]
+pub struct ContentFilter {
+    /// The languages to keep. All languages are kept when empty.
+    pub languages: Vec<String>,
+    /// The min words to keep the page.
+    pub min_words: Option<usize>,
+    /// The max words to keep the page.
+    pub max_words: Option<usize>,
+    /// The min main content ratio to keep the page.
+    pub min_main_content_ratio: Option<f64>,
+}
+
+impl ContentFilter {
+    /// Keep only the languages ex: `["en", "de"]`.
+    pub fn with_languages<S: AsRef<str>>(mut self, languages: &[S]) -> Self {
+        self.languages = languages.iter().map(|l| l.as_ref().into()).collect();
+        self
+    }
+
+    /// Keep the pages with at least the words.
+    pub fn with_min_words(mut self, min_words: usize) -> Self {
+        self.min_words = Some(min_words);
+        self
+    }
+
+    /// Keep the pages with at most the words.
+    pub fn with_max_words(mut self, max_words: usize) -> Self {
+        self.max_words = Some(max_words);
+        self
+    }
+
+    /// Keep the pages with at least the main content ratio.
+    pub fn with_min_main_content_ratio(mut self, ratio: f64) -> Self {
+        self.min_main_content_ratio = Some(ratio);
+        self
+    }
+
+    /// The content info matches the filter.
+    pub fn matches(&self, info: &ContentInfo) -> bool {
+        (self.languages.is_empty() || info.language_in(&self.languages))
+            && self.min_words.is_none_or(|min| info.word_count >= min)
+            && self.max_words.is_none_or(|max| info.word_count <= max)
+            && self
+                .min_main_content_ratio
+                .is_none_or(|min| info.main_content_ratio >= min)
+    }
+
+    /// The page matches the filter.
+    pub fn matches_page(&self, page: &Page) -> bool {
+        self.matches(&content_info(page))
+    }
+}
+
+/// The primary language subtag in lowercase ex: `en-US` is `en`.
+fn primary_language(tag: &str) -> Option<String> {
+    let primary = tag
+        .split(',')
+        .next()?
+        .split(['-', '_', ';'])
+        .next()?
+        .trim()
+        .to_ascii_lowercase();
+
+    if primary.is_empty() || primary == "x" || primary == "und" {
+        None
+    } else {
+        Some(primary)
+    }
+}
+
+/// The char is a CJK ideograph, kana or hangul syllable.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{ac00}'..='\u{d7af}')
+}
+
+/// Count the words of the text. Each CJK character counts as a word.
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| {
+            let cjk = word.chars().filter(|c| is_cjk(*c)).count();
+
+            if cjk > 0 {
+                cjk
+            } else if word.chars().any(|c| c.is_alphanumeric()) {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Detect the language of the text from the script and the common words. Returns the ISO 639-1 code ex: `en`.
+pub fn detect_language(text: &str) -> Option<String> {
+    let mut kana = 0;
+    let mut han = 0;
+    let mut hangul = 0;
+    let mut cyrillic = 0;
+    let mut ukrainian = 0;
+    let mut arabic = 0;
+    let mut persian = 0;
+    let mut hebrew = 0;
+    let mut greek = 0;
+    let mut thai = 0;
+    let mut devanagari = 0;
+    let mut latin = 0;
+
+    for c in text.chars() {
+        match c {
+            '\u{3040}'..='\u{30ff}' => kana += 1,
+            '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{f900}'..='\u{faff}' => han += 1,
+            '\u{ac00}'..='\u{d7af}' | '\u{1100}'..='\u{11ff}' => hangul += 1,
+            'і' | 'ї' | 'є' | 'ґ' | 'І' | 'Ї' | 'Є' | 'Ґ' => {
+                cyrillic += 1;
+                ukrainian += 1;
+            }
+            '\u{0400}'..='\u{04ff}' => cyrillic += 1,
+            'پ' | 'چ' | 'ژ' | 'گ' | 'ی' => {
+                arabic += 1;
+                persian += 1;
+            }
+            '\u{0600}'..='\u{06ff}' => arabic += 1,
+            '\u{0590}'..='\u{05ff}' => hebrew += 1,
+            '\u{0370}'..='\u{03ff}' => greek += 1,
+            '\u{0e00}'..='\u{0e7f}' => thai += 1,
+            '\u{0900}'..='\u{097f}' => devanagari += 1,
+            c if c.is_alphabetic() => latin += 1,
+            _ => (),
+        }
+    }
+
+    let scripts = [
+        (kana + han, if kana > 0 { "ja" } else { "zh" }),
+        (hangul, "ko"),
+        (cyrillic, if ukrainian > 0 { "uk" } else { "ru" }),
+        (arabic, if persian * 20 > arabic { "fa" } else { "ar" }),
+        (hebrew, "he"),
+        (greek, "el"),
+        (thai, "th"),
+        (devanagari, "hi"),
+        (latin, ""),
+    ];
+
+    let (count, language) = scripts.iter().max_by_key(|(count, _)| *count)?;
+
+    if *count == 0 {
+        return None;
+    }
+
+    if !language.is_empty() {
+        return Some(language.to_string());
+    }
+
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+
+    let mut scores: Vec<(usize, &str)> = STOPWORDS
+        .iter()
+        .map(|(language, stopwords)| {
+            let hits = words
+                .iter()
+                .filter(|word| stopwords.contains(&word.as_str()))
+                .count();
+            (hits, *language)
+        })
+        .collect();
+
+    scores.sort_by_key(|(hits, _)| std::cmp::Reverse(*hits));
+
+    match scores.as_slice() {
+        [(best, language), (second, _), ..] if *best >= MIN_STOPWORDS && best > second => {
+            Some(language.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Detect the encoding of the page bytes. The html meta charset is used first, then the `Content-Type` charset, the byte order mark, the utf-8 validation and the encoding of the language.
+pub fn detect_encoding(
+    bytes: &[u8],
+    content_type: Option<&str>,
+    language: Option<&str>,
+) -> Option<String> {
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let declared = spider::auto_encoder::detect_encoding(bytes).or_else(|| {
+        content_type?
+            .split(';')
+            .find_map(|part| part.trim().strip_prefix("charset="))
+            .map(|charset| charset.trim_matches('"').to_string())
+    });
+
+    let encoding = declared
+        .and_then(|label| Encoding::for_label(label.trim().as_bytes()))
+        .or_else(|| Encoding::for_bom(bytes).map(|(encoding, _)| encoding))
+        .or_else(|| {
+            std::str::from_utf8(bytes)
+                .ok()
+                .map(|_| spider::auto_encoder::encoding_rs::UTF_8)
+        })
+        .or_else(|| language.and_then(spider::auto_encoder::encoding_for_locale))
+        .unwrap_or(spider::auto_encoder::encoding_rs::WINDOWS_1252);
+
+    Some(encoding.name().to_string())
+}
+
+/// Get the content info of the html. The encoding is not set.
+pub fn html_content_info(html: &str, content_language: Option<&str>) -> ContentInfo {
+    let text = super::text_extract::extract_text(html, &None);
+    let html_lang = spider::auto_encoder::detect_language(html.as_bytes())
+        .and_then(|lang| primary_language(&lang));
+    let content_language = content_language.and_then(primary_language);
+    let detected_language = detect_language(&text);
+    let word_count = word_count(&text);
+
+    ContentInfo {
+        language: html_lang
+            .clone()
+            .or_else(|| content_language.clone())
+            .or_else(|| detected_language.clone()),
+        html_lang,
+        content_language,
+        detected_language,
+        word_count,
+        reading_time_secs: (word_count * 60).div_ceil(WORDS_PER_MINUTE) as u64,
+        main_content_ratio: content_ratio(html),
+        encoding: None,
+    }
+}
+
+/// Get the header value of the page.
+#[cfg(feature = "headers")]
+fn header_value(res: &Page, name: &str) -> Option<String> {
+    res.headers
+        .as_ref()?
+        .get(name)?
+        .to_str()
+        .ok()
+        .map(|value| value.to_string())
+}
+
+/// Get the header value of the page.
+#[cfg(not(feature = "headers"))]
+fn header_value(_res: &Page, _name: &str) -> Option<String> {
+    None
+}
+
+/// Get the content info of the page. Enable the `headers` feature to use the `Content-Language` and `Content-Type` headers.
+pub fn content_info(res: &Page) -> ContentInfo {
+    let content_language = header_value(res, "content-language");
+    let content_type = header_value(res, "content-type");
+    let mut info = html_content_info(&res.get_html(), content_language.as_deref());
+
+    info.encoding = detect_encoding(
+        res.get_html_bytes_u8(),
+        content_type.as_deref(),
+        info.language.as_deref(),
+    );
+
+    info
+}
@@ -0,0 +1,317 @@
+use std::io::{Cursor, Read};
+
+/// The document types with text extraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocumentType {
+    /// Portable Document Format.
+    Pdf,
+    /// Word document.
+    Docx,
+    /// Excel workbook.
+    Xlsx,
+}
+
+/// A page or sheet of the document.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DocumentSection {
+    /// The name of the section ex: `Page 1` or the sheet name.
+    pub name: String,
+    /// The text of the section.
+    pub text: String,
+    /// The rows of the sheet cells. This is empty for pages.
+    pub rows: Vec<Vec<String>>,
+    /// The headings of the text. This is empty for the documents without heading styles.
+    pub headings: Vec<DocumentHeading>,
+}
+
+/// A heading line of the section text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DocumentHeading {
+    /// The index of the line in the section text.
+    pub line: usize,
+    /// The level of the heading from `1` to `6`.
+    pub level: usize,
+}
+
+/// The text extracted from a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedDocument {
+    /// The type of the document.
+    pub document_type: DocumentType,
+    /// The pages or sheets of the document in order.
+    pub sections: Vec<DocumentSection>,
+}
+
+impl ExtractedDocument {
+    /// The plain text of the document. The pages and sheets are separated with a form feed.
+    pub fn to_text(&self) -> String {
+        self.sections
+            .iter()
+            .map(|section| section.text.trim())
+            .collect::<Vec<_>>()
+            .join("\n\x0c\n")
+    }
+
+    /// The markdown of the document. Each page or sheet starts with a heading and the sheets are tables.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+
+        for section in self.sections.iter() {
+            if !markdown.is_empty() {
+                markdown.push_str("\n\n");
+            }
+            markdown.push_str("## ");
+            markdown.push_str(&section.name);
+            markdown.push_str("\n\n");
+
+            if section.rows.is_empty() {
+                markdown.push_str(section_markdown(section).trim());
+            } else {
+                markdown_table(&mut markdown, &section.rows);
+            }
+        }
+
+        markdown
+    }
+}
+
+/// The text of the section with the `#` markers of the headings.
+fn section_markdown(section: &DocumentSection) -> String {
+    if section.headings.is_empty() {
+        return section.text.clone();
+    }
+
+    section
+        .text
+        .split('\n')
+        .enumerate()
+        .map(
+            |(line, text)| match section.headings.iter().find(|heading| heading.line == line) {
+                Some(heading) => format!("{} {text}", "#".repeat(heading.level)),
+                _ => text.to_string(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Write the rows as a markdown table with the first row as the header.
+fn markdown_table(markdown: &mut String, rows: &[Vec<String>]) {
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+
+    for (i, row) in rows.iter().enumerate() {
+        markdown.push('|');
+        for column in 0..columns {
+            let cell = row.get(column).map(|c| c.as_str()).unwrap_or_default();
+            markdown.push(' ');
+            markdown.push_str(&cell.replace('|', "\\|").replace('\n', " "));
+            markdown.push_str(" |");
+        }
+        markdown.push('\n');
+
+        if i == 0 {
+            markdown.push('|');
+            for _ in 0..columns {
+                markdown.push_str(" --- |");
+            }
+            markdown.push('\n');
+        }
+    }
+}
+
+/// Detect the document type from the bytes and the `Content-Type`.
+pub fn detect_document_type(bytes: &[u8], content_type: Option<&str>) -> Option<DocumentType> {
+    let content_type = content_type
+        .and_then(|c| c.split(';').next())
+        .map(|c| c.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if bytes.starts_with(b"%PDF-") || content_type == "application/pdf" {
+        return Some(DocumentType::Pdf);
+    }
+
+    if !bytes.starts_with(b"PK\x03\x04") {
+        return None;
+    }
+
+    if content_type.contains("wordprocessingml") {
+        return Some(DocumentType::Docx);
+    }
+
+    if content_type.contains("spreadsheetml") {
+        return Some(DocumentType::Xlsx);
+    }
+
+    let archive = zip::ZipArchive::new(Cursor::new(bytes)).ok()?;
+    let mut names = archive.file_names();
+
+    names.find_map(|name| match name {
+        "word/document.xml" => Some(DocumentType::Docx),
+        "xl/workbook.xml" => Some(DocumentType::Xlsx),
+        _ => None,
+    })
+}
+
+/// Extract the text of the document keeping the pages and sheets.
+pub fn extract_document(bytes: &[u8], document_type: DocumentType) -> Option<ExtractedDocument> {
+    let sections = match document_type {
+        DocumentType::Pdf => extract_pdf(bytes),
+        DocumentType::Docx => extract_docx(bytes),
+        DocumentType::Xlsx => extract_xlsx(bytes),
+    }?;
+
+    Some(ExtractedDocument {
+        document_type,
+        sections,
+    })
+}
+
+/// Extract the text of the pdf pages.
+fn extract_pdf(bytes: &[u8]) -> Option<Vec<DocumentSection>> {
+    let mut document = lopdf::Document::load_mem(bytes).ok()?;
+
+    if document.is_encrypted() && document.decrypt("").is_err() {
+        return None;
+    }
+
+    let sections = document
+        .get_pages()
+        .keys()
+        .map(|page| DocumentSection {
+            name: format!("Page {}", page),
+            text: document.extract_text(&[*page]).unwrap_or_default(),
+            ..Default::default()
+        })
+        .collect();
+
+    Some(sections)
+}
+
+/// Extract the text of the docx paragraphs. Page breaks start a new section.
+fn extract_docx(bytes: &[u8]) -> Option<Vec<DocumentSection>> {
+    use quick_xml::events::Event;
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).ok()?;
+    let mut xml = String::new();
+
+    archive
+        .by_name("word/document.xml")
+        .ok()?
+        .read_to_string(&mut xml)
+        .ok()?;
+
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    let mut sections = Vec::new();
+    let mut text = String::new();
+    let mut paragraph = String::new();
+    let mut heading: Option<usize> = None;
+    let mut headings = Vec::new();
+    let mut in_text = false;
+
+    let new_section = |text: &mut String,
+                       headings: &mut Vec<DocumentHeading>,
+                       sections: &mut Vec<DocumentSection>| {
+        sections.push(DocumentSection {
+            name: format!("Page {}", sections.len() + 1),
+            text: std::mem::take(text),
+            rows: Vec::new(),
+            headings: std::mem::take(headings),
+        });
+    };
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"t" => in_text = true,
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"tab" => paragraph.push('\t'),
+                b"br" => {
+                    let page_break = e.attributes().flatten().any(|a| {
+                        a.key.local_name().as_ref() == b"type" && a.value.as_ref() == b"page"
+                    });
+
+                    if page_break {
+                        text.push_str(&paragraph);
+                        paragraph.clear();
+                        new_section(&mut text, &mut headings, &mut sections);
+                    } else {
+                        paragraph.push('\n');
+                    }
+                }
+                b"pStyle" => {
+                    heading = e
+                        .attributes()
+                        .flatten()
+                        .find(|a| a.key.local_name().as_ref() == b"val")
+                        .and_then(|a| {
+                            let value = String::from_utf8_lossy(&a.value).to_ascii_lowercase();
+                            value
+                                .strip_prefix("heading")
+                                .and_then(|level| level.parse::<usize>().ok())
+                        });
+                }
+                _ => (),
+            },
+            Ok(Event::Text(e)) if in_text => {
+                if let Ok(t) = e.unescape() {
+                    paragraph.push_str(&t);
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => {
+                    if let Some(level) = heading.take() {
+                        if !paragraph.trim().is_empty() {
+                            headings.push(DocumentHeading {
+                                line: text.matches('\n').count(),
+                                level: level.clamp(1, 6),
+                            });
+                        }
+                    }
+                    text.push_str(&paragraph);
+                    text.push('\n');
+                    paragraph.clear();
+                }
+                _ => (),
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => (),
+        }
+    }
+
+    text.push_str(&paragraph);
+    new_section(&mut text, &mut headings, &mut sections);
+
+    Some(sections)
+}
+
+/// Extract the cells of the workbook sheets.
+fn extract_xlsx(bytes: &[u8]) -> Option<Vec<DocumentSection>> {
+    use calamine::Reader;
+
+    let mut workbook = calamine::open_workbook_auto_from_rs(Cursor::new(bytes)).ok()?;
+    let mut sections = Vec::new();
+
+    for name in workbook.sheet_names() {
+        if let Ok(range) = workbook.worksheet_range(&name) {
+            let rows: Vec<Vec<String>> = range
+                .rows()
+                .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+                .collect();
+
+            let text = rows
+                .iter()
+                .map(|row| row.join("\t"))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            sections.push(DocumentSection {
+                name,
+                text,
+                rows,
+                ..Default::default()
+            });
+        }
+    }
+
+    Some(sections)
+}
@@ -0,0 +1,447 @@
+use super::content::SelectorConfiguration;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use spider::lazy_static::lazy_static;
+use spider::url::Url;
+use std::collections::HashMap;
+
+lazy_static! {
+    /// Class and id hints of boilerplate blocks.
+    static ref NEGATIVE_HINTS: Regex = Regex::new(
+        r"(?i)nav|menu|footer|sidebar|side-bar|comment|share|social|related|promo|banner|advert|\bads?\b|cookie|breadcrumb|subscribe|newsletter|popup|modal|widget|sponsor"
+    )
+    .unwrap();
+    /// Class and id hints of content blocks.
+    static ref POSITIVE_HINTS: Regex =
+        Regex::new(r"(?i)article|content|entry|post|main|story|text|blog").unwrap();
+}
+
+/// Elements skipped for scoring.
+const IGNORED_TAGS: [&str; 11] = [
+    "script", "style", "noscript", "template", "svg", "iframe", "head", "button", "select",
+    "option", "textarea",
+];
+
+/// Elements that are always boilerplate.
+const BOILERPLATE_TAGS: [&str; 4] = ["nav", "footer", "aside", "form"];
+
+/// Elements that hold a block of text.
+const BLOCK_TAGS: [&str; 26] = [
+    "p",
+    "div",
+    "article",
+    "main",
+    "section",
+    "td",
+    "th",
+    "li",
+    "dd",
+    "dt",
+    "blockquote",
+    "pre",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "body",
+    "table",
+    "ul",
+    "ol",
+    "figure",
+    "figcaption",
+    "header",
+    "address",
+];
+
+/// Elements that can hold the main content.
+const CANDIDATE_TAGS: [&str; 6] = ["div", "article", "main", "section", "td", "body"];
+
+/// The method used to find the main content.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionMethod {
+    /// The domain override selector matched.
+    Selector,
+    /// The text and link density scoring.
+    Scoring,
+    /// The readability fallback.
+    Readability,
+    #[default]
+    /// No content was found, the full html is returned.
+    FullHtml,
+}
+
+/// The main content of the page.
+#[derive(Debug, Default, Clone)]
+pub struct MainContent {
+    /// The html of the main content.
+    pub html: String,
+    /// The confidence from `0.0` to `1.0` that the html is only the main content.
+    pub confidence: f64,
+    /// The method used to find the content.
+    pub method: ExtractionMethod,
+}
+
+/// The main content extraction configuration.
+#[derive(Debug, Clone)]
+pub struct MainContentConfig {
+    /// The min confidence of the scoring before falling back to readability.
+    pub min_confidence: f64,
+    /// Use readability when the scoring is below the min confidence.
+    pub readability_fallback: bool,
+    /// The selectors to use per domain. Domains also match the subdomains ex: `example.com` matches `blog.example.com`.
+    pub domain_selectors: HashMap<String, SelectorConfiguration>,
+}
+
+impl Default for MainContentConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.3,
+            readability_fallback: true,
+            domain_selectors: HashMap::new(),
+        }
+    }
+}
+
+impl MainContentConfig {
+    /// Add a selector override for the domain.
+    pub fn with_domain_selector(
+        mut self,
+        domain: &str,
+        selector_config: SelectorConfiguration,
+    ) -> Self {
+        self.domain_selectors
+            .insert(domain.to_ascii_lowercase(), selector_config);
+        self
+    }
+
+    /// Get the selector override for the url.
+    pub fn selector_configuration(&self, url: Option<&Url>) -> Option<&SelectorConfiguration> {
+        let mut host = url?.host_str()?;
+
+        loop {
+            if let Some(config) = self.domain_selectors.get(host) {
+                return Some(config);
+            }
+            match host.find('.') {
+                Some(index) => host = &host[index + 1..],
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// The scores of a candidate element.
+struct Candidate<'a> {
+    /// The element.
+    element: ElementRef<'a>,
+    /// The content text length.
+    good: usize,
+    /// The boilerplate text length.
+    bad: usize,
+    /// The boilerplate text length removed from the output.
+    removed: usize,
+    /// The weight from the tag and hints.
+    weight: f64,
+    /// The depth of the element.
+    depth: usize,
+}
+
+impl Candidate<'_> {
+    /// The score of the candidate.
+    fn score(&self) -> f64 {
+        (self.good as f64 - self.bad as f64) * self.weight
+    }
+}
+
+/// The state of the scoring walk.
+#[derive(Default)]
+struct Scoring<'a> {
+    /// The candidates found.
+    candidates: Vec<Candidate<'a>>,
+    /// The top boilerplate elements to remove from the output.
+    boilerplate: Vec<ElementRef<'a>>,
+    /// The total content text length.
+    good: usize,
+}
+
+/// The text stats of a subtree.
+#[derive(Default)]
+struct Tally {
+    /// The content text length of the blocks.
+    good: usize,
+    /// The boilerplate text length of the blocks.
+    bad: usize,
+    /// The boilerplate text length of the hinted elements.
+    removed: usize,
+    /// The text length not in a block yet.
+    text: usize,
+    /// The link text length not in a block yet.
+    link: usize,
+    /// The words not in a block yet.
+    words: usize,
+}
+
+/// The element is hinted as boilerplate.
+fn is_boilerplate(element: &ElementRef) -> bool {
+    let value = element.value();
+
+    BOILERPLATE_TAGS.contains(&value.name())
+        || value.attr("role").is_some_and(|role| {
+            role == "navigation" || role == "complementary" || role == "contentinfo"
+        })
+        || value
+            .attr("class")
+            .is_some_and(|class| NEGATIVE_HINTS.is_match(class))
+        || value.id().is_some_and(|id| NEGATIVE_HINTS.is_match(id))
+}
+
+/// The weight of the candidate from the tag and hints.
+fn candidate_weight(element: &ElementRef) -> f64 {
+    let value = element.value();
+
+    if value.name() == "article" || value.name() == "main" || value.attr("role") == Some("main") {
+        1.5
+    } else if value
+        .attr("class")
+        .is_some_and(|class| POSITIVE_HINTS.is_match(class))
+        || value.id().is_some_and(|id| POSITIVE_HINTS.is_match(id))
+    {
+        1.25
+    } else {
+        1.0
+    }
+}
+
+/// Score the subtree of the element.
+fn score_element<'a>(
+    element: ElementRef<'a>,
+    scoring: &mut Scoring<'a>,
+    depth: usize,
+    negative: bool,
+) -> Tally {
+    let name = element.value().name();
+    let boilerplate = !negative && is_boilerplate(&element);
+    let negative = negative || boilerplate;
+    let mut tally = Tally::default();
+
+    if boilerplate {
+        scoring.boilerplate.push(element);
+    }
+
+    for child in element.children() {
+        match ElementRef::wrap(child) {
+            Some(child) => {
+                if IGNORED_TAGS.contains(&child.value().name()) {
+                    continue;
+                }
+                let child_tally = score_element(child, scoring, depth + 1, negative);
+
+                tally.good += child_tally.good;
+                tally.bad += child_tally.bad;
+                tally.removed += child_tally.removed;
+                tally.text += child_tally.text;
+                tally.link += child_tally.link;
+                tally.words += child_tally.words;
+            }
+            _ => {
+                if let Some(text) = child.value().as_text() {
+                    for word in text.split_whitespace() {
+                        tally.text += word.chars().count();
+                        tally.words += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    if name == "a" {
+        tally.link = tally.text;
+    }
+
+    if (boilerplate || BLOCK_TAGS.contains(&name)) && tally.text > 0 {
+        let link_density = tally.link as f64 / tally.text as f64;
+        let heading = name.len() == 2 && name.starts_with('h') && name != "hr";
+
+        if negative || link_density > 0.5 {
+            tally.bad += tally.text;
+            if negative {
+                tally.removed += tally.text;
+            }
+        } else if link_density <= 0.3 && (tally.words >= 8 || heading) {
+            tally.good += tally.text;
+            scoring.good += tally.text;
+        }
+
+        tally.text = 0;
+        tally.link = 0;
+        tally.words = 0;
+    }
+
+    if !negative && (CANDIDATE_TAGS.contains(&name) || element.value().attr("role") == Some("main"))
+    {
+        scoring.candidates.push(Candidate {
+            element,
+            good: tally.good,
+            bad: tally.bad,
+            removed: tally.removed,
+            weight: candidate_weight(&element),
+            depth,
+        });
+    }
+
+    tally
+}
+
+/// Score the content density of the whole html. Returns the content length over the total text length.
+pub(crate) fn content_ratio(html: &str) -> f64 {
+    let document = Html::parse_document(html);
+    let mut scoring = Scoring::default();
+    let tally = score_element(document.root_element(), &mut scoring, 0, false);
+    let total = tally.good + tally.bad + tally.text;
+
+    if total == 0 {
+        0.0
+    } else {
+        tally.good as f64 / total as f64
+    }
+}
+
+/// Find the main content with the text and link density scoring.
+fn score_document(document: &Html) -> Option<MainContent> {
+    let mut scoring = Scoring::default();
+
+    score_element(document.root_element(), &mut scoring, 0, false);
+
+    let mut best: Option<&Candidate> = None;
+
+    for candidate in scoring.candidates.iter() {
+        if candidate.good == 0 {
+            continue;
+        }
+        best = match best {
+            Some(b)
+                if b.score() > candidate.score()
+                    || b.score() == candidate.score() && b.depth >= candidate.depth =>
+            {
+                Some(b)
+            }
+            _ => Some(candidate),
+        };
+    }
+
+    let best = best?;
+    let best_id = best.element.id();
+    let coverage = best.good as f64 / scoring.good.max(1) as f64;
+    let purity = best.good as f64 / (best.good + best.bad - best.removed) as f64;
+
+    let removals: Vec<_> = scoring
+        .boilerplate
+        .iter()
+        .filter(|el| el.ancestors().any(|a| a.id() == best_id))
+        .map(|el| el.id())
+        .collect();
+
+    let html = if removals.is_empty() {
+        best.element.html()
+    } else {
+        let mut document = document.clone();
+
+        for id in removals {
+            document.remove_node(id);
+        }
+
+        document
+            .tree
+            .get(best_id)
+            .and_then(ElementRef::wrap)
+            .map(|el| el.html())
+            .unwrap_or_default()
+    };
+
+    Some(MainContent {
+        html,
+        confidence: coverage * purity,
+        method: ExtractionMethod::Scoring,
+    })
+}
+
+/// Apply the domain selector override to the document. Returns the content when the root selector matched.
+fn apply_selector(
+    document: &mut Html,
+    selector_config: &SelectorConfiguration,
+) -> Option<MainContent> {
+    if let Some(exclude) = selector_config.exclude_selector.as_deref() {
+        if let Ok(exclude) = Selector::parse(exclude) {
+            let removals: Vec<_> = document.select(&exclude).map(|el| el.id()).collect();
+
+            for id in removals {
+                document.remove_node(id);
+            }
+        }
+    }
+
+    let root = Selector::parse(selector_config.root_selector.as_deref()?).ok()?;
+    let html: String = document.select(&root).map(|el| el.html()).collect();
+
+    if html.is_empty() {
+        None
+    } else {
+        Some(MainContent {
+            html,
+            confidence: 1.0,
+            method: ExtractionMethod::Selector,
+        })
+    }
+}
+
+/// Extract the main content of the html. The domain selector override is used first, then the text and link density scoring, then readability when the scoring is below the min confidence, and last the full html.
+pub fn extract_main_content(
+    html: &str,
+    url: Option<&Url>,
+    config: &MainContentConfig,
+) -> MainContent {
+    let mut document = Html::parse_document(html);
+
+    if let Some(selector_config) = config.selector_configuration(url) {
+        if let Some(content) = apply_selector(&mut document, selector_config) {
+            return content;
+        }
+    }
+
+    let scored = score_document(&document);
+
+    if let Some(ref content) = scored {
+        if content.confidence >= config.min_confidence {
+            return content.clone();
+        }
+    }
+
+    if config.readability_fallback {
+        if let Some(url) = url {
+            if let Ok(product) = llm_readability::extractor::extract(&mut html.as_bytes(), url) {
+                if !product.text.trim().is_empty() {
+                    let confidence = content_ratio(&product.content);
+
+                    if scored
+                        .as_ref()
+                        .is_none_or(|scored| confidence > scored.confidence)
+                    {
+                        return MainContent {
+                            html: product.content,
+                            confidence,
+                            method: ExtractionMethod::Readability,
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    scored.unwrap_or_else(|| MainContent {
+        html: html.into(),
+        confidence: 0.0,
+        method: ExtractionMethod::FullHtml,
+    })
+}
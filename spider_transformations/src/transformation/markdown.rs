@@ -0,0 +1,666 @@
+use super::tables::{cell_span, table_rows};
+use regex::Regex;
+use scraper::node::Text;
+use scraper::{ElementRef, Html, Node};
+use spider::lazy_static::lazy_static;
+use spider::url::Url;
+use std::collections::{HashMap, HashSet};
+
+lazy_static! {
+    /// Markdown links and images.
+    static ref MARKDOWN_LINK: Regex =
+        Regex::new(r"(!?)\[((?:[^\[\]]|\[[^\[\]]*\])*)\]\((<[^<>]*>|[^()\s]+)\)").unwrap();
+    /// The quote and list item markers that start a line.
+    static ref LINE_PREFIX: Regex = Regex::new(r"^((?:\s*>)*\s*)((?:[*+-]|\d+[.)])\s+)?").unwrap();
+}
+
+/// The start of the placeholder of the rendered blocks in the html. The private use placeholders are stripped from the parsed input.
+const BLOCK_START: char = '\u{E000}';
+/// The end of the placeholder of the rendered blocks in the html.
+const BLOCK_END: char = '\u{E001}';
+/// The placeholder of the whitespace next to the inline elements. The converter trims the text around the elements.
+const SPACE_PLACEHOLDER: char = '\u{E002}';
+
+/// The placeholder characters.
+fn is_placeholder(c: char) -> bool {
+    matches!(c, BLOCK_START | BLOCK_END | SPACE_PLACEHOLDER)
+}
+
+/// The inline elements keeping the whitespace around them.
+const INLINE_ELEMENTS: [&str; 18] = [
+    "a", "abbr", "b", "cite", "code", "del", "em", "i", "img", "kbd", "mark", "q", "s", "small",
+    "span", "strong", "sub", "sup",
+];
+
+/// How tables are rendered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableStyle {
+    #[default]
+    /// GitHub flavored markdown tables. The first row is the header.
+    Gfm,
+    /// One line of text per row with the cells separated by a space.
+    PlainText,
+}
+
+/// How links are rendered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkStyle {
+    #[default]
+    /// Inline links ex: `[text](url)`.
+    Inline,
+    /// Reference links ex: `[text][1]` with the definitions at the end.
+    Reference,
+}
+
+/// How headings are rendered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeadingStyle {
+    #[default]
+    /// Headings with a `#` prefix.
+    Atx,
+    /// Underlined `h1` and `h2` headings. The other headings keep the `#` prefix.
+    Setext,
+}
+
+/// The markdown rendering options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MarkdownOptions {
+    /// The table rendering.
+    pub tables: TableStyle,
+    /// The link rendering.
+    pub links: LinkStyle,
+    /// Keep the alt text of the images.
+    pub image_alt: bool,
+    /// The heading rendering.
+    pub headings: HeadingStyle,
+    /// Render the code blocks as fences with the language of the `class="language-*"`.
+    pub code_language: bool,
+    /// Wrap the lines of text at the max width. Code blocks, tables and headings are not wrapped.
+    pub max_width: Option<usize>,
+    /// Remove the links without text or images.
+    pub strip_empty_links: bool,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            tables: TableStyle::Gfm,
+            links: LinkStyle::Inline,
+            image_alt: true,
+            headings: HeadingStyle::Atx,
+            code_language: true,
+            max_width: None,
+            strip_empty_links: false,
+        }
+    }
+}
+
+/// The change to make to an element before converting the html.
+enum Action {
+    /// Replace the element with the rendered block.
+    Replace(String),
+    /// Remove the element.
+    Remove,
+    /// Remove the alt attribute of the image.
+    RemoveAlt,
+}
+
+/// The markdown converter of the elements rendered before the html.
+struct Renderer<'a> {
+    /// Use commonmark.
+    commonmark: bool,
+    /// The url of the page.
+    url: &'a Option<Url>,
+    /// The elements to ignore.
+    custom_ignore: &'a Option<HashSet<String>>,
+    /// The rendering options.
+    options: &'a MarkdownOptions,
+}
+
+impl Renderer<'_> {
+    /// Convert the inner html of the element to markdown on a single line.
+    fn inline(&self, element: &ElementRef) -> String {
+        let markdown = html2md::rewrite_html_custom_with_url(
+            &element.inner_html(),
+            self.custom_ignore,
+            self.commonmark,
+            self.url,
+        );
+
+        markdown.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Find the changes to make to the element and the children.
+    fn visit<'b>(&self, element: ElementRef<'b>, actions: &mut Vec<(ElementRef<'b>, Action)>) {
+        let action = match element.value().name() {
+            "table" => self.table(&element).map(Action::Replace),
+            "pre" if self.options.code_language => Some(Action::Replace(code_block(&element))),
+            "h1" | "h2" if self.options.headings == HeadingStyle::Setext => {
+                let text = self.inline(&element);
+                let underline = if element.value().name() == "h1" {
+                    "="
+                } else {
+                    "-"
+                };
+
+                Some(if text.is_empty() {
+                    Action::Remove
+                } else {
+                    let width = text.chars().count();
+                    Action::Replace(format!("{}\n{}", text, underline.repeat(width)))
+                })
+            }
+            "a" if self.options.strip_empty_links && is_empty_link(&element) => {
+                Some(Action::Remove)
+            }
+            "img" if !self.options.image_alt && element.value().attr("alt").is_some() => {
+                Some(Action::RemoveAlt)
+            }
+            _ => None,
+        };
+
+        if let Some(action) = action {
+            let keep_visiting = matches!(action, Action::RemoveAlt);
+
+            actions.push((element, action));
+
+            if !keep_visiting {
+                return;
+            }
+        }
+
+        for child in element.children().filter_map(ElementRef::wrap) {
+            self.visit(child, actions);
+        }
+    }
+
+    /// Render the table rows. Nested tables are flattened into the cells.
+    fn table(&self, element: &ElementRef) -> Option<String> {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+
+        for row in table_rows(element) {
+            let mut cells = Vec::new();
+
+            for cell in row.children().filter_map(ElementRef::wrap) {
+                let name = cell.value().name();
+
+                if name != "td" && name != "th" {
+                    continue;
+                }
+                let span = cell_span(&cell, "colspan").unwrap_or(1);
+
+                cells.push(self.inline(&cell));
+                cells.extend(std::iter::repeat_n(String::new(), span - 1));
+            }
+
+            if !cells.is_empty() {
+                rows.push(cells);
+            }
+        }
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        let mut markdown = String::new();
+
+        match self.options.tables {
+            TableStyle::PlainText => {
+                for row in rows.iter() {
+                    let line = row
+                        .iter()
+                        .filter(|cell| !cell.is_empty())
+                        .map(|cell| cell.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+
+                    if !line.is_empty() {
+                        if !markdown.is_empty() {
+                            markdown.push('\n');
+                        }
+                        markdown.push_str(&line);
+                    }
+                }
+            }
+            TableStyle::Gfm => {
+                let columns = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+
+                for (i, row) in rows.iter().enumerate() {
+                    if i > 0 {
+                        markdown.push('\n');
+                    }
+                    markdown.push('|');
+                    for column in 0..columns {
+                        let cell = row.get(column).map(|c| c.as_str()).unwrap_or_default();
+                        markdown.push(' ');
+                        markdown.push_str(&cell.replace('|', "\\|"));
+                        markdown.push_str(" |");
+                    }
+                    if i == 0 {
+                        markdown.push_str("\n|");
+                        for _ in 0..columns {
+                            markdown.push_str(" --- |");
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(markdown)
+    }
+}
+
+/// The link has no text or images.
+fn is_empty_link(element: &ElementRef) -> bool {
+    element.text().all(|text| text.trim().is_empty())
+        && !element
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .any(|el| matches!(el.value().name(), "img" | "svg" | "picture" | "video"))
+}
+
+/// The language of the code block from the `language-*` or `lang-*` class.
+fn code_language(element: &ElementRef) -> Option<String> {
+    let code = element
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|el| el.value().name() == "code");
+
+    std::iter::once(element)
+        .chain(code.as_ref())
+        .flat_map(|el| el.value().classes())
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+                .filter(|language| !language.is_empty())
+                .map(|language| language.to_string())
+        })
+}
+
+/// Render the code block as a fence. The fence is longer than the backticks of the code.
+fn code_block(element: &ElementRef) -> String {
+    let code: String = element.text().collect();
+    let code = code.trim_matches('\n');
+    let mut longest = 0;
+    let mut current = 0;
+
+    for c in code.chars() {
+        if c == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+
+    let fence = "`".repeat(longest.max(2) + 1);
+
+    format!(
+        "{}{}\n{}\n{}",
+        fence,
+        code_language(element).unwrap_or_default(),
+        code,
+        fence
+    )
+}
+
+/// The node is an inline element.
+fn is_inline(node: Option<&Node>) -> bool {
+    matches!(node, Some(Node::Element(element)) if INLINE_ELEMENTS.contains(&element.name()))
+}
+
+/// Remove the placeholder characters from the text and the attributes. Returns `true` when the document changed.
+fn strip_placeholders(document: &mut Html) -> bool {
+    let ids: Vec<_> = document
+        .tree
+        .nodes()
+        .filter(|node| match node.value() {
+            Node::Text(text) => text.contains(is_placeholder),
+            Node::Element(element) => element
+                .attrs()
+                .any(|(_, value)| value.contains(is_placeholder)),
+            _ => false,
+        })
+        .map(|node| node.id())
+        .collect();
+
+    for id in ids.iter() {
+        if let Some(mut node) = document.tree.get_mut(*id) {
+            match node.value() {
+                Node::Text(text) => {
+                    text.text = text.replace(is_placeholder, "").as_str().into();
+                }
+                Node::Element(element) => {
+                    for value in element.attrs.values_mut() {
+                        *value = value.replace(is_placeholder, "").as_str().into();
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    !ids.is_empty()
+}
+
+/// Replace the whitespace of the text next to the inline elements with the placeholder. Returns `true` when the text changed.
+fn keep_inline_spaces(document: &mut Html) -> bool {
+    let changes: Vec<_> = document
+        .tree
+        .root()
+        .descendants()
+        .filter_map(|node| {
+            let text = match node.value() {
+                Node::Text(text) => &text.text,
+                _ => return None,
+            };
+
+            if node.ancestors().any(|ancestor| {
+                matches!(ancestor.value(), Node::Element(element) if matches!(element.name(), "pre" | "code" | "script" | "style"))
+            }) {
+                return None;
+            }
+
+            let leading = is_inline(node.prev_sibling().map(|node| node.value())) && text.starts_with(char::is_whitespace);
+            let trailing = is_inline(node.next_sibling().map(|node| node.value())) && text.ends_with(char::is_whitespace);
+
+            if !leading && !trailing {
+                return None;
+            }
+
+            let trimmed = text.trim();
+
+            let text = if trimmed.is_empty() {
+                SPACE_PLACEHOLDER.to_string()
+            } else {
+                let mut text = String::with_capacity(trimmed.len() + 6);
+                if leading {
+                    text.push(SPACE_PLACEHOLDER);
+                }
+                text.push_str(trimmed);
+                if trailing {
+                    text.push(SPACE_PLACEHOLDER);
+                }
+                text
+            };
+
+            Some((node.id(), text))
+        })
+        .collect();
+
+    let changed = !changes.is_empty();
+
+    for (id, text) in changes {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            *node.value() = Node::Text(Text {
+                text: text.as_str().into(),
+            });
+        }
+    }
+
+    changed
+}
+
+/// Apply the options that need the html elements. Returns the html when it changed with the rendered blocks.
+fn prepare_html(html: &str, renderer: &Renderer, blocks: &mut Vec<String>) -> Option<String> {
+    let mut document = Html::parse_document(html);
+    let stripped = strip_placeholders(&mut document);
+    let spaces = keep_inline_spaces(&mut document);
+
+    let actions: Vec<_> = {
+        let mut actions = Vec::new();
+        renderer.visit(document.root_element(), &mut actions);
+        actions
+            .into_iter()
+            .map(|(element, action)| (element.id(), action))
+            .collect()
+    };
+
+    if actions.is_empty() && !spaces && !stripped {
+        return None;
+    }
+
+    for (id, action) in actions {
+        match action {
+            Action::Replace(block) => {
+                let children: Vec<_> = match document.tree.get(id) {
+                    Some(node) => node.children().map(|child| child.id()).collect(),
+                    _ => continue,
+                };
+
+                for child in children {
+                    document.remove_node(child);
+                }
+
+                if let Some(mut node) = document.tree.get_mut(id) {
+                    let placeholder = format!("{}{}{}", BLOCK_START, blocks.len(), BLOCK_END);
+
+                    *node.value() = Node::Text(Text {
+                        text: placeholder.as_str().into(),
+                    });
+                    blocks.push(block);
+                }
+            }
+            Action::Remove => document.remove_node(id),
+            Action::RemoveAlt => {
+                if let Some(mut node) = document.tree.get_mut(id) {
+                    if let Node::Element(element) = node.value() {
+                        element.attrs.retain(|name, _| &*name.local != "alt");
+                    }
+                }
+            }
+        }
+    }
+
+    Some(document.html())
+}
+
+/// Replace the placeholders with the rendered blocks on their own lines.
+fn replace_blocks(markdown: &str, blocks: &[String]) -> String {
+    if blocks.is_empty() {
+        return markdown.into();
+    }
+
+    let mut output = String::with_capacity(markdown.len());
+    let mut rest = markdown;
+
+    while let Some(start) = rest.find(BLOCK_START) {
+        let after = &rest[start + BLOCK_START.len_utf8()..];
+        let digits = after.find(BLOCK_END).unwrap_or_default();
+
+        match after[..digits]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| blocks.get(index))
+        {
+            Some(block) => {
+                output.push_str(rest[..start].trim_end());
+                if !output.is_empty() && !output.ends_with('\n') {
+                    output.push('\n');
+                }
+                output.push_str(block);
+                output.push('\n');
+                rest = after[digits + BLOCK_END.len_utf8()..].trim_start();
+            }
+            _ => {
+                output.push_str(&rest[..start + BLOCK_START.len_utf8()]);
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(rest);
+
+    output.trim_end().into()
+}
+
+/// The line opens or closes a code fence.
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// Convert the inline links to reference links with the definitions at the end. The links in the code blocks are kept.
+fn reference_links(markdown: &str) -> String {
+    let mut references: HashMap<String, usize> = HashMap::new();
+    let mut definitions: Vec<String> = Vec::new();
+    let mut output = String::with_capacity(markdown.len());
+    let mut in_code = false;
+
+    for (i, line) in markdown.split('\n').enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+
+        if is_fence(line) {
+            in_code = !in_code;
+        }
+
+        if in_code || is_fence(line) {
+            output.push_str(line);
+            continue;
+        }
+
+        let line = MARKDOWN_LINK.replace_all(line, |caps: &regex::Captures| {
+            if !caps[1].is_empty() {
+                return caps[0].to_string();
+            }
+
+            let url = caps.get(3).map(|m| m.as_str()).unwrap_or_default();
+            let next = references.len() + 1;
+            let index = *references.entry(url.to_string()).or_insert_with(|| {
+                definitions.push(url.to_string());
+                next
+            });
+
+            format!("[{}][{}]", &caps[2], index)
+        });
+
+        output.push_str(&line);
+    }
+
+    if !definitions.is_empty() {
+        output.push_str("\n\n");
+
+        for (i, url) in definitions.iter().enumerate() {
+            if i > 0 {
+                output.push('\n');
+            }
+            output.push_str(&format!("[{}]: {}", i + 1, url));
+        }
+    }
+
+    output
+}
+
+/// Wrap the lines of text at the width. The code blocks, tables, headings and reference definitions are kept.
+fn wrap_lines(markdown: &str, width: usize) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut in_code = false;
+
+    for (i, line) in markdown.split('\n').enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+
+        let fence = is_fence(line);
+
+        if fence {
+            in_code = !in_code;
+        }
+
+        let trimmed = line.trim_start();
+
+        if in_code
+            || fence
+            || line.chars().count() <= width
+            || trimmed.starts_with('|')
+            || trimmed.starts_with('#')
+            || (trimmed.starts_with('[') && trimmed.contains("]: "))
+        {
+            output.push_str(line);
+            continue;
+        }
+
+        let (prefix, indent) = match LINE_PREFIX.captures(line) {
+            Some(caps) => {
+                let quote = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+                let marker = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
+
+                (
+                    format!("{}{}", quote, marker),
+                    format!("{}{}", quote, " ".repeat(marker.chars().count())),
+                )
+            }
+            _ => (String::new(), String::new()),
+        };
+
+        let mut current = prefix.clone();
+        let mut current_width = current.chars().count();
+        let mut empty = true;
+
+        for word in line[prefix.len()..].split_whitespace() {
+            let word_width = word.chars().count();
+
+            if !empty && current_width + 1 + word_width > width {
+                output.push_str(&current);
+                output.push('\n');
+                current = indent.clone();
+                current_width = current.chars().count();
+                empty = true;
+            }
+
+            if !empty {
+                current.push(' ');
+                current_width += 1;
+            }
+
+            current.push_str(word);
+            current_width += word_width;
+            empty = false;
+        }
+
+        output.push_str(&current);
+    }
+
+    output
+}
+
+/// Convert the html to markdown with the rendering options.
+pub fn rewrite_markdown(
+    html: &str,
+    commonmark: bool,
+    url: &Option<Url>,
+    custom_ignore: &Option<HashSet<String>>,
+    options: &MarkdownOptions,
+) -> String {
+    let renderer = Renderer {
+        commonmark,
+        url,
+        custom_ignore,
+        options,
+    };
+    let mut blocks = Vec::new();
+    let prepared = prepare_html(html, &renderer, &mut blocks);
+    let markdown = html2md::rewrite_html_custom_with_url(
+        prepared.as_deref().unwrap_or(html),
+        custom_ignore,
+        commonmark,
+        url,
+    );
+    let markdown = match prepared {
+        Some(_) => replace_blocks(&markdown, &blocks).replace(SPACE_PLACEHOLDER, " "),
+        _ => markdown,
+    };
+
+    let markdown = match options.links {
+        LinkStyle::Reference => reference_links(&markdown),
+        LinkStyle::Inline => markdown,
+    };
+
+    match options.max_width {
+        Some(width) if width > 0 => wrap_lines(&markdown, width),
+        _ => markdown,
+    }
+}
@@ -2,6 +2,19 @@
 pub mod chunking;
 /// Content utils.
 pub mod content;
+/// Content language and size info.
+pub mod content_info;
+/// PDF, DOCX and XLSX text extraction.
+#[cfg(feature = "documents")]
+pub mod documents;
+/// Main content extraction.
+pub mod main_content;
+/// Markdown rendering options.
+pub mod markdown;
+/// Streaming markdown and text transformation.
+pub mod streaming;
+/// Table extraction.
+pub mod tables;
 /// Text extraction.
 pub mod text_extract;
 
@@ -119,6 +132,297 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transformations_markdown_options() {
+        use crate::transformation::markdown::{
+            HeadingStyle, LinkStyle, MarkdownOptions, TableStyle,
+        };
+        use maud::html;
+
+        let markup = html! {
+            h1 { "Options" }
+            p { "Read the " a href="https://spider.cloud/docs" { "docs" } " and the " a href="https://spider.cloud/guides" { "guides" } " for the crawler and the transformations." }
+            a href="https://spider.cloud/empty" {}
+            img src="https://spider.cloud/logo.png" alt="Spider logo";
+            table {
+                tr { th { "Name" } th { "Stars" } }
+                tr { td { "spider" } td { "1|000" } }
+            }
+            pre { code class="language-rust" { "let spider = \"crawl\";\nspider.len();" } }
+        }
+        .into_string();
+
+        let mut conf = content::TransformConfig::default();
+        let mut page_response = PageResponse::default();
+
+        page_response.content = Some(Box::new(markup.into()));
+        let page = build_with_parse("https://spider.cloud", page_response);
+
+        conf.return_format = ReturnFormat::Markdown;
+        conf.markdown_options = Some(MarkdownOptions::default());
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert!(
+            content.contains("![Spider logo](https://spider.cloud/logo.png)\n| Name | Stars |\n| --- | --- |\n| spider | 1\\|000 |\n```rust\nlet spider = \"crawl\";\nspider.len();\n```"),
+            "The markdown tables and code blocks are invalid"
+        );
+
+        conf.markdown_options = Some(MarkdownOptions {
+            tables: TableStyle::PlainText,
+            links: LinkStyle::Reference,
+            image_alt: false,
+            headings: HeadingStyle::Setext,
+            code_language: true,
+            max_width: Some(42),
+            strip_empty_links: true,
+        });
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert!(
+            content.starts_with("Options\n=======\nRead the [docs][1] and the [guides][2] for\nthe crawler and the transformations.\n![](https://spider.cloud/logo.png)\nName Stars\nspider 1|000\n"),
+            "The markdown options are invalid"
+        );
+        assert!(
+            content.ends_with("[1]: https://spider.cloud/docs\n[2]: https://spider.cloud/guides"),
+            "The reference links are invalid"
+        );
+        assert!(!content.contains("empty"), "The empty link is not removed");
+    }
+
+    #[test]
+    fn test_transformations_markdown_options_placeholders() {
+        use crate::transformation::markdown::MarkdownOptions;
+        use maud::html;
+
+        let markup = html! {
+            p { "SPIDERMARKDOWNSPACE and SPIDERMARKDOWNBLOCK0X stay " a href="https://spider.cloud" { "here" } "\u{E000}0\u{E001}\u{E002}." }
+            table {
+                tr { th { "Name" } }
+                tr { td { "spider" } }
+            }
+        }
+        .into_string();
+
+        let mut conf = content::TransformConfig::default();
+        let mut page_response = PageResponse::default();
+
+        page_response.content = Some(Box::new(markup.into()));
+        let page = build_with_parse("https://spider.cloud", page_response);
+
+        conf.return_format = ReturnFormat::Markdown;
+        conf.markdown_options = Some(MarkdownOptions::default());
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert_eq!(
+            content,
+            "SPIDERMARKDOWNSPACE and SPIDERMARKDOWNBLOCK0X stay [here](https://spider.cloud)0.\n| Name |\n| --- |\n| spider |",
+            "The page text is changed by the placeholders"
+        );
+    }
+
+    #[test]
+    fn test_transformations_text_citations() {
+        use maud::html;
+
+        let markup = html! {
+            p { "Read the " a href="/docs?a=1&b=2" { "docs" } " and the " a href="https://github.com/spider-rs" { "source" } "." }
+            p { "The " a href="/docs?a=1&b=2" { "docs" } " again and a " a href="#top" { "fragment" } "." }
+        }
+        .into_string();
+
+        let mut conf = content::TransformConfig::default();
+        let mut page_response = PageResponse::default();
+
+        page_response.content = Some(Box::new(markup.into()));
+        let page = build_with_parse("https://spider.cloud/blog/", page_response);
+
+        conf.return_format = ReturnFormat::Text;
+        conf.citations = true;
+
+        let cited = content::transform_content_with_links(&page, &conf, &None, &None, &None);
+
+        assert_eq!(
+            cited.text,
+            "Read the docs [1] and the source [2]. The docs [1] again and a fragment.\n\n[1] https://spider.cloud/docs?a=1&b=2\n[2] https://github.com/spider-rs"
+        );
+        assert_eq!(cited.links.len(), 2);
+        assert_eq!(cited.links[0].url, "https://spider.cloud/docs?a=1&b=2");
+        assert_eq!(cited.links[0].text, "docs");
+        assert_eq!(cited.links[1].index, 2);
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert_eq!(content, cited.text);
+    }
+
+    #[test]
+    fn test_transformations_text_citations_entities() {
+        let cited = crate::transformation::text_extract::extract_text_with_links(
+            r#"<p>Search <a href="/search?q=&amp;lt;a&amp;gt;&amp;amp;page=2">tags</a>, then read.</p>"#,
+            &spider::url::Url::parse("https://spider.cloud/").ok(),
+            &None,
+        );
+
+        assert_eq!(
+            cited.text,
+            "Search tags [1], then read.\n\n[1] https://spider.cloud/search?q=&lt;a&gt;&amp;page=2"
+        );
+
+        let cited = crate::transformation::text_extract::extract_text_with_links(
+            r#"<p><a href="/search?q=a&#38;page=2&#x26;sort=new">Next</a></p>"#,
+            &spider::url::Url::parse("https://spider.cloud/").ok(),
+            &None,
+        );
+
+        assert_eq!(
+            cited.text,
+            "Next [1]\n\n[1] https://spider.cloud/search?q=a&page=2&sort=new"
+        );
+    }
+
+    #[test]
+    fn test_content_info_language() {
+        use crate::transformation::content_info::{
+            content_info, detect_language, html_content_info, ContentFilter,
+        };
+
+        assert_eq!(
+            detect_language("The crawler is fast and the transformations are ready for the index.")
+                .as_deref(),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language("Der Crawler ist schnell und die Daten sind nicht auf dem Server.")
+                .as_deref(),
+            Some("de")
+        );
+        assert_eq!(
+            detect_language("これはウェブクローラーです。").as_deref(),
+            Some("ja")
+        );
+        assert_eq!(
+            detect_language("Это быстрый веб-краулер.").as_deref(),
+            Some("ru")
+        );
+        assert_eq!(detect_language("1234 5678"), None);
+
+        let markup = r#"<html lang="de-DE"><body><article><p>The crawler is fast and the transformations are ready for the index of the pages.</p></article></body></html>"#;
+
+        let info = html_content_info(markup, Some("fr"));
+
+        assert_eq!(info.language.as_deref(), Some("de"));
+        assert_eq!(info.content_language.as_deref(), Some("fr"));
+        assert_eq!(info.detected_language.as_deref(), Some("en"));
+        assert_eq!(info.word_count, 15);
+        assert_eq!(info.reading_time_secs, 5);
+        assert!(info.main_content_ratio > 0.9);
+
+        let mut page_response = PageResponse::default();
+        page_response.content = Some(Box::new(markup.into()));
+        let page = build_with_parse("https://spider.cloud", page_response);
+
+        let info = content_info(&page);
+
+        assert_eq!(info.encoding.as_deref(), Some("UTF-8"));
+        assert!(ContentFilter::default()
+            .with_languages(&["en", "de"])
+            .matches(&info));
+        assert!(!ContentFilter::default()
+            .with_languages(&["en"])
+            .matches(&info));
+        assert!(!ContentFilter::default().with_min_words(100).matches(&info));
+
+        let mut conf = content::TransformConfig::default();
+        let content_filter = Some(ContentFilter::default().with_languages(&["de"]));
+
+        conf.return_format = ReturnFormat::Text;
+
+        let (text, info) = content::transform_content_with_info(
+            &page,
+            &conf,
+            &content_filter,
+            &None,
+            &None,
+            &None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            text,
+            content::transform_content(&page, &conf, &None, &None, &None)
+        );
+        assert_eq!(info.word_count, 15);
+
+        let content_filter = Some(ContentFilter::default().with_languages(&["en"]));
+
+        assert!(content::transform_content_with_info(
+            &page,
+            &conf,
+            &content_filter,
+            &None,
+            &None,
+            &None
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_transformations_tables() {
+        use crate::transformation::tables::{extract_page_tables, extract_tables};
+
+        let markup = r#"<html><body>
+            <table>
+                <caption>Crawlers</caption>
+                <thead>
+                    <tr><th rowspan="2">Name</th><th colspan="2">Speed</th></tr>
+                    <tr><th>Pages</th><th>Time</th></tr>
+                </thead>
+                <tbody>
+                    <tr><td rowspan="2">spider</td><td>1,000</td><td>2s</td></tr>
+                    <tr><td>200</td><td>"fast"</td></tr>
+                    <tr><td colspan="3">Total <table><tr><td>nested</td></tr></table></td></tr>
+                </tbody>
+            </table>
+            <table><tr><td>a</td><td>b</td></tr></table>
+        </body></html>"#;
+
+        let tables = extract_tables(markup);
+
+        assert_eq!(tables.len(), 3);
+
+        let table = &tables[0];
+
+        assert_eq!(table.caption.as_deref(), Some("Crawlers"));
+        assert_eq!(table.headers, vec!["Name", "Speed Pages", "Speed Time"]);
+        assert_eq!(table.rows[0], vec!["spider", "1,000", "2s"]);
+        assert_eq!(table.rows[1], vec!["spider", "200", "\"fast\""]);
+        assert_eq!(table.rows[2], vec!["Total nested"; 3]);
+        assert_eq!(tables[1].rows, vec![vec!["nested"]]);
+        assert!(tables[2].headers.is_empty());
+
+        assert_eq!(
+            table.to_csv().lines().take(3).collect::<Vec<_>>(),
+            vec![
+                "Name,Speed Pages,Speed Time",
+                "spider,\"1,000\",2s",
+                "spider,200,\"\"\"fast\"\"\"",
+            ]
+        );
+        assert!(table
+            .to_json()
+            .starts_with(r#"[{"Name":"spider","Speed Pages":"1,000","Speed Time":"2s"}"#));
+        assert_eq!(tables[2].to_json(), r#"[["a","b"]]"#);
+
+        let mut page_response = PageResponse::default();
+        page_response.content = Some(Box::new(markup.into()));
+        let page = build_with_parse("https://spider.cloud", page_response);
+
+        assert_eq!(extract_page_tables(&page), tables);
+    }
+
     #[test]
     fn test_transformations_root_selector() {
         let markup = template().into_string();
@@ -220,6 +524,364 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_transformations_streaming() {
+        use crate::transformation::streaming::{transform_page_stream, StreamingTransformer};
+        use maud::html;
+
+        let markup = html! {
+            h1 { "Streaming" }
+            @for i in 0..20 {
+                h2 { "Section " (i) }
+                p { "The paragraph " (i) " is here with a " a href="/docs" { "link" } "." }
+                ul { li { "One" } li { "Two" } }
+            }
+            pre { "let spider = true;" }
+        }
+        .into_string();
+        let url = spider::url::Url::parse("https://spider.cloud").ok();
+
+        let mut page_response = PageResponse::default();
+        page_response.content = Some(Box::new(markup.clone().into()));
+        let page = build_with_parse("https://spider.cloud", page_response);
+
+        let mut chunks = Vec::new();
+        let transformer = StreamingTransformer::new(ReturnFormat::Markdown, url.clone());
+        transform_page_stream(&page, transformer, 64, |chunk| chunks.push(chunk))
+            .await
+            .unwrap();
+
+        let expected = html2md::rewrite_html_custom_with_url(&markup, &None, false, &url);
+
+        assert!(chunks.len() > 1, "The markdown is not streamed");
+        assert_eq!(chunks.concat(), expected);
+
+        let mut chunks = Vec::new();
+        let transformer = StreamingTransformer::new(ReturnFormat::Text, None);
+        transform_page_stream(&page, transformer, 64, |chunk| chunks.push(chunk))
+            .await
+            .unwrap();
+
+        assert!(chunks.len() > 1, "The text is not streamed");
+        assert_eq!(chunks.concat(), content::transform_text(&markup));
+    }
+
+    #[test]
+    fn test_chunk_by_character_length_multibyte() {
+        use crate::transformation::chunking::{chunk_text, ChunkingAlgorithm};
+
+        let chunks = chunk_text("héllo wörld", ChunkingAlgorithm::ByCharacterLength(5));
+
+        assert_eq!(chunks, vec!["héllo", " wörl", "d"]);
+    }
+
+    #[test]
+    fn test_chunk_by_tokens_overlap() {
+        use crate::transformation::chunking::{chunk_by_tokens, ChunkOptions, WordTokenizer};
+
+        let text = "one two three four five six seven";
+        let options = ChunkOptions::new(3)
+            .with_overlap(1)
+            .with_url(Some("https://spider.cloud".into()));
+        let chunks = chunk_by_tokens(text, &WordTokenizer, &options);
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+
+        assert_eq!(
+            texts,
+            vec!["one two three", "three four five", "five six seven"]
+        );
+
+        for chunk in chunks {
+            assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+            assert_eq!(chunk.url.as_deref(), Some("https://spider.cloud"));
+        }
+    }
+
+    #[test]
+    fn test_chunk_markdown_headings() {
+        use crate::transformation::chunking::{chunk_markdown, ChunkOptions, WordTokenizer};
+
+        let text = "intro\n# Guide\nsetup steps\n## Install\n```\n# not a heading\n```\n## Usage\nrun it\n# FAQ\nask";
+        let chunks = chunk_markdown(text, &WordTokenizer, &ChunkOptions::new(100));
+        let headings: Vec<Vec<String>> = chunks.iter().map(|c| c.headings.clone()).collect();
+
+        assert_eq!(
+            headings,
+            vec![
+                vec![],
+                vec!["Guide".to_string()],
+                vec!["Guide".to_string(), "Install".to_string()],
+                vec!["Guide".to_string(), "Usage".to_string()],
+                vec!["FAQ".to_string()],
+            ]
+        );
+        assert_eq!(chunks[2].text, "## Install\n```\n# not a heading\n```");
+        assert_eq!(&text[chunks[4].start..chunks[4].end], "# FAQ\nask");
+    }
+
+    /// the article template with boilerplate to re-use
+    fn article_template() -> String {
+        use maud::{html, DOCTYPE};
+
+        let paragraph = "Spider crawls the web at scale with streaming and concurrency built in for every page.";
+
+        html! {
+            (DOCTYPE)
+            title { "Article" }
+            nav { a href="/" { "Home" } a href="/docs" { "Docs" } a href="/blog" { "Blog" } }
+            div class="layout" {
+                div class="sidebar" {
+                    a href="/a" { "Related post one" } a href="/b" { "Related post two" }
+                }
+                div class="post-body" {
+                    h1 { "Crawling at scale" }
+                    p { (paragraph) }
+                    p { (paragraph) }
+                    div class="share-buttons" { a href="/share" { "Share this article" } }
+                    p { (paragraph) }
+                }
+            }
+            footer { "Copyright Spider Cloud all rights reserved and more footer text here" }
+        }
+        .into_string()
+    }
+
+    #[test]
+    fn test_main_content_scoring() {
+        use crate::transformation::main_content::{
+            extract_main_content, ExtractionMethod, MainContentConfig,
+        };
+
+        let html = article_template();
+        let url = spider::url::Url::parse("https://spider.cloud/blog/crawl").unwrap();
+        let content = extract_main_content(&html, Some(&url), &MainContentConfig::default());
+
+        assert_eq!(content.method, ExtractionMethod::Scoring);
+        assert!(content.confidence > 0.9);
+        assert!(content.html.starts_with("<div class=\"post-body\">"));
+        assert!(content.html.contains("Crawling at scale"));
+        assert!(!content.html.contains("Share this article"));
+        assert!(!content.html.contains("Related post"));
+        assert!(!content.html.contains("Copyright"));
+    }
+
+    #[test]
+    fn test_main_content_domain_selector() {
+        use crate::transformation::main_content::{
+            extract_main_content, ExtractionMethod, MainContentConfig,
+        };
+
+        let html = article_template();
+        let url = spider::url::Url::parse("https://docs.spider.cloud/blog/crawl").unwrap();
+        let select_config = SelectorConfiguration {
+            root_selector: Some("h1".into()),
+            ..Default::default()
+        };
+        let config =
+            MainContentConfig::default().with_domain_selector("spider.cloud", select_config);
+        let content = extract_main_content(&html, Some(&url), &config);
+
+        assert_eq!(content.method, ExtractionMethod::Selector);
+        assert_eq!(content.html, "<h1>Crawling at scale</h1>");
+    }
+
+    #[test]
+    fn test_transformations_main_content_config() {
+        use crate::transformation::main_content::MainContentConfig;
+
+        let select_config = SelectorConfiguration {
+            root_selector: Some("h1".into()),
+            ..Default::default()
+        };
+        let mut conf = content::TransformConfig::default();
+        let mut page_response = PageResponse::default();
+
+        page_response.content = Some(Box::new(article_template().into()));
+        let page = build_with_parse("https://docs.spider.cloud/blog/crawl", page_response);
+
+        conf.return_format = ReturnFormat::Text;
+        conf.main_content = true;
+
+        let main_content_config =
+            Some(MainContentConfig::default().with_domain_selector("spider.cloud", select_config));
+
+        let content = content::transform_content_with_main_content(
+            &page,
+            &conf,
+            &main_content_config,
+            &None,
+            &None,
+            &None,
+        );
+
+        assert_eq!(content, "Crawling at scale");
+    }
+
+    /// zip the files into an archive
+    #[cfg(feature = "documents")]
+    fn zip_files(files: &[(&str, &str)]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+
+        for (name, content) in files {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[cfg(feature = "documents")]
+    #[test]
+    fn test_transformations_docx() {
+        let docx = zip_files(&[(
+            "word/document.xml",
+            r#"<w:document xmlns:w="w"><w:body>
+                <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Spider</w:t></w:r></w:p>
+                <w:p><w:r><w:t>Fast &amp; safe.</w:t></w:r><w:r><w:br w:type="page"/></w:r></w:p>
+                <w:p><w:r><w:t>Second page</w:t></w:r></w:p>
+            </w:body></w:document>"#,
+        )]);
+
+        let mut conf = content::TransformConfig::default();
+        let mut page_response = PageResponse::default();
+
+        page_response.content = Some(Box::new(docx));
+        let page = build_with_parse("https://example.com/doc.docx", page_response);
+
+        conf.return_format = ReturnFormat::Markdown;
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert_eq!(
+            content,
+            "## Page 1\n\n# Spider\nFast & safe.\n\n## Page 2\n\nSecond page"
+        );
+
+        conf.return_format = ReturnFormat::Text;
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert_eq!(content, "Spider\nFast & safe.\n\x0c\nSecond page");
+    }
+
+    #[cfg(feature = "documents")]
+    #[test]
+    fn test_transformations_xlsx() {
+        let xlsx = zip_files(&[
+            (
+                "[Content_Types].xml",
+                r#"<?xml version="1.0" encoding="UTF-8"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#,
+            ),
+            (
+                "_rels/.rels",
+                r#"<?xml version="1.0" encoding="UTF-8"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#,
+            ),
+            (
+                "xl/workbook.xml",
+                r#"<?xml version="1.0" encoding="UTF-8"?><workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Prices" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+            ),
+            (
+                "xl/_rels/workbook.xml.rels",
+                r#"<?xml version="1.0" encoding="UTF-8"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+            ),
+            (
+                "xl/worksheets/sheet1.xml",
+                r#"<?xml version="1.0" encoding="UTF-8"?><worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>Plan</t></is></c><c r="B1" t="inlineStr"><is><t>Price</t></is></c></row><row r="2"><c r="A2" t="inlineStr"><is><t>Pro</t></is></c><c r="B2"><v>9</v></c></row></sheetData></worksheet>"#,
+            ),
+        ]);
+
+        let mut conf = content::TransformConfig::default();
+        let mut page_response = PageResponse::default();
+
+        page_response.content = Some(Box::new(xlsx));
+        let page = build_with_parse("https://example.com/prices.xlsx", page_response);
+
+        conf.return_format = ReturnFormat::Markdown;
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert_eq!(
+            content,
+            "## Prices\n\n| Plan | Price |\n| --- | --- |\n| Pro | 9 |\n"
+        );
+    }
+
+    #[cfg(feature = "documents")]
+    #[test]
+    fn test_transformations_pdf_pages() {
+        use lopdf::content::{Content, Operation};
+        use lopdf::{dictionary, Document, Object, Stream};
+
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Courier",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+        let mut kids = vec![];
+
+        for text in ["First page", "Second page"] {
+            let content = Content {
+                operations: vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(text)]),
+                    Operation::new("ET", vec![]),
+                ],
+            };
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            kids.push(
+                doc.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                    "Contents" => content_id,
+                })
+                .into(),
+            );
+        }
+
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => 2,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+
+        let mut pdf = Vec::new();
+        doc.save_to(&mut pdf).unwrap();
+
+        let mut conf = content::TransformConfig::default();
+        let mut page_response = PageResponse::default();
+
+        page_response.content = Some(Box::new(pdf));
+        let page = build_with_parse("https://example.com/doc.pdf", page_response);
+
+        conf.return_format = ReturnFormat::Markdown;
+
+        let content = content::transform_content(&page, &conf, &None, &None, &None);
+
+        assert_eq!(
+            content,
+            "## Page 1\n\nFirst page\n\n## Page 2\n\nSecond page"
+        );
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_transformations_pdf_handling() {
@@ -0,0 +1,308 @@
+use super::content::ReturnFormat;
+use html2md::extended::sifter::WhitespaceSifterBytes;
+use lol_html::errors::RewritingError;
+use lol_html::html_content::TextType;
+use lol_html::{element, text, OutputSink};
+use spider::page::Page;
+use spider::tokio_stream::{Stream, StreamExt};
+use spider::url::Url;
+use std::sync::{Arc, Mutex};
+
+/// The rewriter output not emitted yet.
+type Pending = Arc<Mutex<Vec<u8>>>;
+
+/// The output sink of the rewriter. The text handlers write to the pending buffer directly so the html is dropped.
+struct PendingSink(Option<Pending>);
+
+impl OutputSink for PendingSink {
+    fn handle_chunk(&mut self, chunk: &[u8]) {
+        if let Some(pending) = &self.0 {
+            if let Ok(mut pending) = pending.lock() {
+                pending.extend_from_slice(chunk);
+            }
+        }
+    }
+}
+
+/// The output of the transformer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamingOutput {
+    /// The html2md rewriter output.
+    Markdown,
+    /// The text of the elements.
+    Text,
+    /// The html as is.
+    Raw,
+}
+
+/// Transform html chunks into markdown or text as the bytes arrive. The markdown is emitted by complete lines, the text by complete words and both match the whitespace cleaning of `transform_content`.
+///
+/// `Markdown` and `CommonMark` use the markdown rewriter, `Text` and `Html2Text` use the text extraction and the other formats pass the html through. Readability, the main content and the selectors need the whole document and are not applied.
+pub struct StreamingTransformer {
+    /// The lol_html rewriter.
+    rewriter: lol_html::send::HtmlRewriter<'static, PendingSink>,
+    /// The html after the last tag of the chunks. The text is written whole since the handlers trim each text chunk.
+    carry: Vec<u8>,
+    /// The output emitter.
+    emitter: Emitter,
+}
+
+/// Emit the complete output of the rewriter.
+struct Emitter {
+    /// The rewriter output not emitted yet.
+    pending: Pending,
+    /// The output type.
+    output: StreamingOutput,
+    /// Output was emitted already.
+    emitted: bool,
+}
+
+impl StreamingTransformer {
+    /// A new transformer for the return format. The url is used to make the markdown links absolute.
+    pub fn new(return_format: ReturnFormat, url: Option<Url>) -> Self {
+        Self::with_ignore(return_format, url, &None)
+    }
+
+    /// A new transformer for the return format removing the elements that match the css selectors.
+    pub fn with_ignore(
+        return_format: ReturnFormat,
+        url: Option<Url>,
+        custom_ignore: &Option<std::collections::HashSet<String>>,
+    ) -> Self {
+        let pending: Pending = Default::default();
+
+        let (output, settings) = match return_format {
+            ReturnFormat::Markdown | ReturnFormat::CommonMark => (
+                StreamingOutput::Markdown,
+                html2md::rewriter::writer::get_rewriter_settings_send(
+                    return_format == ReturnFormat::CommonMark,
+                    custom_ignore,
+                    url,
+                ),
+            ),
+            ReturnFormat::Text | ReturnFormat::Html2Text => (
+                StreamingOutput::Text,
+                text_settings(pending.clone(), custom_ignore),
+            ),
+            _ => (
+                StreamingOutput::Raw,
+                lol_html::send::Settings::new_for_handler_types(),
+            ),
+        };
+
+        let sink = PendingSink(if output == StreamingOutput::Text {
+            None
+        } else {
+            Some(pending.clone())
+        });
+
+        Self {
+            rewriter: lol_html::send::HtmlRewriter::new(settings, sink),
+            carry: Vec::new(),
+            emitter: Emitter {
+                pending,
+                output,
+                emitted: false,
+            },
+        }
+    }
+
+    /// Write the next chunk of html. Returns the output completed by the chunk, this can be empty.
+    pub fn write(&mut self, chunk: &[u8]) -> Result<String, RewritingError> {
+        match chunk.iter().rposition(|b| *b == b'>') {
+            Some(position) => {
+                if self.carry.is_empty() {
+                    self.rewriter.write(&chunk[..=position])?;
+                } else {
+                    self.carry.extend_from_slice(&chunk[..=position]);
+                    self.rewriter.write(&self.carry)?;
+                    self.carry.clear();
+                }
+                self.carry.extend_from_slice(&chunk[position + 1..]);
+            }
+            _ => self.carry.extend_from_slice(chunk),
+        }
+
+        Ok(self.emitter.take(false))
+    }
+
+    /// Finish the document. Returns the rest of the output.
+    pub fn end(self) -> Result<String, RewritingError> {
+        let Self {
+            mut rewriter,
+            carry,
+            mut emitter,
+        } = self;
+
+        if !carry.is_empty() {
+            rewriter.write(&carry)?;
+        }
+        rewriter.end()?;
+        Ok(emitter.take(true))
+    }
+}
+
+impl Emitter {
+    /// Take the complete output from the pending buffer.
+    fn take(&mut self, last: bool) -> String {
+        let mut pending = match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(_) => return Default::default(),
+        };
+
+        if self.output == StreamingOutput::Raw {
+            let valid = if last {
+                pending.len()
+            } else {
+                match std::str::from_utf8(&pending) {
+                    Ok(_) => pending.len(),
+                    Err(e) => e.valid_up_to(),
+                }
+            };
+            let block: Vec<u8> = pending.drain(..valid).collect();
+
+            return String::from_utf8_lossy(&block).into_owned();
+        }
+
+        // split before the whitespace of the last line break or word so the sifting of the blocks matches the whole output.
+        let split = if last {
+            pending.len()
+        } else {
+            let boundary = match self.output {
+                StreamingOutput::Markdown => pending.iter().rposition(|b| *b == b'\n'),
+                _ => pending.iter().rposition(|b| b.is_ascii_whitespace()),
+            };
+
+            match boundary {
+                Some(mut position) => {
+                    while position > 0 && pending[position - 1].is_ascii_whitespace() {
+                        position -= 1;
+                    }
+                    position
+                }
+                _ => 0,
+            }
+        };
+
+        if split == 0 {
+            return Default::default();
+        }
+
+        let block: Vec<u8> = pending.drain(..split).collect();
+        let sifted = block.sift_bytes();
+
+        if sifted.is_empty() {
+            return sifted;
+        }
+
+        let mut output = String::with_capacity(sifted.len() + 1);
+
+        if self.emitted {
+            if let Some(separator) = block.first().filter(|b| b.is_ascii_whitespace()) {
+                output.push(*separator as char);
+            }
+        }
+
+        output.push_str(&sifted);
+        self.emitted = true;
+
+        output
+    }
+}
+
+/// The rewriter settings of the text extraction writing to the pending buffer.
+fn text_settings(
+    pending: Pending,
+    custom_ignore: &Option<std::collections::HashSet<String>>,
+) -> lol_html::send::Settings<'static, 'static> {
+    let mut element_content_handlers = Vec::with_capacity(
+        1 + custom_ignore
+            .as_ref()
+            .map_or(0, |c| if c.is_empty() { 0 } else { 1 }),
+    );
+
+    if let Some(ignore) = custom_ignore {
+        let ignore_handler = element!(
+            ignore.iter().cloned().collect::<Vec<String>>().join(","),
+            |el| {
+                el.remove();
+                Ok(())
+            }
+        );
+
+        element_content_handlers.push(ignore_handler);
+    }
+
+    element_content_handlers.push(text!(
+        "*:not(script):not(style):not(svg):not(noscript):not(nav):not(footer)",
+        move |text| {
+            if let TextType::RCData | TextType::Data = text.text_type() {
+                if let Ok(mut extracted_text) = pending.lock() {
+                    let el_text = text.as_str().trim_start();
+
+                    if !el_text.is_empty() {
+                        if !extracted_text.ends_with(b" ") && !extracted_text.is_empty() {
+                            extracted_text.push(b' ');
+                        }
+                        extracted_text.extend_from_slice(el_text.as_bytes());
+                    }
+                    if text.text_type() == TextType::RCData {
+                        extracted_text.push(b'\n');
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    ));
+
+    lol_html::send::Settings {
+        element_content_handlers,
+        ..lol_html::send::Settings::new_for_handler_types()
+    }
+}
+
+/// Transform the html byte stream calling `on_chunk` with the output as it completes.
+pub async fn transform_stream<S, B, F>(
+    stream: S,
+    mut transformer: StreamingTransformer,
+    mut on_chunk: F,
+) -> Result<(), RewritingError>
+where
+    S: Stream<Item = B> + Unpin,
+    B: AsRef<[u8]>,
+    F: FnMut(String),
+{
+    let mut stream = stream;
+
+    while let Some(chunk) = stream.next().await {
+        let output = transformer.write(chunk.as_ref())?;
+
+        if !output.is_empty() {
+            on_chunk(output);
+        }
+    }
+
+    let output = transformer.end()?;
+
+    if !output.is_empty() {
+        on_chunk(output);
+    }
+
+    Ok(())
+}
+
+/// Transform the page html in chunks calling `on_chunk` with the output as it completes. Use this in the `subscribe` loop to write the output without building it in memory.
+pub async fn transform_page_stream<F>(
+    page: &Page,
+    transformer: StreamingTransformer,
+    chunk_size: usize,
+    on_chunk: F,
+) -> Result<(), RewritingError>
+where
+    F: FnMut(String),
+{
+    let chunks = page.get_html_bytes_u8().chunks(chunk_size.max(1));
+
+    transform_stream(spider::tokio_stream::iter(chunks), transformer, on_chunk).await
+}
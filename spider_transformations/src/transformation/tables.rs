@@ -0,0 +1,303 @@
+use scraper::{ElementRef, Html, Selector};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Serialize, Serializer};
+use spider::lazy_static::lazy_static;
+use spider::page::Page;
+
+lazy_static! {
+    /// The tables of the document.
+    static ref TABLE_SELECTOR: Selector = Selector::parse("table").expect("valid table selector");
+}
+
+/// The max rows or columns a cell can span.
+const MAX_SPAN: usize = 1000;
+
+/// A table of the page with the spans resolved into a grid.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table {
+    /// The table caption.
+    pub caption: Option<String>,
+    /// The column headers from the `thead` rows or a first row of `th` cells. Empty when the table has no header.
+    pub headers: Vec<String>,
+    /// The body rows. A spanned cell repeats its text in every slot it covers.
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// The number of columns of the widest row.
+    pub fn columns(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| row.len())
+            .chain(std::iter::once(self.headers.len()))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The table as RFC 4180 CSV with the headers as the first record.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+
+        if !self.headers.is_empty() {
+            write_csv_record(&mut csv, &self.headers);
+        }
+
+        for row in self.rows.iter() {
+            write_csv_record(&mut csv, row);
+        }
+
+        csv
+    }
+
+    /// The table as JSON. The rows are objects keyed by the headers or arrays of cells when the table has no header.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&Records(self)).unwrap_or_default()
+    }
+
+    /// The unique keys of the columns for the JSON records.
+    fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::with_capacity(self.headers.len());
+
+        for (i, header) in self.headers.iter().enumerate() {
+            let base = if header.is_empty() {
+                format!("column_{}", i + 1)
+            } else {
+                header.clone()
+            };
+            let mut key = base.clone();
+            let mut n = 1;
+
+            while keys.contains(&key) {
+                n += 1;
+                key = format!("{}_{}", base, n);
+            }
+
+            keys.push(key);
+        }
+
+        keys
+    }
+}
+
+/// The rows of the table serialized as records keeping the column order.
+struct Records<'a>(&'a Table);
+
+impl Serialize for Records<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let table = self.0;
+        let mut seq = serializer.serialize_seq(Some(table.rows.len()))?;
+
+        if table.headers.is_empty() {
+            for row in table.rows.iter() {
+                seq.serialize_element(row)?;
+            }
+        } else {
+            let keys = table.keys();
+
+            for row in table.rows.iter() {
+                seq.serialize_element(&Record(&keys, row))?;
+            }
+        }
+
+        seq.end()
+    }
+}
+
+/// A row keyed by the headers. The cells without a header are keyed by the column.
+struct Record<'a>(&'a [String], &'a [String]);
+
+impl Serialize for Record<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Record(keys, row) = self;
+        let mut map = serializer.serialize_map(Some(keys.len().max(row.len())))?;
+
+        for i in 0..keys.len().max(row.len()) {
+            let cell = row.get(i).map(|cell| cell.as_str()).unwrap_or_default();
+
+            match keys.get(i) {
+                Some(key) => map.serialize_entry(key, cell)?,
+                _ => map.serialize_entry(&format!("column_{}", i + 1), cell)?,
+            }
+        }
+
+        map.end()
+    }
+}
+
+/// Write the cells as a CSV record quoting the fields when needed.
+fn write_csv_record(csv: &mut String, cells: &[String]) {
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        if cell.contains([',', '"', '\r', '\n']) {
+            csv.push('"');
+            csv.push_str(&cell.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(cell);
+        }
+    }
+    csv.push_str("\r\n");
+}
+
+/// The rows of the table without the rows of the nested tables.
+pub(crate) fn table_rows<'b>(table: &ElementRef<'b>) -> Vec<ElementRef<'b>> {
+    let table_id = table.id();
+
+    table
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|el| el.value().name() == "tr")
+        .filter(|el| {
+            el.ancestors()
+                .filter_map(ElementRef::wrap)
+                .find(|a| a.value().name() == "table")
+                .is_some_and(|a| a.id() == table_id)
+        })
+        .collect()
+}
+
+/// The cells of the row.
+fn row_cells<'b>(row: &ElementRef<'b>) -> impl Iterator<Item = ElementRef<'b>> {
+    row.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+}
+
+/// The span attribute of the cell. A `rowspan` of 0 is returned as `None` and spans the rest of the table.
+pub(crate) fn cell_span(cell: &ElementRef, attr: &str) -> Option<usize> {
+    match cell
+        .value()
+        .attr(attr)
+        .and_then(|span| span.trim().parse::<usize>().ok())
+    {
+        Some(0) if attr == "rowspan" => None,
+        Some(span) => Some(span.clamp(1, MAX_SPAN)),
+        _ => Some(1),
+    }
+}
+
+/// The text of the element with the whitespace collapsed.
+fn collapsed_text(element: &ElementRef) -> String {
+    let mut text = String::new();
+
+    for word in element.text().flat_map(|t| t.split_whitespace()) {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(word);
+    }
+
+    text
+}
+
+/// The row is part of the `thead` of the table.
+fn in_thead(row: &ElementRef) -> bool {
+    row.ancestors()
+        .filter_map(ElementRef::wrap)
+        .take_while(|a| a.value().name() != "table")
+        .any(|a| a.value().name() == "thead")
+}
+
+/// Extract the table resolving the `colspan` and `rowspan` of the cells.
+pub fn extract_table(table: &ElementRef) -> Table {
+    let rows = table_rows(table);
+    let mut grid: Vec<Vec<Option<String>>> = vec![Vec::new(); rows.len()];
+    let mut header_rows = 0;
+    let mut header_possible = true;
+
+    for (r, row) in rows.iter().enumerate() {
+        let mut column = 0;
+        let mut all_th = true;
+        let mut has_cells = false;
+
+        for cell in row_cells(row) {
+            has_cells = true;
+            all_th = all_th && cell.value().name() == "th";
+
+            while grid[r].get(column).is_some_and(|slot| slot.is_some()) {
+                column += 1;
+            }
+
+            let text = collapsed_text(&cell);
+            let colspan = cell_span(&cell, "colspan").unwrap_or(1);
+            let rowspan = cell_span(&cell, "rowspan").unwrap_or(rows.len() - r);
+
+            for line in grid.iter_mut().skip(r).take(rowspan) {
+                if line.len() < column + colspan {
+                    line.resize(column + colspan, None);
+                }
+                for slot in line.iter_mut().skip(column).take(colspan) {
+                    *slot = Some(text.clone());
+                }
+            }
+
+            column += colspan;
+        }
+
+        // the header is the leading thead rows or a first row of th cells.
+        if header_possible && has_cells && (in_thead(row) || (r == 0 && all_th)) {
+            header_rows = r + 1;
+        } else if has_cells {
+            header_possible = false;
+        }
+    }
+
+    let mut grid: Vec<Vec<String>> = grid
+        .into_iter()
+        .map(|row| row.into_iter().map(Option::unwrap_or_default).collect())
+        .collect();
+
+    let columns = grid.iter().map(|row| row.len()).max().unwrap_or_default();
+    let body = grid.split_off(header_rows.min(grid.len()));
+
+    let mut headers: Vec<String> = Vec::new();
+
+    if !grid.is_empty() {
+        headers.resize(columns, String::new());
+
+        // the spanned header rows join into one header per column.
+        for row in grid.iter() {
+            for (header, cell) in headers.iter_mut().zip(row.iter()) {
+                if !cell.is_empty() && !header.ends_with(cell.as_str()) {
+                    if !header.is_empty() {
+                        header.push(' ');
+                    }
+                    header.push_str(cell);
+                }
+            }
+        }
+    }
+
+    let caption = table
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|child| child.value().name() == "caption")
+        .map(|caption| collapsed_text(&caption))
+        .filter(|caption| !caption.is_empty());
+
+    Table {
+        caption,
+        headers,
+        rows: body
+            .into_iter()
+            .filter(|row| row.iter().any(|cell| !cell.is_empty()))
+            .collect(),
+    }
+}
+
+/// Extract every table of the html in document order. Nested tables are extracted separately and their text stays in the outer cell.
+pub fn extract_tables(html: &str) -> Vec<Table> {
+    let document = Html::parse_document(html);
+
+    document
+        .select(&TABLE_SELECTOR)
+        .map(|table| extract_table(&table))
+        .collect()
+}
+
+/// Extract every table of the page.
+pub fn extract_page_tables(res: &Page) -> Vec<Table> {
+    extract_tables(&res.get_html())
+}
@@ -1,5 +1,6 @@
 use html2md::extended::sifter::WhitespaceSifter;
 use lol_html::{element, html_content::TextType, text, RewriteStrSettings};
+use spider::utils::decode_html_entities;
 
 /// extract the text from HTML document.
 pub fn extract_text(html: &str, custom: &Option<std::collections::HashSet<String>>) -> String {
@@ -168,6 +169,186 @@ pub async fn extract_text_streaming(
     extract_text_streaming_with_size(html, custom, 8192).await
 }
 
+/// A link cited in the text.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TextLink {
+    /// The number of the reference marker starting at `1`.
+    pub index: usize,
+    /// The absolute url of the link.
+    pub url: String,
+    /// The text of the first link to the url.
+    pub text: String,
+}
+
+/// The text with the reference markers and the links cited.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CitedText {
+    /// The text with the `[1]` markers after the links and the footnotes of the urls.
+    pub text: String,
+    /// The links in the order of the markers.
+    pub links: Vec<TextLink>,
+}
+
+/// The punctuation kept next to the prior text and markers.
+const CLOSING_PUNCTUATION: [char; 8] = ['.', ',', ';', ':', '!', '?', ')', ']'];
+
+/// The state of the text and links extraction.
+#[derive(Default)]
+struct CitationState {
+    /// The text extracted.
+    text: String,
+    /// The links found.
+    links: Vec<TextLink>,
+}
+
+/// Resolve the href of the link against the page url. Fragments and scripts are not cited.
+fn resolve_link(href: &str, url: &Option<spider::url::Url>) -> Option<String> {
+    let href = href.trim();
+
+    if href.is_empty()
+        || href.starts_with('#')
+        || href
+            .get(..11)
+            .is_some_and(|s| s.eq_ignore_ascii_case("javascript:"))
+    {
+        return None;
+    }
+
+    match url {
+        Some(url) => url.join(href).ok().map(|u| u.to_string()),
+        _ => spider::url::Url::parse(href).ok().map(|u| u.to_string()),
+    }
+}
+
+/// extract the text from HTML document with the `[1]` reference markers after the links and the footnotes of the absolute urls.
+pub fn extract_text_with_links(
+    html: &str,
+    url: &Option<spider::url::Url>,
+    custom: &Option<std::collections::HashSet<String>>,
+) -> CitedText {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let state: Rc<RefCell<CitationState>> = Default::default();
+    let text_state = state.clone();
+    let link_state = state.clone();
+
+    let mut element_content_handlers = Vec::with_capacity(
+        2 + custom
+            .as_ref()
+            .map_or(0, |c| if c.is_empty() { 0 } else { 1 }),
+    );
+
+    if let Some(ignore) = custom {
+        let ignore_handler = element!(
+            ignore.iter().cloned().collect::<Vec<String>>().join(","),
+            |el| {
+                el.remove();
+                Ok(())
+            }
+        );
+
+        element_content_handlers.push(ignore_handler);
+    }
+
+    element_content_handlers.push(element!("a[href]", move |el| {
+        let link = el
+            .get_attribute("href")
+            .and_then(|href| resolve_link(&decode_html_entities(&href), url));
+
+        if let Some(link) = link {
+            let start = link_state.borrow().text.len();
+            let state = link_state.clone();
+
+            let handler: lol_html::EndTagHandler<'static> = Box::new(move |_end| {
+                let mut state = state.borrow_mut();
+                let state = &mut *state;
+                let text = state.text.get(start..).unwrap_or_default().trim();
+
+                if !text.is_empty() {
+                    let index = match state.links.iter().find(|l| l.url == link) {
+                        Some(existing) => existing.index,
+                        _ => {
+                            let index = state.links.len() + 1;
+                            state.links.push(TextLink {
+                                index,
+                                url: link.clone(),
+                                text: text.sift(),
+                            });
+                            index
+                        }
+                    };
+
+                    if !state.text.ends_with(' ') {
+                        state.text.push(' ');
+                    }
+                    state.text.push_str(&format!("[{}]", index));
+                }
+
+                Ok(())
+            });
+
+            if let Some(handlers) = el.end_tag_handlers() {
+                handlers.push(handler);
+            }
+        }
+
+        Ok(())
+    }));
+
+    element_content_handlers.push(text!(
+        "*:not(script):not(style):not(svg):not(noscript):not(nav):not(footer)",
+        move |text| {
+            if let TextType::RCData | TextType::Data = text.text_type() {
+                let extracted_text = &mut text_state.borrow_mut().text;
+                let el_text = text.as_str().trim_start();
+
+                if !el_text.is_empty() {
+                    if !extracted_text.ends_with(' ')
+                        && !extracted_text.is_empty()
+                        && !el_text.starts_with(CLOSING_PUNCTUATION)
+                    {
+                        extracted_text.push(' ');
+                    }
+                    extracted_text.push_str(el_text);
+                }
+                if text.text_type() == TextType::RCData {
+                    extracted_text.push('\n');
+                }
+            }
+
+            Ok(())
+        }
+    ));
+
+    let _ = rewrite_str_empty(
+        html,
+        RewriteStrSettings {
+            element_content_handlers,
+            ..RewriteStrSettings::default()
+        },
+    );
+
+    let state = state.take();
+    let mut text = state.text.sift();
+
+    if !state.links.is_empty() {
+        text.push_str("\n\n");
+
+        for (i, link) in state.links.iter().enumerate() {
+            if i > 0 {
+                text.push('\n');
+            }
+            text.push_str(&format!("[{}] {}", link.index, link.url));
+        }
+    }
+
+    CitedText {
+        text,
+        links: state.links,
+    }
+}
+
 pub fn rewrite_str_empty<'h, 's, H: lol_html::HandlerTypes>(
     html: &str,
     settings: impl Into<lol_html::Settings<'h, 's, H>>,
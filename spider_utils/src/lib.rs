@@ -19,6 +19,10 @@ pub struct DocumentSelectors<K> {
 #[cfg(feature = "transformations")]
 pub use spider_transformations;
 
+/// Declarative schema extraction into JSON.
+#[cfg(feature = "schema")]
+pub mod schema;
+
 /// Extracted content from CSS query selectors.
 type CSSQueryMap = HashMap<String, Vec<String>>;
 
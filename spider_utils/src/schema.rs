@@ -0,0 +1,553 @@
+use crate::is_valid_xpath;
+use lazy_static::lazy_static;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use sxd_document::parser;
+use sxd_xpath::evaluate_xpath;
+
+/// The declarative schema to extract structured data from a page.
+/// ```rust
+/// use spider_utils::schema::{ExtractionSchema, SchemaExtractor};
+///
+/// let schema = ExtractionSchema::from_toml(
+///     r#"
+///     [fields.title]
+///     selector = "h1"
+///     required = true
+///
+///     [fields.products]
+///     selector = ".product"
+///     list = true
+///
+///     [fields.products.fields.name]
+///     selector = ".name"
+///
+///     [fields.products.fields.price]
+///     selector = ".price"
+///     type = "float"
+///
+///     [fields.products.fields.link]
+///     selector = "a@href"
+///     type = "url"
+///     "#,
+/// )
+/// .unwrap();
+///
+/// let extractor = SchemaExtractor::new(&schema).unwrap();
+/// let data = extractor
+///     .extract(
+///         r#"<html><body><h1>Shop</h1>
+///         <div class="product"><span class="name">Spider</span><span class="price">$1,299.50</span><a href="/spider">View</a></div>
+///         </body></html>"#,
+///         Some("https://example.com/shop/"),
+///     )
+///     .unwrap();
+///
+/// assert_eq!(data["title"], "Shop");
+/// assert_eq!(data["products"][0]["price"], 1299.5);
+/// assert_eq!(data["products"][0]["link"], "https://example.com/spider");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractionSchema {
+    /// The fields to extract by the output key.
+    #[serde(default)]
+    pub fields: BTreeMap<String, SchemaField>,
+}
+
+impl ExtractionSchema {
+    /// Load the schema from JSON.
+    pub fn from_json(input: &str) -> Result<Self, SchemaError> {
+        serde_json::from_str(input).map_err(|e| SchemaError::Parse(e.to_string()))
+    }
+
+    /// Load the schema from TOML.
+    pub fn from_toml(input: &str) -> Result<Self, SchemaError> {
+        toml::from_str(input).map_err(|e| SchemaError::Parse(e.to_string()))
+    }
+}
+
+/// A field of the extraction schema.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaField {
+    /// The CSS or XPath selector. Use the `@attr` suffix on CSS selectors to select an attribute, ex: `a@href`.
+    pub selector: String,
+    /// The attribute to select instead of the text.
+    #[serde(default)]
+    pub attr: Option<String>,
+    /// The type to coerce the value into.
+    #[serde(default, rename = "type")]
+    pub field_type: FieldType,
+    /// Collect every match into a list instead of the first match.
+    #[serde(default)]
+    pub list: bool,
+    /// Fail the extraction when the field is missing.
+    #[serde(default)]
+    pub required: bool,
+    /// The regex to post-process the value with. The first capture group is used when set.
+    #[serde(default)]
+    pub regex: Option<String>,
+    /// The value to use when the field is missing.
+    #[serde(default)]
+    pub default: Option<Value>,
+    /// The nested fields to extract relative to each match. Nested objects require a CSS selector.
+    #[serde(default)]
+    pub fields: BTreeMap<String, SchemaField>,
+}
+
+/// The type to coerce a field into.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    /// The text with the whitespace collapsed.
+    #[default]
+    String,
+    /// A whole number.
+    Int,
+    /// A decimal number.
+    Float,
+    /// A boolean from `true`, `yes`, `on` or `1`.
+    Bool,
+    /// A date formatted as `YYYY-MM-DD` or RFC 3339 when the time is known.
+    Date,
+    /// An absolute URL resolved against the page URL.
+    Url,
+}
+
+/// The errors building the schema or extracting the data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The schema could not be parsed.
+    Parse(String),
+    /// The selector is not a valid CSS or XPath selector.
+    InvalidSelector(String),
+    /// The regex is not valid.
+    InvalidRegex(String),
+    /// A required field was not found on the page.
+    MissingField(String),
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::Parse(e) => write!(f, "failed to parse the schema: {}", e),
+            SchemaError::InvalidSelector(s) => write!(f, "invalid selector: {}", s),
+            SchemaError::InvalidRegex(e) => write!(f, "invalid regex: {}", e),
+            SchemaError::MissingField(field) => write!(f, "missing required field: {}", field),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// The compiled selector of a field.
+#[derive(Debug)]
+enum FieldSelector {
+    /// CSS selector.
+    Css(Selector),
+    /// XPath selector.
+    XPath(String),
+}
+
+/// A field with the selectors and regex compiled.
+#[derive(Debug)]
+struct CompiledField {
+    /// The output key.
+    name: String,
+    /// The selector.
+    selector: FieldSelector,
+    /// The attribute to select.
+    attr: Option<String>,
+    /// The type to coerce into.
+    field_type: FieldType,
+    /// Collect every match.
+    list: bool,
+    /// The field is required.
+    required: bool,
+    /// The regex post-processing.
+    regex: Option<regex::Regex>,
+    /// The default value.
+    default: Option<Value>,
+    /// The nested fields.
+    fields: Vec<CompiledField>,
+}
+
+/// Split the `@attr` suffix from a CSS selector.
+fn split_attr(selector: &str) -> (&str, Option<&str>) {
+    let selector = selector.trim();
+
+    if selector.starts_with('/') || selector.starts_with('(') {
+        return (selector, None);
+    }
+
+    match selector.rfind('@') {
+        Some(index)
+            if !selector[index..].contains(']')
+                && selector[index + 1..]
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':') =>
+        {
+            let attr = &selector[index + 1..];
+            let css = selector[..index].trim();
+
+            (
+                if css.is_empty() { "*" } else { css },
+                if attr.is_empty() { None } else { Some(attr) },
+            )
+        }
+        _ => (selector, None),
+    }
+}
+
+/// Compile the fields of the schema.
+fn compile_fields(
+    fields: &BTreeMap<String, SchemaField>,
+    path: &str,
+) -> Result<Vec<CompiledField>, SchemaError> {
+    let mut compiled = Vec::with_capacity(fields.len());
+
+    for (name, field) in fields {
+        let path = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", path, name)
+        };
+        let (selector, attr) = split_attr(&field.selector);
+
+        let selector = match Selector::parse(selector) {
+            Ok(selector) => FieldSelector::Css(selector),
+            _ if is_valid_xpath(selector) && field.fields.is_empty() => {
+                FieldSelector::XPath(selector.to_string())
+            }
+            _ => {
+                return Err(SchemaError::InvalidSelector(format!(
+                    "{} '{}'",
+                    path, field.selector
+                )))
+            }
+        };
+
+        let regex = match field.regex {
+            Some(ref regex) => Some(
+                regex::Regex::new(regex)
+                    .map_err(|e| SchemaError::InvalidRegex(format!("{} {}", path, e)))?,
+            ),
+            _ => None,
+        };
+
+        compiled.push(CompiledField {
+            name: name.clone(),
+            selector,
+            attr: field.attr.clone().or_else(|| attr.map(String::from)),
+            field_type: field.field_type,
+            list: field.list,
+            required: field.required,
+            regex,
+            default: field.default.clone(),
+            fields: compile_fields(&field.fields, &path)?,
+        });
+    }
+
+    Ok(compiled)
+}
+
+/// Extract structured data from pages with a compiled schema.
+#[derive(Debug)]
+pub struct SchemaExtractor {
+    /// The compiled fields.
+    fields: Vec<CompiledField>,
+}
+
+impl SchemaExtractor {
+    /// Compile the schema selectors and regex.
+    pub fn new(schema: &ExtractionSchema) -> Result<Self, SchemaError> {
+        Ok(Self {
+            fields: compile_fields(&schema.fields, "")?,
+        })
+    }
+
+    /// Extract the data from the html into a JSON object. URL fields are resolved against the page url.
+    pub fn extract(&self, html: &str, page_url: Option<&str>) -> Result<Value, SchemaError> {
+        let document = Html::parse_document(html);
+        let base = page_url.and_then(|u| url::Url::parse(u).ok());
+
+        extract_fields(&self.fields, document.root_element(), html, &base, "").map(Value::Object)
+    }
+}
+
+/// Extract the structured data of the html with the schema.
+pub fn extract_schema(
+    html: &str,
+    schema: &ExtractionSchema,
+    page_url: Option<&str>,
+) -> Result<Value, SchemaError> {
+    SchemaExtractor::new(schema)?.extract(html, page_url)
+}
+
+/// Extract the fields relative to the scope element.
+fn extract_fields(
+    fields: &[CompiledField],
+    scope: ElementRef,
+    scope_html: &str,
+    base: &Option<url::Url>,
+    path: &str,
+) -> Result<Map<String, Value>, SchemaError> {
+    let mut map = Map::new();
+
+    for field in fields {
+        let path = if path.is_empty() {
+            field.name.clone()
+        } else {
+            format!("{}.{}", path, field.name)
+        };
+
+        let mut values = Vec::new();
+
+        match field.selector {
+            FieldSelector::Css(ref selector) if !field.fields.is_empty() => {
+                for element in scope.select(selector) {
+                    let html = element.html();
+                    values.push(Value::Object(extract_fields(
+                        &field.fields,
+                        element,
+                        &html,
+                        base,
+                        &path,
+                    )?));
+                    if !field.list {
+                        break;
+                    }
+                }
+            }
+            FieldSelector::Css(ref selector) => {
+                for element in scope.select(selector) {
+                    if let Some(value) = field.value(&element_value(element, &field.attr), base) {
+                        values.push(value);
+                        if !field.list {
+                            break;
+                        }
+                    }
+                }
+            }
+            FieldSelector::XPath(ref selector) => {
+                for raw in xpath_values(scope_html, selector) {
+                    if let Some(value) = field.value(&raw, base) {
+                        values.push(value);
+                        if !field.list {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let value = if field.list && !values.is_empty() {
+            Value::Array(values)
+        } else if let Some(value) = values.into_iter().next() {
+            value
+        } else if field.required {
+            return Err(SchemaError::MissingField(path));
+        } else {
+            field.default.clone().unwrap_or(if field.list {
+                Value::Array(Vec::new())
+            } else {
+                Value::Null
+            })
+        };
+
+        map.insert(field.name.clone(), value);
+    }
+
+    Ok(map)
+}
+
+impl CompiledField {
+    /// Post-process and coerce the raw value.
+    fn value(&self, raw: &str, base: &Option<url::Url>) -> Option<Value> {
+        let raw = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let raw = match self.regex {
+            Some(ref regex) => {
+                let captures = regex.captures(&raw)?;
+                captures
+                    .get(1)
+                    .or_else(|| captures.get(0))?
+                    .as_str()
+                    .to_string()
+            }
+            _ => raw,
+        };
+
+        if raw.is_empty() {
+            return None;
+        }
+
+        coerce(&raw, self.field_type, base)
+    }
+}
+
+/// Get the attribute or the text of the element.
+fn element_value(element: ElementRef, attr: &Option<String>) -> String {
+    match attr {
+        Some(attr) => element.attr(attr).unwrap_or_default().to_string(),
+        _ if element.value().name() == "meta" => {
+            element.attr("content").unwrap_or_default().to_string()
+        }
+        _ => crate::clean_element_text(&element),
+    }
+}
+
+/// Get the string values of the xpath nodes.
+fn xpath_values(html: &str, selector: &str) -> Vec<String> {
+    let mut values = Vec::new();
+
+    if let Ok(package) = parser::parse(html) {
+        let document = package.as_document();
+
+        match evaluate_xpath(&document, selector) {
+            Ok(sxd_xpath::Value::Nodeset(nodes)) => {
+                for node in nodes.document_order() {
+                    values.push(node.string_value());
+                }
+            }
+            Ok(value) => {
+                let value = value.into_string();
+                if !value.is_empty() {
+                    values.push(value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    values
+}
+
+/// The date formats to try.
+const DATE_FORMATS: [&str; 7] = [
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%m/%d/%Y",
+    "%d.%m.%Y",
+    "%B %d, %Y",
+    "%b %d, %Y",
+    "%d %B %Y",
+];
+
+lazy_static! {
+    /// The first integer of the text with the thousands separators.
+    static ref INT_NUMBER: regex::Regex = regex::Regex::new(r"-?\d[\d,]*").unwrap();
+    /// The first decimal number of the text with the thousands separators.
+    static ref FLOAT_NUMBER: regex::Regex =
+        regex::Regex::new(r"-?(?:\d[\d,]*(?:\.\d+)?|\.\d+)").unwrap();
+}
+
+/// The first well formed number of the text without the thousands separators.
+fn first_number(raw: &str, number: &regex::Regex) -> Option<String> {
+    number.find(raw).map(|m| m.as_str().replace(',', ""))
+}
+
+/// Coerce the value into the type.
+fn coerce(raw: &str, field_type: FieldType, base: &Option<url::Url>) -> Option<Value> {
+    match field_type {
+        FieldType::String => Some(Value::String(raw.to_string())),
+        FieldType::Int => first_number(raw, &INT_NUMBER)
+            .and_then(|number| number.parse::<i64>().ok())
+            .map(Value::from),
+        FieldType::Float => first_number(raw, &FLOAT_NUMBER)
+            .and_then(|number| number.parse::<f64>().ok())
+            .map(Value::from),
+        FieldType::Bool => match raw.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(Value::Bool(true)),
+            "false" | "no" | "off" | "0" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        FieldType::Date => {
+            if let Ok(date) = chrono::DateTime::parse_from_rfc3339(raw) {
+                return Some(Value::String(date.to_rfc3339()));
+            }
+            if let Ok(date) = chrono::DateTime::parse_from_rfc2822(raw) {
+                return Some(Value::String(date.to_rfc3339()));
+            }
+            DATE_FORMATS.iter().find_map(|format| {
+                chrono::NaiveDate::parse_from_str(raw, format)
+                    .ok()
+                    .map(|date| Value::String(date.format("%Y-%m-%d").to_string()))
+            })
+        }
+        FieldType::Url => {
+            let url = match base {
+                Some(base) => base.join(raw).ok(),
+                _ => url::Url::parse(raw).ok(),
+            };
+            url.map(|url| Value::String(url.to_string()))
+        }
+    }
+}
+
+#[test]
+fn test_split_attr() {
+    assert_eq!(split_attr("a.more@href"), ("a.more", Some("href")));
+    assert_eq!(split_attr("a[href^='@']"), ("a[href^='@']", None));
+    assert_eq!(split_attr("//a/@href"), ("//a/@href", None));
+}
+
+#[test]
+fn test_extract_schema_json() {
+    let schema = ExtractionSchema::from_json(
+        r#"{
+            "fields": {
+                "title": { "selector": "title", "required": true },
+                "published": { "selector": "meta[name='date']", "type": "date" },
+                "count": { "selector": ".count", "type": "int", "regex": "([0-9,]+) items" },
+                "tags": { "selector": "//li[@class='tag']", "list": true },
+                "missing": { "selector": ".missing", "default": "none" }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let data = extract_schema(
+        r#"<html><head><title>Spider</title><meta name="date" content="March 5, 2024"/></head><body><p class="count">Showing 1,024 items</p><ul><li class="tag">rust</li><li class="tag">crawler</li></ul></body></html>"#,
+        &schema,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(data["title"], "Spider");
+    assert_eq!(data["published"], "2024-03-05");
+    assert_eq!(data["count"], 1024);
+    assert_eq!(data["tags"], serde_json::json!(["rust", "crawler"]));
+    assert_eq!(data["missing"], "none");
+}
+
+#[test]
+fn test_extract_schema_required() {
+    let schema = ExtractionSchema::from_json(
+        r#"{ "fields": { "price": { "selector": ".price", "type": "float", "required": true } } }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        extract_schema(
+            "<html><body><p class=\"price\">N/A</p></body></html>",
+            &schema,
+            None
+        ),
+        Err(SchemaError::MissingField("price".into()))
+    );
+}
+
+#[test]
+fn test_coerce_numbers() {
+    let int = |raw| coerce(raw, FieldType::Int, &None);
+    let float = |raw| coerce(raw, FieldType::Float, &None);
+
+    assert_eq!(int("In stock - 12 left"), Some(Value::from(12)));
+    assert_eq!(int("-1,234 points"), Some(Value::from(-1234)));
+    assert_eq!(int("Sold out"), None);
+    assert_eq!(float("Save - $12.50 today"), Some(Value::from(12.5)));
+    assert_eq!(float("$1,234.50 USD"), Some(Value::from(1234.5)));
+    assert_eq!(float("Now .5 off"), Some(Value::from(0.5)));
+}
@@ -0,0 +1,223 @@
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+lazy_static! {
+    /// The shared secret clients send with the `Proxy-Authorization: Bearer <secret>` header. Set with `SPIDER_WORKER_SECRET`.
+    static ref WORKER_SECRET: Option<String> = std::env::var("SPIDER_WORKER_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .map(|secret| format!("Bearer {}", secret));
+    /// The target hosts allowed to fetch. Set with a comma separated list `SPIDER_WORKER_ALLOWED_HOSTS`, use `*.example.com` to allow subdomains.
+    static ref ALLOWED_HOSTS: Option<Vec<String>> = std::env::var("SPIDER_WORKER_ALLOWED_HOSTS")
+        .ok()
+        .map(|hosts| parse_allowed_hosts(&hosts))
+        .filter(|hosts| !hosts.is_empty());
+}
+
+/// The worker is ready to handle request.
+static READY: AtomicBool = AtomicBool::new(false);
+
+/// The request is missing the shared secret.
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// The target host is not in the allow-list.
+#[derive(Debug)]
+struct ForbiddenHost;
+
+impl warp::reject::Reject for ForbiddenHost {}
+
+/// The page could not be rendered with chrome.
+#[cfg(feature = "chrome")]
+#[derive(Debug)]
+pub struct RenderUnavailable;
+
+#[cfg(feature = "chrome")]
+impl warp::reject::Reject for RenderUnavailable {}
+
+/// Parse the comma separated allow-list of hosts.
+fn parse_allowed_hosts(hosts: &str) -> Vec<String> {
+    hosts
+        .split(',')
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .collect()
+}
+
+/// Get the host name without the scheme, path, or port.
+fn host_name(host: &str) -> String {
+    let host = match host.find("://") {
+        Some(index) => &host[index + 3..],
+        _ => host,
+    };
+    let host = host.split('/').next().unwrap_or_default();
+    let host = match host.rfind(':') {
+        Some(index) if !host.ends_with(']') => &host[..index],
+        _ => host,
+    };
+
+    host.to_ascii_lowercase()
+}
+
+/// Compare the values in constant time.
+fn secure_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// The proxy authorization matches the shared secret. All request are allowed without a secret set.
+fn is_authorized(authorization: Option<&str>) -> bool {
+    match WORKER_SECRET.as_deref() {
+        Some(secret) => match authorization {
+            Some(authorization) => secure_eq(authorization.as_bytes(), secret.as_bytes()),
+            _ => false,
+        },
+        _ => true,
+    }
+}
+
+/// The host is allowed to fetch.
+fn is_host_allowed(allowed_hosts: &Option<Vec<String>>, host: &str) -> bool {
+    match allowed_hosts {
+        Some(allowed_hosts) => {
+            let host = host_name(host);
+
+            allowed_hosts
+                .iter()
+                .any(|allowed| match allowed.strip_prefix("*.") {
+                    Some(domain) => {
+                        host.len() > domain.len() + 1
+                            && host.ends_with(domain)
+                            && host[..host.len() - domain.len()].ends_with('.')
+                    }
+                    _ => host == *allowed,
+                })
+        }
+        _ => true,
+    }
+}
+
+/// Reject request without the shared secret or with a target host outside of the allow-list.
+pub fn authorize() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>("proxy-authorization")
+        .and(warp::header::<String>("host"))
+        .and_then(|authorization: Option<String>, host: String| async move {
+            if !is_authorized(authorization.as_deref()) {
+                Err(warp::reject::custom(Unauthorized))
+            } else if !is_host_allowed(&ALLOWED_HOSTS, &host) {
+                Err(warp::reject::custom(ForbiddenHost))
+            } else {
+                Ok(())
+            }
+        })
+        .untuple_one()
+}
+
+/// Map the rejections to a status code.
+pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
+    #[cfg(feature = "chrome")]
+    if err.find::<RenderUnavailable>().is_some() {
+        return Ok(warp::reply::with_status(
+            "Service Unavailable",
+            StatusCode::SERVICE_UNAVAILABLE,
+        ));
+    }
+
+    let status = if err.find::<Unauthorized>().is_some() {
+        StatusCode::PROXY_AUTHENTICATION_REQUIRED
+    } else if err.find::<ForbiddenHost>().is_some() {
+        StatusCode::FORBIDDEN
+    } else if err.is_not_found() {
+        StatusCode::NOT_FOUND
+    } else {
+        StatusCode::BAD_REQUEST
+    };
+
+    Ok(warp::reply::with_status(
+        status.canonical_reason().unwrap_or_default(),
+        status,
+    ))
+}
+
+/// Mark the worker ready to handle request.
+pub fn set_ready(ready: bool) {
+    READY.store(ready, Ordering::Relaxed);
+}
+
+/// Start the `/health` and `/ready` endpoints for orchestration. Use `SPIDER_WORKER_HEALTH_PORT` to adjust the port, defaults to `3032`.
+pub async fn serve_health() {
+    if WORKER_SECRET.is_none() {
+        spider::utils::log(
+            "Spider_Worker SPIDER_WORKER_SECRET is not set, request are not authenticated.",
+            "",
+        );
+    }
+
+    let health = warp::path("health").and(warp::path::end()).map(|| "ok");
+    let ready = warp::path("ready").and(warp::path::end()).map(|| {
+        if READY.load(Ordering::Relaxed) {
+            warp::reply::with_status("ready", StatusCode::OK)
+        } else {
+            warp::reply::with_status("not ready", StatusCode::SERVICE_UNAVAILABLE)
+        }
+    });
+
+    let port: u16 = std::env::var("SPIDER_WORKER_HEALTH_PORT")
+        .unwrap_or_else(|_| "3032".into())
+        .parse()
+        .unwrap_or(3032);
+
+    spider::utils::log(
+        "Spider_Worker health starting at 0.0.0.0:",
+        port.to_string(),
+    );
+
+    warp::serve(health.or(ready))
+        .run(([0, 0, 0, 0], port))
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_name() {
+        assert_eq!(host_name("Example.com:443"), "example.com");
+        assert_eq!(host_name("https://example.com/path"), "example.com");
+        assert_eq!(host_name("[::1]"), "[::1]");
+    }
+
+    #[test]
+    fn test_allowed_hosts() {
+        let allowed = Some(parse_allowed_hosts("example.com, *.spider.cloud"));
+
+        assert!(is_host_allowed(&allowed, "example.com:80"));
+        assert!(is_host_allowed(&allowed, "docs.spider.cloud"));
+        assert!(!is_host_allowed(&allowed, "spider.cloud"));
+        assert!(!is_host_allowed(&allowed, "evilspider.cloud"));
+        assert!(!is_host_allowed(&allowed, "example.com.evil.com"));
+        assert!(is_host_allowed(&None, "anything.com"));
+    }
+
+    #[test]
+    fn test_secure_eq() {
+        assert!(secure_eq(b"Bearer secret", b"Bearer secret"));
+        assert!(!secure_eq(b"Bearer secret", b"Bearer secreT"));
+        assert!(!secure_eq(b"Bearer", b"Bearer secret"));
+    }
+
+    #[cfg(feature = "chrome")]
+    #[spider::tokio::test(crate = "spider::tokio")]
+    async fn test_render_unavailable_status() {
+        let reply = handle_rejection(warp::reject::custom(RenderUnavailable))
+            .await
+            .unwrap()
+            .into_response();
+
+        assert_eq!(reply.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
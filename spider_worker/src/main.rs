@@ -6,6 +6,10 @@ use warp::{path::FullPath, Filter};
 #[macro_use]
 extern crate lazy_static;
 
+mod guard;
+#[cfg(feature = "chrome")]
+mod render;
+
 lazy_static! {
     /// top level request client to re-use
     static ref CLIENT: spider::Client = {
@@ -17,13 +21,43 @@ lazy_static! {
     };
 }
 
+/// Initialize the shared client and start the health endpoints.
+fn setup_worker() {
+    lazy_static::initialize(&CLIENT);
+    tokio::spawn(guard::serve_health());
+}
+
+/// The chrome render options of the request headers.
+#[cfg(all(not(feature = "scrape"), feature = "chrome"))]
+fn render_options() -> impl Filter<
+    Extract = (Option<spider::features::decentralized_headers::WorkerRenderOptions>,),
+    Error = Infallible,
+> + Clone {
+    warp::header::headers_cloned().map(|headers: warp::http::HeaderMap| {
+        spider::features::decentralized_headers::WorkerRenderOptions::from_pairs(
+            headers
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_bytes())),
+        )
+    })
+}
+
+/// The chrome render options are not used without the `chrome` feature.
+#[cfg(all(not(feature = "scrape"), not(feature = "chrome")))]
+fn render_options() -> impl Filter<Extract = (), Error = Infallible> + Clone {
+    warp::any()
+}
+
 /// forward request to get resources
 #[cfg(not(feature = "scrape"))]
 async fn forward(
     path: FullPath,
     host: String,
     referer: Option<String>,
-) -> Result<impl warp::Reply, Infallible> {
+    #[cfg(feature = "chrome")] render: Option<
+        spider::features::decentralized_headers::WorkerRenderOptions,
+    >,
+) -> Result<impl warp::Reply, warp::Rejection> {
     use spider::{
         flexbuffers,
         page::build,
@@ -51,6 +85,16 @@ async fn forward(
         _ => (false, false),
     };
 
+    #[cfg(feature = "chrome")]
+    if let Some(options) = render {
+        // the crawler fails over to another worker on the error status.
+        let (page, rendered) = render::render(&url_path, &CLIENT, &options, subdomains, tld)
+            .await
+            .ok_or_else(|| warp::reject::custom(guard::RenderUnavailable))?;
+
+        return pack(page, render::rendered_payload(&rendered));
+    }
+
     let mut page = build("", Default::default());
 
     let extracted = {
@@ -85,7 +129,10 @@ async fn forward(
 
     #[cfg(feature = "headers")]
     /// Return the response with the header information.
-    fn pack(page: spider::page::Page, extracted: Vec<u8>) -> Result<impl warp::Reply, Infallible> {
+    fn pack(
+        page: spider::page::Page,
+        extracted: Vec<u8>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
         use spider::features::decentralized_headers::WorkerProxyHeaderBuilder;
         use warp::http::{Response, StatusCode};
 
@@ -125,7 +172,10 @@ async fn forward(
 
     #[cfg(not(feature = "headers"))]
     /// Return the response.
-    fn pack(_page: spider::page::Page, extracted: Vec<u8>) -> Result<impl warp::Reply, Infallible> {
+    fn pack(
+        _page: spider::page::Page,
+        extracted: Vec<u8>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
         Ok(extracted)
     }
 
@@ -244,15 +294,19 @@ async fn test1(domain: &str) {
 ))]
 async fn main() {
     env_logger::init();
+    setup_worker();
     test1("https://www.heygoody.com").await;
     // test2(domain).await;
     let host = warp::header::<String>("host");
     let referer = warp::header::optional::<String>("referer");
 
-    let routes = warp::path::full()
+    let routes = guard::authorize()
+        .and(warp::path::full())
         .and(host)
         .and(referer)
+        .and(render_options())
         .and_then(forward)
+        .recover(guard::handle_rejection)
         .boxed();
 
     let port: u16 = std::env::var("SPIDER_WORKER_PORT")
@@ -262,15 +316,23 @@ async fn main() {
 
     utils::log("Spider_Worker starting at 0.0.0.0:", port.to_string());
 
-    warp::serve(routes).run(([0, 0, 0, 0], port)).await;
+    let (_, server) = warp::serve(routes).bind_ephemeral(([0, 0, 0, 0], port));
+    guard::set_ready(true);
+    server.await;
 }
 
 #[tokio::main]
 #[cfg(all(feature = "scrape", not(feature = "tls"),))]
 async fn main() {
     env_logger::init();
+    setup_worker();
     let host = warp::header::<String>("host");
-    let routes = warp::path::full().and(host).and_then(scrape).boxed();
+    let routes = guard::authorize()
+        .and(warp::path::full())
+        .and(host)
+        .and_then(scrape)
+        .recover(guard::handle_rejection)
+        .boxed();
     let port: u16 = std::env::var("SPIDER_WORKER_SCRAPER_PORT")
         .unwrap_or_else(|_| "3031".into())
         .parse()
@@ -278,7 +340,9 @@ async fn main() {
 
     utils::log("Spider_Worker starting at 0.0.0.0:", &port.to_string());
 
-    warp::serve(routes).run(([0, 0, 0, 0], port)).await;
+    let (_, server) = warp::serve(routes).bind_ephemeral(([0, 0, 0, 0], port));
+    guard::set_ready(true);
+    server.await;
 }
 
 #[tokio::main]
@@ -289,17 +353,26 @@ async fn main() {
 ))]
 async fn main() {
     env_logger::init();
+    setup_worker();
     let host = warp::header::<String>("host");
     let referer = warp::header::optional::<String>("referer");
-    let routes = warp::path::full()
+    let routes = guard::authorize()
+        .and(warp::path::full())
         .and(host)
         .and(referer)
+        .and(render_options())
         .and_then(forward)
+        .recover(guard::handle_rejection)
         .boxed();
 
-    tokio::spawn(async {
+    let scraper = {
         let host = warp::header::<String>("host");
-        let routes = warp::path::full().and(host).and_then(scrape).boxed();
+        let routes = guard::authorize()
+            .and(warp::path::full())
+            .and(host)
+            .and_then(scrape)
+            .recover(guard::handle_rejection)
+            .boxed();
         let port: u16 = std::env::var("SPIDER_WORKER_SCRAPER_PORT")
             .unwrap_or_else(|_| "3031".into())
             .parse()
@@ -310,8 +383,10 @@ async fn main() {
             &port.to_string(),
         );
 
-        warp::serve(routes).run(([0, 0, 0, 0], port)).await;
-    });
+        warp::serve(routes).bind_ephemeral(([0, 0, 0, 0], port)).1
+    };
+
+    tokio::spawn(scraper);
 
     let port: u16 = std::env::var("SPIDER_WORKER_PORT")
         .unwrap_or_else(|_| "3030".into())
@@ -319,7 +394,9 @@ async fn main() {
         .unwrap_or_else(|_| 3030);
     utils::log("Spider_Worker starting at 0.0.0.0:", &port.to_string());
 
-    warp::serve(routes).run(([0, 0, 0, 0], port)).await;
+    let (_, server) = warp::serve(routes).bind_ephemeral(([0, 0, 0, 0], port));
+    guard::set_ready(true);
+    server.await;
 }
 
 // tls handling
@@ -332,14 +409,18 @@ async fn main() {
 ))]
 async fn main() {
     env_logger::init();
+    setup_worker();
 
     let host = warp::header::<String>("host");
     let referer = warp::header::optional::<String>("referer");
 
-    let routes = warp::path::full()
+    let routes = guard::authorize()
+        .and(warp::path::full())
         .and(host)
         .and(referer)
+        .and(render_options())
         .and_then(forward)
+        .recover(guard::handle_rejection)
         .boxed();
 
     let port: u16 = std::env::var("SPIDER_WORKER_PORT")
@@ -354,20 +435,28 @@ async fn main() {
     let rsa_key: String =
         std::env::var("SPIDER_WORKER_KEY_PATH").unwrap_or_else(|_| "/key.rsa".into());
 
-    warp::serve(routes)
+    let (_, server) = warp::serve(routes)
         .tls()
         .cert_path(pem_cert)
         .key_path(rsa_key)
-        .run(([0, 0, 0, 0], port))
-        .await;
+        .bind_ephemeral(([0, 0, 0, 0], port));
+
+    guard::set_ready(true);
+    server.await;
 }
 
 #[tokio::main]
 #[cfg(all(feature = "scrape", feature = "tls"))]
 async fn main() {
     env_logger::init();
+    setup_worker();
     let host = warp::header::<String>("host");
-    let routes = warp::path::full().and(host).and_then(scrape).boxed();
+    let routes = guard::authorize()
+        .and(warp::path::full())
+        .and(host)
+        .and_then(scrape)
+        .recover(guard::handle_rejection)
+        .boxed();
     let port: u16 = std::env::var("SPIDER_WORKER_SCRAPER_PORT")
         .unwrap_or_else(|_| "3031".into())
         .parse()
@@ -380,29 +469,40 @@ async fn main() {
     let rsa_key: String =
         std::env::var("SPIDER_WORKER_KEY_PATH").unwrap_or_else(|_| "/key.rsa".into());
 
-    warp::serve(routes)
+    let (_, server) = warp::serve(routes)
         .tls()
         .cert_path(pem_cert)
         .key_path(rsa_key)
-        .run(([0, 0, 0, 0], port))
-        .await;
+        .bind_ephemeral(([0, 0, 0, 0], port));
+
+    guard::set_ready(true);
+    server.await;
 }
 
 #[tokio::main]
 #[cfg(all(not(feature = "scrape"), feature = "full_resources", feature = "tls"))]
 async fn main() {
     env_logger::init();
+    setup_worker();
     let host = warp::header::<String>("host");
     let referer = warp::header::optional::<String>("referer");
-    let routes = warp::path::full()
+    let routes = guard::authorize()
+        .and(warp::path::full())
         .and(host)
         .and(referer)
+        .and(render_options())
         .and_then(forward)
+        .recover(guard::handle_rejection)
         .boxed();
 
-    tokio::spawn(async {
+    let scraper = {
         let host = warp::header::<String>("host");
-        let routes = warp::path::full().and(host).and_then(scrape).boxed();
+        let routes = guard::authorize()
+            .and(warp::path::full())
+            .and(host)
+            .and_then(scrape)
+            .recover(guard::handle_rejection)
+            .boxed();
         let port: u16 = std::env::var("SPIDER_WORKER_SCRAPER_PORT")
             .unwrap_or_else(|_| "3031".into())
             .parse()
@@ -413,8 +513,10 @@ async fn main() {
             &port.to_string(),
         );
 
-        warp::serve(routes).run(([0, 0, 0, 0], port)).await;
-    });
+        warp::serve(routes).bind_ephemeral(([0, 0, 0, 0], port)).1
+    };
+
+    tokio::spawn(scraper);
 
     let port: u16 = std::env::var("SPIDER_WORKER_PORT")
         .unwrap_or_else(|_| "3030".into())
@@ -428,10 +530,12 @@ async fn main() {
     let rsa_key: String =
         std::env::var("SPIDER_WORKER_KEY_PATH").unwrap_or_else(|_| "/key.rsa".into());
 
-    warp::serve(routes)
+    let (_, server) = warp::serve(routes)
         .tls()
         .cert_path(pem_cert)
         .key_path(rsa_key)
-        .run(([0, 0, 0, 0], port))
-        .await;
+        .bind_ephemeral(([0, 0, 0, 0], port));
+
+    guard::set_ready(true);
+    server.await;
 }
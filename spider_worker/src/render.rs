@@ -0,0 +1,203 @@
+use spider::configuration::{
+    BrowserPoolConfig, Configuration, ScreenShotConfig, ScreenshotParams, WaitFor,
+};
+use spider::features::chrome_common::{
+    convert_to_trie_execution_scripts, ExecutionScripts, WaitForDelay, WaitForIdleNetwork,
+    WaitForSelector,
+};
+use spider::features::chrome_pool::BrowserPool;
+use spider::features::decentralized_headers::{WorkerRenderOptions, WorkerRenderedPage};
+use spider::hashbrown::HashMap;
+use std::time::Duration;
+
+/// The max time to wait for the selector.
+const SELECTOR_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The env var as a number.
+fn env_usize(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(default)
+        .max(1)
+}
+
+lazy_static! {
+    /// The browser configuration. Set `CHROME_URL` to connect to a remote chrome instance.
+    static ref CONFIG: Configuration = Configuration::new();
+    /// The browser pool relaunching the crashed browsers. Set `SPIDER_WORKER_CHROME_BROWSERS` for the amount of browsers, defaults to `1`, and `SPIDER_WORKER_CHROME_TABS` for the max tabs open at once on each browser, defaults to `10`.
+    static ref POOL: BrowserPool = BrowserPool::new(
+        BrowserPoolConfig::new(
+            env_usize("SPIDER_WORKER_CHROME_BROWSERS", 1),
+            env_usize("SPIDER_WORKER_CHROME_TABS", 10),
+        ),
+        &CONFIG,
+        &None,
+    );
+}
+
+/// The wait for configuration of the render.
+fn wait_for(options: &WorkerRenderOptions) -> Option<WaitFor> {
+    if options.wait_for_selector.is_none()
+        && options.wait_for_idle_network.is_none()
+        && options.wait_for_delay.is_none()
+    {
+        return None;
+    }
+
+    Some(WaitFor {
+        selector: options
+            .wait_for_selector
+            .as_ref()
+            .map(|selector| WaitForSelector::new(Some(SELECTOR_TIMEOUT), selector.clone())),
+        idle_network: options
+            .wait_for_idle_network
+            .map(|timeout| WaitForIdleNetwork::new(Some(timeout))),
+        delay: options
+            .wait_for_delay
+            .map(|timeout| WaitForDelay::new(Some(timeout))),
+        ..Default::default()
+    })
+}
+
+/// The screenshot configuration returning the bytes.
+fn screenshot(options: &WorkerRenderOptions) -> Option<ScreenShotConfig> {
+    if options.screenshot {
+        Some(ScreenShotConfig::new(
+            ScreenshotParams::new(Default::default(), Some(options.full_page), None),
+            true,
+            false,
+            None,
+        ))
+    } else {
+        None
+    }
+}
+
+/// The script to run on the page.
+fn execution_scripts(url: &str, options: &WorkerRenderOptions) -> Option<ExecutionScripts> {
+    options.script.as_ref().and_then(|script| {
+        let mut scripts = HashMap::new();
+        scripts.insert(url.to_string(), script.clone());
+        convert_to_trie_execution_scripts(&Some(scripts))
+    })
+}
+
+/// Render the page with chrome and gather the html, links and screenshot. Returns `None` when chrome could not be launched, no tab could be opened or the browser crashed during the render.
+pub async fn render(
+    url: &str,
+    client: &spider::Client,
+    options: &WorkerRenderOptions,
+    subdomains: bool,
+    tld: bool,
+) -> Option<(spider::page::Page, WorkerRenderedPage)> {
+    let tab = match POOL.acquire().await {
+        Some(tab) => tab,
+        _ => {
+            spider::utils::log("- unable to open a chrome tab for", url);
+            return None;
+        }
+    };
+
+    let mut page = spider::page::Page::new(
+        url,
+        client,
+        &tab.page,
+        &wait_for(options),
+        &screenshot(options),
+        false,
+        &None,
+        &execution_scripts(url, options),
+        &None,
+        &CONFIG.viewport,
+        &CONFIG.request_timeout,
+        &None,
+    )
+    .await;
+
+    if POOL.release(tab).await {
+        spider::utils::log("- chrome crashed rendering", url);
+        return None;
+    }
+
+    let selectors = spider::page::get_page_selectors(url, subdomains, tld);
+
+    let rendered = WorkerRenderedPage {
+        links: page.links(&selectors, &None).await,
+        html: page.get_bytes().map(|b| b.to_vec()),
+        screenshot: page.screenshot_bytes.take(),
+        status_code: page.status_code.as_u16(),
+    };
+
+    Some((page, rendered))
+}
+
+/// The flexbuffers payload of the rendered page returned to the crawler.
+pub fn rendered_payload(rendered: &WorkerRenderedPage) -> Vec<u8> {
+    use spider::serde::Serialize;
+
+    let mut s = spider::flexbuffers::FlexbufferSerializer::new();
+    let _ = rendered.serialize(&mut s);
+
+    s.take_buffer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_options() {
+        let options = WorkerRenderOptions {
+            wait_for_selector: Some("#app".into()),
+            wait_for_delay: Some(Duration::from_millis(500)),
+            screenshot: true,
+            full_page: true,
+            script: Some("document.title = 'spider';".into()),
+            ..Default::default()
+        };
+
+        let wait = wait_for(&options).unwrap();
+
+        assert_eq!(wait.selector.map(|s| s.selector), Some("#app".to_string()));
+        assert!(wait.idle_network.is_none());
+        assert!(wait.delay.is_some());
+        assert!(wait_for(&WorkerRenderOptions::new()).is_none());
+
+        let screenshot = screenshot(&options).unwrap();
+
+        assert!(screenshot.bytes);
+        assert!(!screenshot.save);
+        assert_eq!(screenshot.params.full_page, Some(true));
+        assert!(super::screenshot(&WorkerRenderOptions::new()).is_none());
+
+        assert!(execution_scripts("https://choosealicense.com", &options).is_some());
+        assert!(
+            execution_scripts("https://choosealicense.com", &WorkerRenderOptions::new()).is_none()
+        );
+    }
+
+    #[test]
+    fn test_rendered_payload() {
+        use spider::serde::Deserialize;
+
+        let rendered = WorkerRenderedPage {
+            links: ["https://choosealicense.com/about/".into()]
+                .into_iter()
+                .collect(),
+            html: Some(b"<html><body>rendered</body></html>".to_vec()),
+            screenshot: Some(vec![137, 80, 78, 71]),
+            status_code: 200,
+        };
+
+        let payload = rendered_payload(&rendered);
+        let root = spider::flexbuffers::Reader::get_root(payload.as_slice()).unwrap();
+
+        // the crawler tries the links only payload first.
+        assert!(
+            spider::hashbrown::HashSet::<spider::CaseInsensitiveString>::deserialize(root.clone())
+                .is_err()
+        );
+        assert_eq!(WorkerRenderedPage::deserialize(root).unwrap(), rendered);
+    }
+}